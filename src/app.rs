@@ -1,10 +1,12 @@
 //! Main rendering and event processing for the application.
 
 use std::collections::HashSet;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
 
-use crate::config::{Config, Peaks, TabKind};
+use crate::config::{self, Config, Peaks, TabKind};
 use crate::wirehose::state::CaptureEligibility;
 use crate::wirehose::{
     media_class, CommandSender, Event as PipewireEvent, PeakProcessor,
@@ -15,24 +17,38 @@ use anyhow::{anyhow, Result};
 
 use ratatui::{
     layout::Flex,
-    prelude::{Buffer, Constraint, Direction, Layout, Position, Rect},
+    prelude::{
+        Alignment, Buffer, Constraint, Direction, Layout, Position, Rect,
+    },
     text::{Line, Span},
     widgets::{Clear, StatefulWidget, Widget},
     DefaultTerminal, Frame,
 };
 
 use crossterm::event::{
-    Event as CrosstermEvent, KeyEvent, KeyEventKind, MouseButton, MouseEvent,
-    MouseEventKind,
+    Event as CrosstermEvent, KeyEvent, KeyEventKind, KeyModifiers, MouseButton,
+    MouseEvent, MouseEventKind,
 };
 
 use serde::Deserialize;
 use smallvec::{smallvec, SmallVec};
 
+use crate::clipboard;
+use crate::details_widget::{Details, DetailsWidget, DetailsWidgetState};
 use crate::device_kind::DeviceKind;
+use crate::dropdown_widget::{DropdownList, DropdownListState};
+use crate::error::ErrorKind;
 use crate::event::Event;
+use crate::headless;
 use crate::help::{HelpWidget, HelpWidgetState};
-use crate::object_list::{ObjectList, ObjectListWidget};
+use crate::hook;
+use crate::mute_memory::MuteMemory;
+use crate::object_list::{Normalize, ObjectList, ObjectListWidget};
+use crate::osc;
+use crate::profile_defaults::ProfileDefaults;
+use crate::remote_picker::RemotePicker;
+use crate::saved_volumes::SavedVolumes;
+use crate::target_history::TargetHistory;
 use crate::view::{self, ListKind, View};
 use crate::wirehose::{state::State, ObjectId};
 
@@ -43,7 +59,7 @@ use crate::wirehose::{state::State, ObjectId};
 /// Also generated by interaction with [`MouseArea`]s.
 ///
 /// The ordering of variants is used in the help screen.
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Deserialize, PartialEq, PartialOrd)]
 pub enum Action {
     Help,
     Exit,
@@ -52,16 +68,56 @@ pub enum Action {
     ToggleMute,
     SetRelativeVolume(f32),
     SetDefault,
+    CycleDefault,
+    SelectDefault,
+    ToggleTarget,
+    NextTarget,
+    PrevTarget,
+    ToggleExpanded,
+    Focus,
+    SetBalance(f32),
+    ToggleBalanceLink,
+    CenterBalance,
+    StartSearch,
+    CycleSort,
+    EnterVolume,
+    ClearClip,
+    ResetPeakHold,
+    ToggleGroup,
+    ResetVolume,
+    ToggleLock,
+    TogglePin,
+    TogglePeaks,
+    MuteAll,
+    NormalizeGroup,
+    ToggleMonitorSources,
+    ToggleHiddenStreams,
+    ToggleStatusBar,
+    ToggleIds,
+    ShowDetails,
+    CopyName,
+    NextTheme,
+    NextCharSet,
+    PickRemote,
+    RereadParams,
+    EditMetadata,
     ActivateDropdown,
     CloseDropdown,
     TabLeft,
     TabRight,
     SelectTab(usize),
     SetAbsoluteVolume(f32),
+    ApplyPreset(String),
     #[serde(skip_deserializing)]
     SelectObject(ObjectId),
     #[serde(skip_deserializing)]
     SetTarget(view::Target),
+    #[serde(skip_deserializing)]
+    SetChannelVolume(usize, f32),
+    #[serde(skip_deserializing)]
+    SelectRemote(usize),
+    #[serde(skip_deserializing)]
+    SelectRoute(usize),
     // This can be used to delete a default keybinding - make it do nothing.
     Nothing,
 }
@@ -69,7 +125,7 @@ pub enum Action {
 impl std::fmt::Display for Action {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Action::SelectTab(tab) => write!(f, "Select {tab} tab"),
+            Action::SelectTab(tab) => write!(f, "Select tab {}", tab + 1),
             Action::MoveUp => write!(f, "Move cursor up"),
             Action::MoveDown => write!(f, "Move cursor down"),
             Action::TabLeft => write!(f, "Select previous tab"),
@@ -88,6 +144,60 @@ impl std::fmt::Display for Action {
                 Self::format_relative_volume(f, *vol)
             }
             Action::SetDefault => write!(f, "Set default"),
+            Action::CycleDefault => write!(f, "Make next default"),
+            Action::SelectDefault => write!(f, "Go to default"),
+            Action::ToggleTarget => write!(f, "Toggle previous target"),
+            Action::NextTarget => write!(f, "Next target"),
+            Action::PrevTarget => write!(f, "Previous target"),
+            Action::ToggleExpanded => write!(f, "Show/hide channels"),
+            Action::Focus => write!(f, "Focus/unfocus channels"),
+            Action::SetBalance(delta) if *delta >= 0.0 => write!(
+                f,
+                "Shift balance right by {}%",
+                Self::format_percentage(*delta)
+            ),
+            Action::SetBalance(delta) => write!(
+                f,
+                "Shift balance left by {}%",
+                Self::format_percentage(-delta)
+            ),
+            Action::ToggleBalanceLink => write!(f, "Link/unlink balance"),
+            Action::CenterBalance => write!(f, "Center balance"),
+            Action::StartSearch => write!(f, "Search"),
+            Action::CycleSort => write!(f, "Change sort order"),
+            Action::EnterVolume => write!(f, "Type volume"),
+            Action::ClearClip => write!(f, "Clear clip indicators"),
+            Action::ResetPeakHold => write!(f, "Reset peak hold"),
+            Action::ToggleGroup => write!(f, "Expand/collapse group"),
+            Action::ResetVolume => write!(f, "Reset volume to 100%"),
+            Action::ToggleLock => write!(f, "Lock/unlock volume"),
+            Action::TogglePin => write!(f, "Pin/unpin meter"),
+            Action::TogglePeaks => write!(f, "Cycle peak meters"),
+            Action::MuteAll => write!(f, "Mute/unmute all"),
+            Action::NormalizeGroup => write!(f, "Level all volumes"),
+            Action::ToggleMonitorSources => {
+                write!(f, "Show/hide monitor sources")
+            }
+            Action::ToggleHiddenStreams => {
+                write!(f, "Show/hide muted and idle streams")
+            }
+            Action::ToggleStatusBar => write!(f, "Show/hide status bar"),
+            Action::ToggleIds => write!(f, "Show/hide object IDs"),
+            Action::ShowDetails => write!(f, "Show properties"),
+            Action::CopyName => write!(f, "Copy node name"),
+            Action::NextTheme => write!(f, "Next theme"),
+            Action::NextCharSet => write!(f, "Next character set"),
+            Action::PickRemote => write!(f, "Switch remote"),
+            Action::RereadParams => write!(f, "Re-read parameters"),
+            Action::EditMetadata => write!(f, "Edit raw metadata"),
+            Action::ApplyPreset(name) => write!(f, "Apply preset {name}"),
+            Action::SetChannelVolume(index, vol) => write!(
+                f,
+                "Set channel {index} volume to {}%",
+                Self::format_percentage(*vol)
+            ),
+            Action::SelectRemote(index) => write!(f, "Select remote {index}"),
+            Action::SelectRoute(index) => write!(f, "Select route {index}"),
             Action::Help => write!(f, "Show/hide help"),
             Action::Exit => write!(f, "Exit wiremix"),
             Action::Nothing => write!(f, "Nothing"),
@@ -96,6 +206,57 @@ impl std::fmt::Display for Action {
 }
 
 impl Action {
+    /// Does this action change a volume, mute state, or default?
+    fn is_adjustment(&self) -> bool {
+        matches!(
+            self,
+            Action::ToggleMute
+                | Action::SetRelativeVolume(_)
+                | Action::SetAbsoluteVolume(_)
+                | Action::SetChannelVolume(..)
+                | Action::SetDefault
+                | Action::CycleDefault
+                | Action::SetBalance(_)
+                | Action::CenterBalance
+                | Action::ResetVolume
+                | Action::MuteAll
+                | Action::ApplyPreset(_)
+        )
+    }
+
+    /// Objects PipeWire will report a change on once an adjustment has been
+    /// processed.
+    fn adjusted_objects(&self, app: &App) -> Vec<ObjectId> {
+        match self {
+            // Default changes are reported as affecting object 0.
+            Action::SetDefault | Action::CycleDefault => {
+                vec![ObjectId::from_raw_id(0)]
+            }
+            // Bulk adjustments can touch any node.
+            Action::MuteAll | Action::ApplyPreset(_) => {
+                app.view.nodes.keys().copied().collect()
+            }
+            _ => {
+                let list = &app.tabs[app.current_tab_index].list;
+                let Some(object_id) = list.selected else {
+                    return Vec::new();
+                };
+                let Some(node) = app.view.nodes.get(&object_id) else {
+                    // A device
+                    return vec![object_id];
+                };
+                let mut objects = match &node.group {
+                    Some(group) => group.members.clone(),
+                    None => vec![object_id],
+                };
+                // Device node changes are made through the device's route.
+                objects
+                    .extend(node.device_info.map(|(device_id, ..)| device_id));
+                objects
+            }
+        }
+    }
+
     fn format_percentage(vol: f32) -> u16 {
         (vol * 100.0).trunc() as u16
     }
@@ -126,39 +287,79 @@ impl Tab {
     fn new(title: String, list: ObjectList) -> Self {
         Self { title, list }
     }
+
+    fn default_title(tab_kind: TabKind) -> &'static str {
+        match tab_kind {
+            TabKind::Playback => "Playback",
+            TabKind::Recording => "Recording",
+            TabKind::Output => "Output Devices",
+            TabKind::Input => "Input Devices",
+            TabKind::Configuration => "Configuration",
+            TabKind::Cards => "Cards",
+            TabKind::All => "All",
+            TabKind::Streams => "Streams",
+        }
+    }
+
+    /// Applies the parts of the configuration that affect an existing tab.
+    fn configure(&mut self, tab_kind: TabKind, config: &Config) {
+        self.title = match config.tab_titles.get(&tab_kind) {
+            Some(title) => title.clone(),
+            None => String::from(Self::default_title(tab_kind)),
+        };
+        self.list.meter_orientation = config.meter_orientation;
+        self.list.node_layout = config.node_layout;
+        self.list.peaks = config.peaks_for(tab_kind);
+        self.list.confirm_profile_change = config.confirm_profile_change;
+        self.list.confirm_device_mute = config.confirm_device_mute;
+        self.list.show_history = config.meter.history > 0;
+    }
 }
 
 impl From<TabKind> for Tab {
     fn from(tab_kind: TabKind) -> Tab {
+        let title = String::from(Self::default_title(tab_kind));
         match tab_kind {
             TabKind::Playback => Tab::new(
-                String::from("Playback"),
+                title,
                 ObjectList::new(ListKind::Node(view::NodeKind::Playback), None),
             ),
             TabKind::Recording => Tab::new(
-                String::from("Recording"),
+                title,
                 ObjectList::new(
                     ListKind::Node(view::NodeKind::Recording),
                     None,
                 ),
             ),
             TabKind::Output => Tab::new(
-                String::from("Output Devices"),
+                title,
                 ObjectList::new(
                     ListKind::Node(view::NodeKind::Output),
                     Some(DeviceKind::Sink),
                 ),
             ),
             TabKind::Input => Tab::new(
-                String::from("Input Devices"),
+                title,
                 ObjectList::new(
                     ListKind::Node(view::NodeKind::Input),
                     Some(DeviceKind::Source),
                 ),
             ),
-            TabKind::Configuration => Tab::new(
-                String::from("Configuration"),
-                ObjectList::new(ListKind::Device, None),
+            TabKind::Configuration => {
+                Tab::new(title, ObjectList::new(ListKind::Device, None))
+            }
+            TabKind::Cards => {
+                let mut list = ObjectList::new(ListKind::Device, None);
+                list.cards = true;
+                Tab::new(title, list)
+            }
+            TabKind::All => Tab::new(
+                title,
+                ObjectList::new(ListKind::Node(view::NodeKind::All), None),
+            ),
+            TabKind::Streams => Tab::new(
+                title,
+                ObjectList::new(ListKind::Node(view::NodeKind::Streams), None),
             ),
         }
     }
@@ -176,6 +377,9 @@ pub type MouseArea =
 pub struct App<'a> {
     /// If set, tells the main loop it's time to exit
     exit: bool,
+    /// In oneshot mode, the objects an adjustment was made to, so exit on the
+    /// next key or once PipeWire reports a change to one of them
+    adjusted: Option<HashSet<ObjectId>>,
     /// wirehose handle, for sending commands
     wirehose: &'a dyn CommandSender,
     /// [`Event`](`crate::event::Event`) channel
@@ -191,6 +395,11 @@ pub struct App<'a> {
     mouse_areas: Vec<MouseArea>,
     /// wirehose has received all initial information
     is_ready: bool,
+    /// The connection to PipeWire was lost and wirehose is reconnecting
+    disconnected: bool,
+    /// Input was handled since the last frame, so the next one shouldn't
+    /// wait for the frame rate limit
+    input_pending: bool,
     /// The current PipeWire state
     state: State,
     /// Does the view need to be updated with the current PipeWire state?
@@ -205,15 +414,51 @@ pub struct App<'a> {
     drag_row: Option<u16>,
     /// Position in help text (None if not showing help)
     help_position: Option<u16>,
+    /// Properties of the selected object (None if not showing them)
+    details: Option<Details>,
+    /// Remotes to switch to (None if not picking one)
+    remote_picker: Option<RemotePicker>,
     /// Object IDs that are currently visible (including any display
     /// dependencies)
     visible_objects: HashSet<ObjectId>,
+    /// Peak meter mode of the tab the visible objects are on
+    visible_peaks: Peaks,
+    /// Set by wirehose when peaks change, and cleared when they are rendered
+    peaks_pending: Option<Arc<AtomicBool>>,
     /// Callback for peak ballistics.
-    peak_processor: Arc<dyn PeakProcessor>,
+    peak_processor: Option<Arc<dyn PeakProcessor>>,
     /// Objects eligible for capture.
     capturable_objects: HashSet<ObjectId>,
     /// Objects currently being captured.
     capturing_objects: HashSet<ObjectId>,
+    /// Nodes whose volumes are locked against changes
+    locked: HashSet<ObjectId>,
+    /// Sinks and sources captured even when nothing is linked to them
+    pinned: HashSet<ObjectId>,
+    /// Recent targets of each node, for toggling between them
+    target_history: TargetHistory,
+    /// Volumes to restore when unmuting
+    mute_memory: MuteMemory,
+    /// Defaults to restore after device profile changes
+    profile_defaults: ProfileDefaults,
+    /// Volumes to restore when nodes reappear, loaded once remember_volumes
+    /// is enabled
+    saved_volumes: Option<SavedVolumes>,
+    /// Current sort mode of each tab
+    sort: config::Sort,
+    /// Whether sinks are listed as sources to record their monitors from
+    show_monitor_sources: bool,
+    /// Whether streams are hidden according to hide_muted and hide_idle
+    hide_streams: bool,
+    /// Whether the status bar is shown
+    status_bar: bool,
+    /// Clients whose streams are shown individually when grouping streams
+    expanded_groups: HashSet<ObjectId>,
+    /// A transient message, such as an error, and when it was shown
+    message: Option<(String, Instant)>,
+    /// When the last keyboard volume step happened, its direction, and how
+    /// many steps in a row have repeated, for volume.accel
+    volume_repeat: Option<(Instant, bool, u32)>,
 }
 
 macro_rules! current_list {
@@ -222,28 +467,106 @@ macro_rules! current_list {
     };
 }
 
+/// How long transient messages are shown
+const MESSAGE_DURATION: Duration = Duration::from_secs(5);
+
+/// Scale of mouse volume steps while Alt is held
+const FINE_VOLUME_SCALE: f32 = 0.1;
+
+/// Builds a peak processor implementing VU-meter-style ballistics, or None if
+/// peaks should be displayed without smoothing.
+pub(crate) fn peak_processor(
+    meter: &config::Meter,
+) -> Option<Arc<dyn PeakProcessor>> {
+    if meter.decay_ms == 0.0 {
+        return None;
+    }
+
+    let decay = meter.decay_ms / 1000.0;
+    let attack = meter.attack_ms.map_or(decay, |ms| ms / 1000.0);
+
+    let peak_processor =
+        move |new_peak: f32, current_peak: f32, samples: u32, rate: u32| {
+            let time_constant = if new_peak > current_peak {
+                attack
+            } else {
+                decay
+            };
+            if time_constant == 0.0 {
+                return new_peak;
+            }
+            let coef =
+                1.0 - (-(samples as f32) / (time_constant * rate as f32)).exp();
+
+            current_peak + (new_peak - current_peak) * coef
+        };
+
+    Some(Arc::new(peak_processor))
+}
+
+/// Starts capturing a node's peaks for both the UI and the meter command.
+/// Fails if the node is unknown, hidden by filters, or has no object.serial.
+pub(crate) fn start_node_capture(
+    wirehose: &dyn CommandSender,
+    state: &State,
+    object_id: ObjectId,
+    filters: &[config::MatchCondition],
+    peak_processor: Option<Arc<dyn PeakProcessor>>,
+) -> Result<()> {
+    let Some(node) = state.nodes.get(&object_id) else {
+        return Err(anyhow!("No node with ID {object_id:?}"));
+    };
+    if filters
+        .iter()
+        .any(|condition| condition.matches(state, node))
+    {
+        return Err(anyhow!("Can't capture peaks of a filtered node"));
+    }
+    let Some(object_serial) = node.props.object_serial() else {
+        return Err(anyhow!("Can't capture peaks without an object.serial"));
+    };
+
+    let capture_sink =
+        node.props
+            .media_class()
+            .as_ref()
+            .is_some_and(|media_class| {
+                media_class::is_sink(media_class)
+                    || media_class::is_source(media_class)
+            });
+
+    wirehose.node_capture_start(
+        object_id,
+        *object_serial,
+        capture_sink,
+        Arc::clone(&node.peaks_dirty),
+        peak_processor,
+    );
+    Ok(())
+}
+
 impl<'a> App<'a> {
     pub fn new(
         wirehose: &'a dyn CommandSender,
         rx: mpsc::Receiver<Event>,
         config: Config,
     ) -> Self {
-        let tabs = config.tabs.iter().copied().map(Tab::from).collect();
+        let mut tabs: Vec<Tab> =
+            config.tabs.iter().copied().map(Tab::from).collect();
+        for (tab, &tab_kind) in tabs.iter_mut().zip(&config.tabs) {
+            tab.configure(tab_kind, &config);
+        }
 
-        // Update peaks with VU-meter-style ballistics
-        let peak_processor = |new_peak, current_peak, samples, rate| {
-            // Attack/release time of 300 ms
-            let time_constant = 0.3;
-            let coef =
-                1.0 - (-(samples as f32) / (time_constant * rate as f32)).exp();
+        let peak_processor = peak_processor(&config.meter);
+        let visible_peaks = config.peaks_for(config.tabs[config.tab]);
 
-            current_peak + (new_peak - current_peak) * coef
-        };
+        let sort = config.sort.clone();
 
         let state = State::default();
 
         App {
             exit: false,
+            adjusted: None,
             wirehose,
             rx,
             error_message: None,
@@ -251,16 +574,35 @@ impl<'a> App<'a> {
             current_tab_index: config.tab,
             mouse_areas: Vec::new(),
             is_ready: false,
+            disconnected: false,
+            input_pending: false,
             state,
             state_dirty: false,
             view: View::new(wirehose),
-            config,
             drag_row: None,
             help_position: None,
+            details: None,
+            remote_picker: None,
             visible_objects: HashSet::new(),
-            peak_processor: Arc::new(peak_processor),
+            visible_peaks,
+            peaks_pending: None,
+            peak_processor,
             capturable_objects: HashSet::new(),
             capturing_objects: HashSet::new(),
+            locked: HashSet::new(),
+            pinned: HashSet::new(),
+            target_history: TargetHistory::default(),
+            mute_memory: MuteMemory::default(),
+            profile_defaults: ProfileDefaults::default(),
+            saved_volumes: None,
+            expanded_groups: HashSet::new(),
+            sort,
+            show_monitor_sources: config.show_monitor_sources,
+            hide_streams: true,
+            status_bar: config.status_bar,
+            message: None,
+            volume_repeat: None,
+            config,
         }
     }
 
@@ -273,6 +615,14 @@ impl<'a> App<'a> {
             let _ = self.handle_events(None);
         }
 
+        self.update_view();
+        if self.config.startup.enabled {
+            self.apply_startup();
+        }
+        if let Some(name) = self.config.select.clone() {
+            self.select_node(&name);
+        }
+
         let mut pacer = RenderPacer::new(self.config.fps);
 
         // Did we handle any events and thus need to re-render?
@@ -281,31 +631,49 @@ impl<'a> App<'a> {
         while !self.exit {
             // Update view if needed
             if self.state_dirty {
-                self.view = View::from(
-                    self.wirehose,
-                    &self.state,
-                    &self.config.names,
-                    &self.config.filters,
-                );
+                self.update_view();
+            }
+
+            if self
+                .message
+                .as_ref()
+                .is_some_and(|(_, shown)| shown.elapsed() >= MESSAGE_DURATION)
+            {
+                self.message = None;
+                needs_render = true;
             }
-            self.state_dirty = false;
 
             let frame = terminal.get_frame();
             current_list!(self).update(frame.area(), &self.view);
 
             let new_visible_objects =
                 current_list!(self).visible_objects(&frame.area(), &self.view);
-            if new_visible_objects != self.visible_objects {
+            let new_visible_peaks = current_list!(self).peaks;
+            if new_visible_objects != self.visible_objects
+                || new_visible_peaks != self.visible_peaks
+            {
                 needs_render = true;
                 self.visible_objects = new_visible_objects;
+                self.visible_peaks = new_visible_peaks;
                 self.update_capturing();
             }
 
-            if needs_render && pacer.is_time_to_render() {
+            // Respond to input right away, but hold peak updates to the fps
+            // limit.
+            let input_pending = std::mem::take(&mut self.input_pending);
+            if needs_render && (input_pending || pacer.is_time_to_render()) {
                 needs_render = false;
+                if input_pending {
+                    pacer.restart();
+                }
 
                 self.mouse_areas.clear();
 
+                // Peaks which change during drawing will be reported again.
+                if let Some(peaks_pending) = self.peaks_pending.take() {
+                    peaks_pending.store(false, Ordering::Relaxed);
+                }
+
                 terminal.draw(|frame| {
                     self.draw(frame);
                 })?;
@@ -318,7 +686,258 @@ impl<'a> App<'a> {
             )?;
         }
 
-        self.error_message.map_or(Ok(()), |s| Err(anyhow!(s)))
+        if let Some(saved_volumes) = &mut self.saved_volumes {
+            let result = saved_volumes.save();
+            if self.error_message.is_none() {
+                result?;
+            }
+        }
+
+        // The only errors reported this way come from PipeWire.
+        self.error_message
+            .map_or(Ok(()), |s| Err(ErrorKind::Connection.tag(anyhow!(s))))
+    }
+
+    fn update_view(&mut self) {
+        let mut view = View::from(
+            self.wirehose,
+            &self.state,
+            &self.config.names,
+            &self.config.filters,
+            &self.sort,
+            &view::Hidden {
+                monitor_sources: !self.show_monitor_sources,
+                muted_streams: self.hide_streams && self.config.hide_muted,
+                // Nothing is captured without peaks, so every stream would
+                // look idle.
+                idle_streams: self.hide_streams
+                    && self.config.hide_idle
+                    && self.config.any_peaks(),
+            },
+        );
+        if self.config.group_streams {
+            view.collapse_groups(&self.expanded_groups);
+        }
+        view.min_volume_percent = self.config.min_volume_percent;
+        view.default_keys = self.config.default_keys.clone();
+        view.wrap_navigation = self.config.wrap_navigation;
+        view.set_capturing(&self.capturing_objects);
+        view.set_locked(&self.locked);
+        // Carry over meter peak holds and histories
+        for (object_id, node) in view.nodes.iter_mut() {
+            if let Some(previous) = self.view.nodes.get(object_id) {
+                node.peak_hold = Rc::clone(&previous.peak_hold);
+                node.peak_history = Rc::clone(&previous.peak_history);
+            }
+        }
+        self.run_default_hooks(&view);
+        self.view = view;
+        self.target_history.update(&self.view);
+        self.mute_memory.update(&self.view);
+        if self.config.remember_profile_defaults {
+            self.profile_defaults.update(&self.view);
+        }
+        if self.config.remember_volumes {
+            self.saved_volumes
+                .get_or_insert_with(|| {
+                    SavedVolumes::load(SavedVolumes::default_path())
+                })
+                .update(&self.view);
+        }
+        self.state_dirty = false;
+    }
+
+    /// Replace the configuration with one re-read from disk.
+    ///
+    /// The tabs, remote, and OSC listener can't be changed while running, so
+    /// they are kept from the current configuration. The current tab and
+    /// selections are preserved.
+    fn reload_config(&mut self, mut config: Config) {
+        config.remote = self.config.remote.take();
+        config.osc_listen = self.config.osc_listen;
+        config.tab = self.config.tab;
+        config.tabs = std::mem::take(&mut self.config.tabs);
+
+        self.config = config;
+        self.sort = self.config.sort.clone();
+        self.show_monitor_sources = self.config.show_monitor_sources;
+        self.status_bar = self.config.status_bar;
+        for (tab, &tab_kind) in self.tabs.iter_mut().zip(&self.config.tabs) {
+            tab.configure(tab_kind, &self.config);
+        }
+        // This only affects captures started after the reload.
+        self.peak_processor = peak_processor(&self.config.meter);
+        self.message = None;
+        // Names and filters may have changed.
+        self.state_dirty = true;
+    }
+
+    /// Runs the on_default_change command for each default that differs
+    /// between the current view and view.
+    fn run_default_hooks(&mut self, view: &View) {
+        let Some(command) = self.config.on_default_change.clone() else {
+            return;
+        };
+        let default_node = |view: &View, target| match target {
+            Some(view::Target::Node(node_id)) => view.nodes.get(&node_id),
+            _ => None,
+        };
+        let changes = [
+            (self.view.default_sink, view.default_sink, DeviceKind::Sink),
+            (
+                self.view.default_source,
+                view.default_source,
+                DeviceKind::Source,
+            ),
+        ];
+        for (previous, current, kind) in changes {
+            // Defaults showing up at startup aren't changes.
+            let Some(previous) = default_node(&self.view, previous) else {
+                continue;
+            };
+            let Some(current) = default_node(view, current) else {
+                continue;
+            };
+            if previous.name == current.name {
+                continue;
+            }
+            if let Err(error) = hook::default_changed(
+                &command,
+                &current.name,
+                &current.title,
+                kind,
+            ) {
+                self.show_message(format!("{error:#}"));
+            }
+        }
+    }
+
+    /// Forgets everything from the PipeWire connection and shows that it is
+    /// being reconnected.
+    fn reset_state(&mut self) {
+        // Everything is sent again after reconnecting, and captures ended
+        // with the connection.
+        self.disconnected = true;
+        self.state = State::default();
+        self.state_dirty = true;
+        self.capturable_objects.clear();
+        self.capturing_objects.clear();
+        self.pinned.clear();
+    }
+
+    /// Reconnects to another PipeWire remote. The new remote is kept across
+    /// configuration reloads.
+    fn switch_remote(&mut self, remote: Option<String>) {
+        if remote == self.config.remote {
+            return;
+        }
+        self.wirehose.switch_remote(remote.clone());
+        self.config.remote = remote;
+        self.reset_state();
+    }
+
+    fn show_message(&mut self, message: String) {
+        self.message = Some((message, Instant::now()));
+    }
+
+    /// Grows relative volume steps which repeat in the same direction within
+    /// volume.accel's threshold, up to its maximum multiplier.
+    fn accelerate(&mut self, action: Action) -> Action {
+        let Action::SetRelativeVolume(delta) = action else {
+            return action;
+        };
+        let accel = &self.config.volume.accel;
+        if !accel.enabled {
+            return action;
+        }
+
+        let now = Instant::now();
+        let threshold =
+            Duration::try_from_secs_f32(accel.threshold_ms / 1000.0)
+                .unwrap_or(Duration::ZERO);
+        let up = delta > 0.0;
+        let count = match self.volume_repeat {
+            Some((last, last_up, count))
+                if last_up == up && now.duration_since(last) <= threshold =>
+            {
+                count.saturating_add(1)
+            }
+            _ => 1,
+        };
+        self.volume_repeat = Some((now, up, count));
+
+        let multiplier = (count as f32).min(accel.max_multiplier);
+        Action::SetRelativeVolume(delta * multiplier)
+    }
+
+    /// Apply the configured startup baseline. This only sends commands - the
+    /// resulting changes come back through the usual state events.
+    fn apply_startup(&self) {
+        let startup = &self.config.startup;
+        if let Some(name) = &startup.default_sink {
+            self.view.set_default_name(name, DeviceKind::Sink);
+        }
+        if let Some(name) = &startup.default_source {
+            self.view.set_default_name(name, DeviceKind::Source);
+        }
+        if startup.clear_targets {
+            self.view.clear_targets();
+        }
+    }
+
+    /// Applies the named preset to every node one of its rules matches. The
+    /// first matching rule wins.
+    fn apply_preset(&mut self, name: &str) {
+        let Some(rules) = self.config.presets.get(name) else {
+            self.show_message(format!("No preset named '{name}'"));
+            return;
+        };
+        let max = self
+            .config
+            .enforce_max_volume
+            .then_some(self.config.max_volume_percent);
+
+        let mut count = 0;
+        for (&object_id, node) in &self.view.nodes {
+            let Some(state_node) = self.state.nodes.get(&object_id) else {
+                continue;
+            };
+            let Some(rule) = rules.iter().find(|rule| {
+                rule.matches
+                    .iter()
+                    .any(|condition| condition.matches(&self.state, state_node))
+            }) else {
+                continue;
+            };
+            if let Some(volume) = rule.volume {
+                let adjustment =
+                    view::VolumeAdjustment::Absolute(volume / 100.0);
+                self.view.volume(object_id, adjustment, max);
+            }
+            // View::mute() toggles
+            if rule.mute.is_some_and(|mute| mute != node.mute) {
+                self.view.mute(object_id);
+            }
+            count += 1;
+        }
+        self.show_message(format!("Applied preset '{name}' to {count} nodes"));
+    }
+
+    /// Selects the named node, switching to the first tab which lists it if
+    /// the current one doesn't. Does nothing if there is no such node.
+    fn select_node(&mut self, name: &str) {
+        let Some(node_id) = headless::find_node(&self.view, name) else {
+            return;
+        };
+        let current = self.current_tab_index;
+        let indexes = std::iter::once(current)
+            .chain((0..self.tabs.len()).filter(|&i| i != current));
+        for index in indexes {
+            if self.tabs[index].list.select_listed(&self.view, node_id) {
+                self.current_tab_index = index;
+                return;
+            }
+        }
     }
 
     fn draw(&mut self, frame: &mut Frame) {
@@ -326,16 +945,45 @@ impl<'a> App<'a> {
             current_tab_index: self.current_tab_index,
             view: &self.view,
             config: &self.config,
+            message: if self.disconnected {
+                Some("Reconnecting to PipeWire...")
+            } else {
+                self.message.as_ref().map(|(message, _)| message.as_str())
+            },
+            status: self.status_bar.then(|| self.status()),
         };
         let mut widget_state = AppWidgetState {
             mouse_areas: &mut self.mouse_areas,
             tabs: &mut self.tabs,
             help_position: &mut self.help_position,
+            details: &mut self.details,
+            remote_picker: &mut self.remote_picker,
         };
 
         frame.render_stateful_widget(widget, frame.area(), &mut widget_state);
     }
 
+    /// Returns the PipeWire server name and version, and hints for keys
+    /// relevant to the current tab, for the status bar.
+    fn status(&self) -> (String, String) {
+        let core = &self.state.core;
+        let server = match (core.core_name(), core.core_version()) {
+            (Some(name), Some(version)) => format!("{name} {version}"),
+            (Some(name), None) => name.clone(),
+            _ => String::new(),
+        };
+        let hints = current_list!(self)
+            .hints()
+            .into_iter()
+            .filter_map(|(action, description)| {
+                let key = self.config.key_for(&action)?;
+                Some(format!("{key}: {description}"))
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        (server, hints)
+    }
+
     fn exit(&mut self, error_message: Option<String>) {
         self.exit = true;
         self.error_message = error_message;
@@ -343,50 +991,48 @@ impl<'a> App<'a> {
 
     fn stop_capture(&mut self, object_id: ObjectId) {
         self.capturing_objects.remove(&object_id);
+        self.view.set_capturing(&self.capturing_objects);
         self.wirehose.node_capture_stop(object_id);
     }
 
     fn start_capture(&mut self, object_id: ObjectId) {
-        if self.config.lazy_capture
-            && !self.visible_objects.contains(&object_id)
-        {
+        if self.config.lazy_capture && !self.wants_capture(object_id) {
             return;
         }
 
-        let Some(node) = self.state.nodes.get(&object_id) else {
-            return;
-        };
-
-        if self
-            .config
-            .filters
-            .iter()
-            .any(|condition| condition.matches(&self.state, node))
+        if start_node_capture(
+            self.wirehose,
+            &self.state,
+            object_id,
+            &self.config.filters,
+            self.peak_processor.clone(),
+        )
+        .is_ok()
         {
-            return;
+            self.capturing_objects.insert(object_id);
+            self.view.set_capturing(&self.capturing_objects);
         }
+    }
 
-        let Some(object_serial) = node.props.object_serial() else {
-            return;
-        };
+    /// Is the object visible on a tab that shows peaks? While idle streams
+    /// are hidden, every stream is wanted, since a hidden stream can't become
+    /// visible until it's captured.
+    fn wants_capture(&self, object_id: ObjectId) -> bool {
+        let hides_idle = self.hide_streams && self.config.hide_idle;
+        self.visible_peaks != Peaks::Off
+            && (self.visible_objects.contains(&object_id)
+                || (hides_idle && self.is_stream(object_id)))
+    }
 
-        let capture_sink =
-            node.props
-                .media_class()
-                .as_ref()
-                .is_some_and(|media_class| {
-                    media_class::is_sink(media_class)
-                        || media_class::is_source(media_class)
-                });
-
-        self.capturing_objects.insert(object_id);
-        self.wirehose.node_capture_start(
-            node.object_id,
-            *object_serial,
-            capture_sink,
-            Arc::clone(&node.peaks_dirty),
-            Some(Arc::clone(&self.peak_processor)),
-        );
+    fn is_stream(&self, object_id: ObjectId) -> bool {
+        self.state
+            .nodes
+            .get(&object_id)
+            .and_then(|node| node.props.media_class().as_ref())
+            .is_some_and(|media_class| {
+                media_class::is_sink_input(media_class)
+                    || media_class::is_source_output(media_class)
+            })
     }
 
     /// If lazy capture is enabled, make sure visible nodes are captured and
@@ -397,8 +1043,9 @@ impl<'a> App<'a> {
         }
 
         let need_to_start: Vec<_> = self
-            .visible_objects
-            .intersection(&self.capturable_objects)
+            .capturable_objects
+            .iter()
+            .filter(|&&object_id| self.wants_capture(object_id))
             .copied()
             .collect();
         for object_id in need_to_start {
@@ -409,7 +1056,8 @@ impl<'a> App<'a> {
 
         let need_to_stop: Vec<_> = self
             .capturing_objects
-            .difference(&self.visible_objects)
+            .iter()
+            .filter(|&&object_id| !self.wants_capture(object_id))
             .copied()
             .collect();
         for object_id in need_to_stop {
@@ -421,7 +1069,7 @@ impl<'a> App<'a> {
         &mut self,
         capture_eligibility: CaptureEligibility,
     ) {
-        if self.config.peaks == Peaks::Off {
+        if !self.config.any_peaks() {
             return;
         }
 
@@ -437,12 +1085,56 @@ impl<'a> App<'a> {
                 self.start_capture(object_id);
             }
             CaptureEligibility::Ineligible(object_id) => {
+                // Pinned nodes keep capturing until they are removed.
+                if self.pinned.contains(&object_id) {
+                    if self.state.nodes.contains_key(&object_id) {
+                        return;
+                    }
+                    self.pinned.remove(&object_id);
+                }
                 self.capturable_objects.remove(&object_id);
                 self.stop_capture(object_id);
             }
         }
     }
 
+    /// Shows peaks as given on every tab until the next reload, and starts or
+    /// stops captures if that turns peaks on or off.
+    fn set_peaks(&mut self, peaks: Peaks) {
+        let had_peaks = self.config.any_peaks();
+        self.config.peaks = peaks;
+        self.config.tab_peaks.clear();
+        for (tab, &tab_kind) in self.tabs.iter_mut().zip(&self.config.tabs) {
+            tab.list.peaks = self.config.peaks_for(tab_kind);
+        }
+
+        if had_peaks && peaks == Peaks::Off {
+            let capturing: Vec<_> =
+                self.capturing_objects.iter().copied().collect();
+            for object_id in capturing {
+                self.stop_capture(object_id);
+            }
+            self.capturable_objects.clear();
+        } else if !had_peaks && peaks != Peaks::Off {
+            // Eligibility isn't tracked while peaks are off.
+            let eligible: Vec<_> = self
+                .state
+                .nodes
+                .keys()
+                .copied()
+                .filter(|&object_id| {
+                    self.state.is_capture_eligible(object_id)
+                        || self.pinned.contains(&object_id)
+                })
+                .collect();
+            for object_id in eligible {
+                self.set_capture_eligibility(CaptureEligibility::Eligible(
+                    object_id,
+                ));
+            }
+        }
+    }
+
     /// Handle events with optional timeout.
     /// Returns true if events were handled.
     fn handle_events(&mut self, timeout: Option<Duration>) -> Result<bool> {
@@ -498,6 +1190,11 @@ impl RenderPacer {
         false
     }
 
+    /// Starts the next frame period now, after rendering out of turn.
+    fn restart(&mut self) {
+        self.next_frame_time = Instant::now() + self.frame_duration;
+    }
+
     fn duration_until_next_frame(&self) -> Duration {
         self.next_frame_time
             .saturating_duration_since(Instant::now())
@@ -513,9 +1210,67 @@ trait Handle {
 impl Handle for Event {
     fn handle(self, app: &mut App) -> Result<bool> {
         match self {
-            Event::Input(event) => event.handle(app),
+            Event::Input(event) => {
+                let handled = event.handle(app)?;
+                app.input_pending |= handled;
+                Ok(handled)
+            }
             Event::Pipewire(event) => event.handle(app),
+            Event::ReloadConfig(Ok(config)) => {
+                app.reload_config(*config);
+                Ok(true)
+            }
+            Event::ReloadConfig(Err(error)) => {
+                app.show_message(format!("{error:#}"));
+                Ok(true)
+            }
+            Event::Osc(command) => command.handle(app),
+            Event::Hangup => {
+                app.exit(None);
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl Handle for osc::Command {
+    fn handle(self, app: &mut App) -> Result<bool> {
+        // Unknown nodes are ignored like any other bad message.
+        let Some(node_id) = headless::find_node(&app.view, self.node()) else {
+            return Ok(false);
+        };
+        let Some(node) = app.view.nodes.get(&node_id) else {
+            return Ok(false);
+        };
+
+        match self {
+            osc::Command::Volume { volume, .. } => {
+                let max = app
+                    .config
+                    .enforce_max_volume
+                    .then_some(app.config.max_volume_percent);
+                app.view.volume(
+                    node_id,
+                    view::VolumeAdjustment::Absolute(volume),
+                    max,
+                );
+            }
+            osc::Command::Mute { mute, .. } => {
+                if mute.map_or(true, |mute| mute != node.mute) {
+                    app.view.mute(node_id);
+                }
+            }
+            osc::Command::SetDefault { .. } => {
+                if media_class::is_sink(&node.media_class) {
+                    app.view.set_default(node_id, DeviceKind::Sink);
+                } else if media_class::is_source(&node.media_class) {
+                    app.view.set_default(node_id, DeviceKind::Source);
+                }
+            }
         }
+
+        // The change is drawn once PipeWire reports it.
+        Ok(false)
     }
 }
 
@@ -536,8 +1291,48 @@ impl Handle for KeyEvent {
             return Ok(false);
         }
 
-        if let Some(&action) = app.config.keybindings.get(&self) {
-            return action.handle(app);
+        if app.adjusted.is_some() {
+            app.exit(None);
+            return Ok(true);
+        }
+
+        if app.help_position.is_none()
+            && app.details.is_none()
+            && app.remote_picker.is_none()
+        {
+            let max = app
+                .config
+                .enforce_max_volume
+                .then_some(app.config.max_volume_percent);
+            let list = &mut current_list!(app);
+            if list.is_volume_editing()
+                && list.volume_input(self, &app.view, max)
+            {
+                return Ok(true);
+            }
+            if list.is_search_editing() && list.search_input(self) {
+                return Ok(true);
+            }
+            if list.is_metadata_editing()
+                && list.metadata_input(self, &app.view)
+            {
+                return Ok(true);
+            }
+            if list.is_dropdown_open() && list.dropdown_input(self) {
+                return Ok(true);
+            }
+        }
+
+        if let Some(actions) = app.config.keybindings.get(&self).cloned() {
+            let mut handled = false;
+            for action in actions {
+                // Only an immediate second press confirms a device mute.
+                if action != Action::ToggleMute {
+                    current_list!(app).cancel_mute();
+                }
+                handled |= app.accelerate(action).handle(app)?;
+            }
+            return Ok(handled);
         }
 
         Ok(false)
@@ -546,6 +1341,21 @@ impl Handle for KeyEvent {
 
 impl Handle for Action {
     fn handle(self, app: &mut App) -> Result<bool> {
+        // Find the objects before applying since the selection may change.
+        let adjusted = (app.config.oneshot && self.is_adjustment())
+            .then(|| self.adjusted_objects(app));
+        let handled = self.apply(app)?;
+        if let (true, Some(objects)) = (handled, adjusted) {
+            app.adjusted
+                .get_or_insert_with(HashSet::new)
+                .extend(objects);
+        }
+        Ok(handled)
+    }
+}
+
+impl Action {
+    fn apply(self, app: &mut App) -> Result<bool> {
         if let Some(ref mut help_position) = app.help_position {
             match self {
                 Action::MoveDown => {
@@ -573,32 +1383,146 @@ impl Handle for Action {
             }
         }
 
-        match self {
-            Action::SelectTab(index) => {
-                if index < app.tabs.len() {
-                    app.current_tab_index = index;
+        if let Some(ref mut details) = app.details {
+            match self {
+                Action::MoveDown => {
+                    details.position = details.position.saturating_add(1);
+                    return Ok(true);
+                }
+                Action::MoveUp => {
+                    details.position = details.position.saturating_sub(1);
+                    return Ok(true);
+                }
+                Action::ActivateDropdown
+                | Action::CloseDropdown
+                | Action::ShowDetails => {
+                    app.details = None;
+                    return Ok(true);
+                }
+                Action::Exit => {
+                    app.exit(None);
+                    return Ok(true);
+                }
+                _ => {
+                    return Ok(false);
                 }
             }
-            Action::MoveDown => {
-                current_list!(app).down(&app.view);
-            }
-            Action::MoveUp => {
-                current_list!(app).up(&app.view);
-            }
-            Action::TabLeft => {
-                app.current_tab_index = app
-                    .current_tab_index
-                    .checked_sub(1)
-                    .unwrap_or(app.tabs.len() - 1)
-            }
-            Action::TabRight => {
-                app.current_tab_index =
-                    (app.current_tab_index + 1) % app.tabs.len()
-            }
-            Action::CloseDropdown => {
-                current_list!(app).dropdown_close();
-            }
-            Action::ActivateDropdown => {
+        }
+
+        if let Some(ref mut remote_picker) = app.remote_picker {
+            match self {
+                Action::MoveDown => {
+                    remote_picker.list_state.select_next();
+                    return Ok(true);
+                }
+                Action::MoveUp => {
+                    remote_picker.list_state.select_previous();
+                    return Ok(true);
+                }
+                Action::ActivateDropdown => {
+                    let remote = remote_picker.selected().cloned();
+                    app.remote_picker = None;
+                    if let Some(remote) = remote {
+                        app.switch_remote(remote);
+                    }
+                    return Ok(true);
+                }
+                Action::SelectRemote(index) => {
+                    let remote = remote_picker.remotes.get(index).cloned();
+                    app.remote_picker = None;
+                    if let Some(remote) = remote {
+                        app.switch_remote(remote);
+                    }
+                    return Ok(true);
+                }
+                Action::CloseDropdown | Action::PickRemote => {
+                    app.remote_picker = None;
+                    return Ok(true);
+                }
+                Action::Exit => {
+                    app.exit(None);
+                    return Ok(true);
+                }
+                _ => {
+                    return Ok(false);
+                }
+            }
+        }
+
+        if current_list!(app).is_focused() {
+            let max = app
+                .config
+                .enforce_max_volume
+                .then_some(app.config.max_volume_percent);
+            let list = &mut current_list!(app);
+            match self {
+                Action::MoveDown => {
+                    list.focus_down(&app.view);
+                    return Ok(true);
+                }
+                Action::MoveUp => {
+                    list.focus_up(&app.view);
+                    return Ok(true);
+                }
+                Action::SetRelativeVolume(delta) => {
+                    // Relative decreases have no maximum.
+                    let max = max.filter(|_| delta > 0.0);
+                    return Ok(list.set_focused_relative_volume(
+                        &app.view,
+                        delta,
+                        max,
+                        app.config.volume_unit,
+                    ));
+                }
+                Action::SetAbsoluteVolume(volume) => {
+                    return Ok(list.focused_channel().is_some_and(|index| {
+                        list.set_channel_volume(&app.view, index, volume, max)
+                    }));
+                }
+                Action::CloseDropdown | Action::Focus => {
+                    list.toggle_focus(&app.view);
+                    return Ok(true);
+                }
+                // The dropdown isn't shown over the focused node.
+                Action::ActivateDropdown => {
+                    return Ok(false);
+                }
+                _ => {}
+            }
+        }
+
+        match self {
+            Action::SelectTab(index) => {
+                if index < app.tabs.len() {
+                    app.current_tab_index = index;
+                }
+            }
+            Action::MoveDown => {
+                current_list!(app).down(&app.view);
+            }
+            Action::MoveUp => {
+                current_list!(app).up(&app.view);
+            }
+            Action::TabLeft => {
+                app.current_tab_index = app
+                    .current_tab_index
+                    .checked_sub(1)
+                    .unwrap_or(app.tabs.len() - 1)
+            }
+            Action::TabRight => {
+                app.current_tab_index =
+                    (app.current_tab_index + 1) % app.tabs.len()
+            }
+            Action::CloseDropdown => {
+                let list = &mut current_list!(app);
+                if list.dropdown_state.selected().is_some() {
+                    list.dropdown_close();
+                } else {
+                    // Nothing to close, so clear the search instead
+                    list.clear_search();
+                }
+            }
+            Action::ActivateDropdown => {
                 current_list!(app).dropdown_activate(&app.view);
             }
             Action::SetTarget(target) => {
@@ -607,8 +1531,17 @@ impl Handle for Action {
             Action::SelectObject(object_id) => {
                 app.tabs[app.current_tab_index].list.selected = Some(object_id)
             }
+            Action::SelectRoute(route) => {
+                current_list!(app).select_route(route);
+            }
             Action::ToggleMute => {
-                current_list!(app).toggle_mute(&app.view);
+                if !current_list!(app)
+                    .toggle_mute(&app.view, &mut app.mute_memory)
+                {
+                    app.show_message(String::from(
+                        "Mute this device? Press again to mute",
+                    ));
+                }
             }
             Action::SetAbsoluteVolume(volume) => {
                 let max = app
@@ -623,12 +1556,240 @@ impl Handle for Action {
                 // Relative decreases have no maximum.
                 let max = (volume > 0.0 && app.config.enforce_max_volume)
                     .then_some(app.config.max_volume_percent);
+                return Ok(current_list!(app).set_relative_volume(
+                    &app.view,
+                    volume,
+                    max,
+                    app.config.volume_unit,
+                ));
+            }
+            Action::SetChannelVolume(index, volume) => {
+                let max = app
+                    .config
+                    .enforce_max_volume
+                    .then_some(app.config.max_volume_percent);
                 return Ok(current_list!(app)
-                    .set_relative_volume(&app.view, volume, max));
+                    .set_channel_volume(&app.view, index, volume, max));
             }
             Action::SetDefault => {
                 current_list!(app).set_default(&app.view);
             }
+            Action::CycleDefault => {
+                return Ok(current_list!(app).cycle_default(&app.view));
+            }
+            Action::SelectDefault => {
+                return Ok(current_list!(app).select_default(&app.view));
+            }
+            Action::ToggleExpanded => {
+                current_list!(app).toggle_expanded();
+            }
+            Action::Focus => {
+                current_list!(app).toggle_focus(&app.view);
+            }
+            Action::SetBalance(delta) => {
+                let max = app
+                    .config
+                    .enforce_max_volume
+                    .then_some(app.config.max_volume_percent);
+                return Ok(
+                    current_list!(app).set_balance(&app.view, delta, max)
+                );
+            }
+            Action::ToggleBalanceLink => {
+                current_list!(app).toggle_balance_link();
+            }
+            Action::CenterBalance => {
+                let max = app
+                    .config
+                    .enforce_max_volume
+                    .then_some(app.config.max_volume_percent);
+                return Ok(current_list!(app).center_balance(&app.view, max));
+            }
+            Action::StartSearch => {
+                current_list!(app).start_search();
+            }
+            Action::EnterVolume => {
+                current_list!(app).start_volume_input();
+            }
+            Action::ClearClip => {
+                for node in app.view.nodes.values() {
+                    node.peak_hold.clear_clips();
+                    // Also drop clips that haven't been displayed yet.
+                    for clip in node.clips.iter().flat_map(|clips| clips.iter())
+                    {
+                        clip.store(false, Ordering::Relaxed);
+                    }
+                }
+            }
+            Action::ResetPeakHold => {
+                if let Some(node) = current_list!(app)
+                    .selected
+                    .and_then(|selected| app.view.nodes.get(&selected))
+                {
+                    node.peak_hold.reset();
+                }
+            }
+            Action::ResetVolume => {
+                let max = app
+                    .config
+                    .enforce_max_volume
+                    .then_some(app.config.max_volume_percent);
+                return Ok(current_list!(app).reset_volume(
+                    &app.view,
+                    max,
+                    app.config.reset_volume_unmutes,
+                ));
+            }
+            Action::ToggleLock => {
+                let Some(selected) = current_list!(app).selected else {
+                    return Ok(false);
+                };
+                let Some(node) = app.view.nodes.get(&selected) else {
+                    return Ok(false);
+                };
+                // Lock or unlock a group's members together
+                let members = match &node.group {
+                    Some(group) => group.members.clone(),
+                    None => vec![selected],
+                };
+                if node.locked {
+                    for member in &members {
+                        app.locked.remove(member);
+                    }
+                } else {
+                    app.locked.extend(members);
+                }
+                app.view.set_locked(&app.locked);
+            }
+            Action::TogglePeaks => {
+                let peaks = current_list!(app).peaks.next();
+                app.set_peaks(peaks);
+            }
+            Action::TogglePin => {
+                let Some(selected) = current_list!(app).selected else {
+                    return Ok(false);
+                };
+                let Some(node) = app.state.nodes.get(&selected) else {
+                    return Ok(false);
+                };
+                let is_endpoint =
+                    node.props.media_class().as_ref().is_some_and(
+                        |media_class| {
+                            media_class::is_sink(media_class)
+                                || media_class::is_source(media_class)
+                        },
+                    );
+                if !is_endpoint {
+                    return Ok(false);
+                }
+                if app.pinned.remove(&selected) {
+                    if !app.state.is_capture_eligible(selected) {
+                        app.set_capture_eligibility(
+                            CaptureEligibility::Ineligible(selected),
+                        );
+                    }
+                } else {
+                    app.pinned.insert(selected);
+                    app.set_capture_eligibility(CaptureEligibility::Eligible(
+                        selected,
+                    ));
+                }
+            }
+            Action::ToggleMonitorSources => {
+                app.show_monitor_sources = !app.show_monitor_sources;
+                app.update_view();
+            }
+            Action::ToggleHiddenStreams => {
+                app.hide_streams = !app.hide_streams;
+                app.update_view();
+                app.update_capturing();
+            }
+            Action::ToggleIds => {
+                app.config.show_ids = !app.config.show_ids;
+            }
+            Action::ToggleStatusBar => {
+                app.status_bar = !app.status_bar;
+            }
+            Action::MuteAll => {
+                return Ok(current_list!(app)
+                    .mute_all(&app.view, &mut app.mute_memory));
+            }
+            Action::NormalizeGroup => {
+                let max = app
+                    .config
+                    .enforce_max_volume
+                    .then_some(app.config.max_volume_percent);
+                match current_list!(app).normalize(&app.view, max) {
+                    Normalize::Nothing => return Ok(false),
+                    Normalize::Preview { volume, count } => {
+                        app.show_message(format!(
+                            "Set {count} volumes to {}%? Press again to apply",
+                            Action::format_percentage(volume)
+                        ));
+                    }
+                    Normalize::Applied => {}
+                }
+            }
+            Action::ToggleGroup => {
+                if !app.config.group_streams {
+                    return Ok(false);
+                }
+                let Some(selected) = current_list!(app).selected else {
+                    return Ok(false);
+                };
+                let Some(client_id) = app
+                    .view
+                    .nodes
+                    .get(&selected)
+                    .and_then(|node| node.client_id)
+                else {
+                    return Ok(false);
+                };
+                if !app.expanded_groups.remove(&client_id) {
+                    app.expanded_groups.insert(client_id);
+                }
+                app.update_view();
+                // Collapsing hides the selected stream, so select the group
+                // in its place.
+                if let Some(group_id) = app
+                    .view
+                    .nodes
+                    .values()
+                    .find(|node| {
+                        node.group.as_ref().is_some_and(|group| {
+                            group.members.contains(&selected)
+                        })
+                    })
+                    .map(|node| node.object_id)
+                {
+                    current_list!(app).selected = Some(group_id);
+                }
+            }
+            Action::CycleSort => {
+                let tab_kind = app.config.tabs[app.current_tab_index];
+                let sort = app.sort.get_mut(tab_kind);
+                *sort = sort.next();
+                // The selection is tracked by ID, so it survives the re-sort.
+                app.update_view();
+            }
+            Action::ToggleTarget => {
+                return Ok(current_list!(app)
+                    .toggle_target(&app.view, &mut app.target_history));
+            }
+            Action::NextTarget => {
+                return Ok(current_list!(app).cycle_target(
+                    &app.view,
+                    true,
+                    app.config.cycle_default_target,
+                ));
+            }
+            Action::PrevTarget => {
+                return Ok(current_list!(app).cycle_target(
+                    &app.view,
+                    false,
+                    app.config.cycle_default_target,
+                ));
+            }
             Action::Exit => {
                 app.exit(None);
             }
@@ -640,6 +1801,73 @@ impl Handle for Action {
                 // Activate the help menu
                 app.help_position = Some(0);
             }
+            Action::ShowDetails => {
+                let Some(object_id) = current_list!(app).selected else {
+                    return Ok(false);
+                };
+                let title = app
+                    .view
+                    .nodes
+                    .get(&object_id)
+                    .map(|node| &node.title)
+                    .or_else(|| {
+                        app.view.devices.get(&object_id).map(|dev| &dev.title)
+                    });
+                let props = app
+                    .state
+                    .nodes
+                    .get(&object_id)
+                    .map(|node| &node.props)
+                    .or_else(|| {
+                        app.state.devices.get(&object_id).map(|dev| &dev.props)
+                    });
+                let (Some(title), Some(props)) = (title, props) else {
+                    return Ok(false);
+                };
+                app.details = Some(Details::new(title.clone(), props));
+            }
+            Action::CopyName => {
+                let Some(node) = current_list!(app)
+                    .selected
+                    .and_then(|object_id| app.view.nodes.get(&object_id))
+                else {
+                    return Ok(false);
+                };
+                let message = match clipboard::copy(&node.name) {
+                    Ok(()) => format!("Copied {}", node.name),
+                    Err(error) => format!("Failed to copy: {error}"),
+                };
+                app.show_message(message);
+            }
+            Action::NextTheme => {
+                return Ok(app.config.next_theme());
+            }
+            Action::NextCharSet => {
+                return Ok(app.config.next_char_set());
+            }
+            Action::PickRemote => {
+                app.remote_picker = Some(RemotePicker::new(
+                    &app.config.remotes,
+                    app.config.remote.as_deref(),
+                ));
+            }
+            Action::SelectRemote(_) => {
+                // Only meaningful while the picker is open
+                return Ok(false);
+            }
+            Action::RereadParams => {
+                app.wirehose.reread_params();
+                app.show_message(String::from("Re-reading parameters"));
+            }
+            Action::EditMetadata => {
+                if !app.config.metadata_editing {
+                    return Ok(false);
+                }
+                current_list!(app).start_metadata_input();
+            }
+            Action::ApplyPreset(name) => {
+                app.apply_preset(&name);
+            }
         }
 
         Ok(true)
@@ -656,6 +1884,12 @@ impl Handle for MouseEvent {
             _ => {}
         }
 
+        let kind = if app.config.invert_scroll {
+            inverted_scroll(self.kind)
+        } else {
+            self.kind
+        };
+
         let actions = app
             .mouse_areas
             .iter()
@@ -664,7 +1898,7 @@ impl Handle for MouseEvent {
                 rect.contains(Position {
                     x: self.column,
                     y: app.drag_row.unwrap_or(self.row),
-                }) && kinds.contains(&self.kind)
+                }) && kinds.contains(&kind)
             })
             .map(|(_, _, action)| action.clone())
             .into_iter()
@@ -673,21 +1907,67 @@ impl Handle for MouseEvent {
         let mut handled_action = false;
         for action in actions {
             handled_action = true;
-            let _ = action.handle(app);
+            let _ = fine_action(action, self.modifiers).handle(app);
         }
 
         Ok(handled_action)
     }
 }
 
+/// Returns the scroll in the opposite direction, or kind if it isn't a
+/// scroll.
+fn inverted_scroll(kind: MouseEventKind) -> MouseEventKind {
+    match kind {
+        MouseEventKind::ScrollUp => MouseEventKind::ScrollDown,
+        MouseEventKind::ScrollDown => MouseEventKind::ScrollUp,
+        MouseEventKind::ScrollLeft => MouseEventKind::ScrollRight,
+        MouseEventKind::ScrollRight => MouseEventKind::ScrollLeft,
+        kind => kind,
+    }
+}
+
+/// Shrinks relative volume changes while Alt is held, so that scrolling can
+/// make fine adjustments without changing modes.
+fn fine_action(action: Action, modifiers: KeyModifiers) -> Action {
+    match action {
+        Action::SetRelativeVolume(delta)
+            if modifiers.contains(KeyModifiers::ALT) =>
+        {
+            Action::SetRelativeVolume(delta * FINE_VOLUME_SCALE)
+        }
+        action => action,
+    }
+}
+
 impl Handle for PipewireEvent {
     fn handle(self, app: &mut App) -> Result<bool> {
         match self {
             PipewireEvent::Ready => {
                 app.is_ready = true;
+                app.disconnected = false;
+                Ok(true)
+            }
+            PipewireEvent::Disconnected => {
+                app.reset_state();
                 Ok(true)
             }
             PipewireEvent::Error(message) => message.handle(app),
+            PipewireEvent::RemoteSwitchFailed {
+                remote,
+                previous,
+                error,
+            } => {
+                let name = remote.as_deref().unwrap_or("default");
+                app.show_message(format!(
+                    "Failed to connect to remote '{name}': {error}"
+                ));
+                // Unless another switch has been requested since, wirehose is
+                // back on the previous remote.
+                if app.config.remote == remote {
+                    app.config.remote = previous;
+                }
+                Ok(true)
+            }
             PipewireEvent::State(event) => event.handle(app),
         }
     }
@@ -696,16 +1976,29 @@ impl Handle for PipewireEvent {
 impl Handle for StateEvent {
     fn handle(self, app: &mut App) -> Result<bool> {
         // Peaks are shared between state and view, so no update is necessary.
-        app.state_dirty = !matches!(&self, StateEvent::NodePeaksDirty { .. });
+        // Only visible nodes are captured, so the peaks need to be rendered.
+        if let StateEvent::PeaksDirty { pending } = self {
+            app.peaks_pending = Some(pending);
+            return Ok(true);
+        }
+        app.state_dirty = true;
 
         // Determine if any on-screen objects are affected by this event.
         // Generally we only need to check the event's object ID, but there are
         // some special cases.
-        let visible_affected = self
-            .affected_objects(&app.state)
+        let affected = self.affected_objects(&app.state);
+        let visible_affected = affected
             .iter()
             .any(|object| app.visible_objects.contains(object));
 
+        // PipeWire reporting a change to an adjusted object means the command
+        // has been processed, so it's safe to exit.
+        if app.adjusted.as_ref().is_some_and(|adjusted| {
+            affected.iter().any(|object| adjusted.contains(object))
+        }) {
+            app.exit(None);
+        }
+
         for capture_eligibility in app.state.update(self) {
             app.set_capture_eligibility(capture_eligibility);
         }
@@ -732,27 +2025,52 @@ pub struct AppWidget<'a, 'b> {
     current_tab_index: usize,
     view: &'a View<'b>,
     config: &'a Config,
+    message: Option<&'a str>,
+    /// Server description and key hints for the status bar, if shown
+    status: Option<(String, String)>,
 }
 
 pub struct AppWidgetState<'a> {
     mouse_areas: &'a mut Vec<MouseArea>,
     tabs: &'a mut Vec<Tab>,
     help_position: &'a mut Option<u16>,
+    details: &'a mut Option<Details>,
+    remote_picker: &'a mut Option<RemotePicker>,
 }
 
 impl<'a> StatefulWidget for AppWidget<'a, '_> {
     type State = AppWidgetState<'a>;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let status_height = if self.status.is_some() { 1 } else { 0 };
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Min(0),    // list_area
-                Constraint::Length(1), // menu_area
+                Constraint::Min(0),                // list_area
+                Constraint::Length(1),             // menu_area
+                Constraint::Length(status_height), // status_area
             ])
             .split(area);
         let list_area = layout[0];
         let menu_area = layout[1];
+        let status_area = layout[2];
+
+        if let Some((server, hints)) = &self.status {
+            let style = self.config.theme.status_bar;
+            let hints_width =
+                hints.chars().count().try_into().unwrap_or(u16::MAX);
+            let [server_area, hints_area] = Layout::horizontal([
+                Constraint::Min(0),
+                Constraint::Length(hints_width),
+            ])
+            .spacing(1)
+            .areas(status_area);
+            Line::from(Span::styled(format!(" {server}"), style))
+                .render(server_area, buf);
+            Line::from(Span::styled(hints.as_str(), style))
+                .alignment(Alignment::Right)
+                .render(hints_area, buf);
+        }
 
         let constraints: Vec<_> = state
             .tabs
@@ -793,6 +2111,25 @@ impl<'a> StatefulWidget for AppWidget<'a, '_> {
             ));
         }
 
+        if let Some(message) = self.message {
+            // Show after the tab titles, using whatever space is left
+            let tabs_width: u16 = state
+                .tabs
+                .iter()
+                .map(|tab| tab.title.len() as u16 + 2)
+                .sum();
+            let [_, message_area] = Layout::horizontal([
+                Constraint::Length(tabs_width),
+                Constraint::Min(0),
+            ])
+            .areas(menu_area);
+            Line::from(Span::styled(
+                format!(" {message}"),
+                self.config.theme.error,
+            ))
+            .render(message_area, buf);
+        }
+
         let mut widget = ObjectListWidget {
             object_list: &mut state.tabs[self.current_tab_index].list,
             view: self.view,
@@ -851,6 +2188,93 @@ impl<'a> StatefulWidget for AppWidget<'a, '_> {
                 },
             );
         }
+
+        // Render the property popup if it's open
+        if let Some(ref mut details) = state.details {
+            // Ignore any mouse actions on the lower area
+            state.mouse_areas.clear();
+            // Close the popup if clicked anywhere outside
+            state.mouse_areas.push((
+                area,
+                smallvec![MouseEventKind::Down(MouseButton::Left)],
+                smallvec![Action::CloseDropdown],
+            ));
+
+            let width: u16 = details.width().try_into().unwrap_or(u16::MAX);
+            let [details_area] = Layout::horizontal([Constraint::Max(width)])
+                .flex(Flex::Center)
+                .areas(list_area);
+            let height: u16 = details
+                .rows
+                .len()
+                .saturating_add(2)
+                .try_into()
+                .unwrap_or(u16::MAX)
+                .min(((details_area.height as f32) * 0.90) as u16);
+            let [details_area] = Layout::vertical([Constraint::Length(height)])
+                .flex(Flex::Center)
+                .areas(details_area);
+
+            Clear.render(details_area, buf);
+
+            DetailsWidget {
+                config: self.config,
+            }
+            .render(
+                details_area,
+                buf,
+                &mut DetailsWidgetState {
+                    mouse_areas: state.mouse_areas,
+                    details,
+                },
+            );
+        }
+
+        // Render the remote picker if it's open
+        if let Some(ref mut remote_picker) = state.remote_picker {
+            // Ignore any mouse actions on the lower area
+            state.mouse_areas.clear();
+
+            let titles = remote_picker.titles();
+            // Add 2 for vertical borders and 2 for highlight symbol
+            let width: u16 = titles
+                .iter()
+                .map(|title| title.chars().count())
+                .max()
+                .unwrap_or_default()
+                .saturating_add(4)
+                .try_into()
+                .unwrap_or(u16::MAX);
+            let height: u16 = titles
+                .len()
+                .saturating_add(2)
+                .try_into()
+                .unwrap_or(u16::MAX)
+                .min(((list_area.height as f32) * 0.90) as u16);
+            let [picker_area] = Layout::horizontal([Constraint::Length(width)])
+                .flex(Flex::Center)
+                .areas(list_area);
+            let [picker_area] = Layout::vertical([Constraint::Length(height)])
+                .flex(Flex::Center)
+                .areas(picker_area);
+
+            let items = titles
+                .into_iter()
+                .enumerate()
+                .map(|(index, title)| {
+                    (title, true, Action::SelectRemote(index))
+                })
+                .collect();
+            DropdownList::new(items, "Remote", picker_area, self.config)
+                .render(
+                    area,
+                    buf,
+                    &mut DropdownListState {
+                        mouse_areas: state.mouse_areas,
+                        list_state: &mut remote_picker.list_state,
+                    },
+                );
+        }
     }
 }
 
@@ -868,20 +2292,63 @@ mod tests {
 
         let config = Config {
             remote: None,
+            remotes: Default::default(),
+            osc_listen: None,
             fps: None,
             mouse: false,
             peaks: Default::default(),
             char_set: Default::default(),
             theme: Default::default(),
+            char_set_name: Default::default(),
+            theme_name: Default::default(),
+            char_sets: Default::default(),
+            themes: Default::default(),
             max_volume_percent: Default::default(),
+            min_volume_percent: Default::default(),
             enforce_max_volume: Default::default(),
+            volume_unit: Default::default(),
+            volume_overlay: Default::default(),
+            min_title_width: Default::default(),
+            wrap_target: Default::default(),
+            show_format: Default::default(),
+            show_latency: Default::default(),
+            show_process: Default::default(),
+            show_links: Default::default(),
+            show_ids: Default::default(),
+            show_monitor_sources: true,
+            status_bar: Default::default(),
+            hide_muted: Default::default(),
+            hide_idle: Default::default(),
+            remember_profile_defaults: Default::default(),
+            remember_volumes: Default::default(),
+            on_default_change: None,
+            meter_orientation: Default::default(),
+            node_layout: Default::default(),
+            cycle_default_target: Default::default(),
+            group_streams: Default::default(),
+            confirm_profile_change: Default::default(),
+            confirm_device_mute: Default::default(),
+            reset_volume_unmutes: Default::default(),
+            wrap_navigation: Default::default(),
+            invert_scroll: Default::default(),
+            metadata_editing: Default::default(),
             keybindings: Default::default(),
             help: Default::default(),
             names: Default::default(),
             tab: 0,
             tabs: vec![TabKind::Playback],
+            tab_titles: Default::default(),
+            tab_peaks: Default::default(),
+            select: Default::default(),
+            oneshot: Default::default(),
             lazy_capture: Default::default(),
             filters: Default::default(),
+            startup: Default::default(),
+            presets: Default::default(),
+            meter: Default::default(),
+            volume: Default::default(),
+            sort: Default::default(),
+            default_keys: Default::default(),
         };
 
         let mut app = App::new(wirehose, event_rx, config);
@@ -905,6 +2372,7 @@ mod tests {
                 object_id,
                 rate: 44100,
                 peaks: Arc::new([0.0.into(), 0.0.into()]),
+                clips: Arc::new([]),
             },
             StateEvent::NodeVolumes {
                 object_id,
@@ -918,8 +2386,14 @@ mod tests {
         for event in events {
             event.handle(&mut app).unwrap();
         }
-        app.view =
-            View::from(wirehose, &app.state, &app.config.names, &Vec::new());
+        app.view = View::from(
+            wirehose,
+            &app.state,
+            &app.config.names,
+            &Vec::new(),
+            &app.sort,
+            &Default::default(),
+        );
 
         // Select the node
         Action::SelectObject(object_id).handle(&mut app).unwrap();
@@ -938,6 +2412,153 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn cycle_sort_on_all_tab() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+        app.config.tabs = vec![TabKind::All];
+        app.tabs = vec![Tab::from(TabKind::All)];
+        app.current_tab_index = 0;
+
+        let sink_id = ObjectId::from_raw_id(1);
+        let mut props = PropertyStore::default();
+        props.set_node_description(String::from("Headphones"));
+        props.set_media_class(String::from("Audio/Sink"));
+        props.set_object_serial(1);
+        for event in [
+            StateEvent::NodeProperties {
+                object_id: sink_id,
+                props,
+            },
+            StateEvent::NodeVolumes {
+                object_id: sink_id,
+                volumes: vec![1.0, 1.0],
+            },
+            StateEvent::NodeMute {
+                object_id: sink_id,
+                mute: false,
+            },
+        ] {
+            event.handle(&mut app).unwrap();
+        }
+        app.update_view();
+        let stream_id = ObjectId::from_raw_id(0);
+        assert_eq!(app.view.nodes_all, vec![stream_id, sink_id]);
+
+        // "Headphones" sorts before "Node name: Media name"
+        Action::CycleSort.handle(&mut app).unwrap();
+        assert_eq!(app.sort.all, config::SortMode::Name);
+        assert_eq!(app.view.nodes_all, vec![sink_id, stream_id]);
+    }
+
+    #[test]
+    fn other_key_cancels_device_mute() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+        use std::collections::HashMap;
+
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+        let mut app = fixture(&wirehose);
+        app.config.tabs = vec![TabKind::Configuration];
+        app.tabs = vec![Tab::from(TabKind::Configuration)];
+        app.tabs[0].list.confirm_device_mute = true;
+        app.current_tab_index = 0;
+
+        let m = KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE);
+        let h = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE);
+        app.config.keybindings = HashMap::from([
+            (m, vec![Action::ToggleMute]),
+            (h, vec![Action::SetRelativeVolume(-0.01)]),
+        ]);
+
+        let object_id = ObjectId::from_raw_id(101);
+        let mut props = PropertyStore::default();
+        props.set_device_nick(String::from("Card"));
+        props.set_object_serial(101);
+        for event in [
+            StateEvent::DeviceProperties { object_id, props },
+            StateEvent::DeviceEnumProfile {
+                object_id,
+                index: 1,
+                description: String::from("Profile"),
+                available: true,
+                classes: Vec::new(),
+            },
+            StateEvent::DeviceProfile {
+                object_id,
+                index: 1,
+            },
+            StateEvent::DeviceRoute {
+                object_id,
+                index: 0,
+                device: 2,
+                profiles: vec![1],
+                description: String::new(),
+                available: true,
+                channel_volumes: vec![1.0, 1.0],
+                mute: false,
+            },
+        ] {
+            event.handle(&mut app).unwrap();
+        }
+        app.update_view();
+        Action::SelectObject(object_id).handle(&mut app).unwrap();
+
+        let is_muted = |commands: &RefCell<VecDeque<_>>| {
+            commands.borrow_mut().drain(..).any(|command| {
+                command == mock::MockCommand::DeviceMute(object_id, 2, true)
+            })
+        };
+
+        // A volume change between the presses means starting over.
+        m.handle(&mut app).unwrap();
+        h.handle(&mut app).unwrap();
+        m.handle(&mut app).unwrap();
+        assert!(!is_muted(&commands));
+
+        m.handle(&mut app).unwrap();
+        assert!(is_muted(&commands));
+    }
+
+    #[test]
+    fn dropdown_keys_move_until_filtering() {
+        use crossterm::event::KeyCode;
+        use std::collections::HashMap;
+
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        app.config.keybindings = HashMap::from([
+            (key('j'), vec![Action::MoveDown]),
+            (key('k'), vec![Action::MoveUp]),
+            (key('/'), vec![Action::StartSearch]),
+        ]);
+
+        let list = &mut current_list!(app);
+        list.targets = ["Speakers", "Headphones", "HDMI"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, title)| {
+                let target =
+                    view::Target::Node(ObjectId::from_raw_id(i as u32));
+                (target, String::from(title), true)
+            })
+            .collect();
+        list.dropdown_state.select(Some(0));
+
+        key('j').handle(&mut app).unwrap();
+        key('j').handle(&mut app).unwrap();
+        assert_eq!(current_list!(app).dropdown_state.selected(), Some(2));
+        key('k').handle(&mut app).unwrap();
+        assert_eq!(current_list!(app).dropdown_state.selected(), Some(1));
+
+        // Once filtering, the same keys are typed instead.
+        key('/').handle(&mut app).unwrap();
+        key('k').handle(&mut app).unwrap();
+        assert_eq!(current_list!(app).dropdown_filter, "k");
+        assert!(current_list!(app).is_dropdown_open());
+    }
+
     #[test]
     fn select_tab_bounds() {
         let wirehose = mock::WirehoseHandle::default();
@@ -958,18 +2579,51 @@ mod tests {
         let ctrl_x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL);
 
         let keybindings = HashMap::from([
-            (x, Action::SelectTab(2)),
-            (ctrl_x, Action::SelectTab(4)),
+            (x, vec![Action::SelectTab(2)]),
+            (ctrl_x, vec![Action::SelectTab(4)]),
         ]);
         let config = Config {
             remote: None,
+            remotes: Default::default(),
+            osc_listen: None,
             fps: None,
             mouse: false,
             peaks: Default::default(),
             char_set: Default::default(),
             theme: Default::default(),
+            char_set_name: Default::default(),
+            theme_name: Default::default(),
+            char_sets: Default::default(),
+            themes: Default::default(),
             max_volume_percent: Default::default(),
+            min_volume_percent: Default::default(),
             enforce_max_volume: Default::default(),
+            volume_unit: Default::default(),
+            volume_overlay: Default::default(),
+            min_title_width: Default::default(),
+            wrap_target: Default::default(),
+            show_format: Default::default(),
+            show_latency: Default::default(),
+            show_process: Default::default(),
+            show_links: Default::default(),
+            show_ids: Default::default(),
+            show_monitor_sources: true,
+            status_bar: Default::default(),
+            hide_muted: Default::default(),
+            hide_idle: Default::default(),
+            remember_profile_defaults: Default::default(),
+            remember_volumes: Default::default(),
+            on_default_change: None,
+            meter_orientation: Default::default(),
+            node_layout: Default::default(),
+            cycle_default_target: Default::default(),
+            group_streams: Default::default(),
+            confirm_profile_change: Default::default(),
+            confirm_device_mute: Default::default(),
+            reset_volume_unmutes: Default::default(),
+            wrap_navigation: Default::default(),
+            invert_scroll: Default::default(),
+            metadata_editing: Default::default(),
             keybindings,
             help: Default::default(),
             names: Default::default(),
@@ -981,8 +2635,18 @@ mod tests {
                 TabKind::Input,
                 TabKind::Configuration,
             ],
+            tab_titles: Default::default(),
+            tab_peaks: Default::default(),
+            select: Default::default(),
+            oneshot: Default::default(),
             lazy_capture: Default::default(),
             filters: Default::default(),
+            startup: Default::default(),
+            presets: Default::default(),
+            meter: Default::default(),
+            volume: Default::default(),
+            sort: Default::default(),
+            default_keys: Default::default(),
         };
         let mut app = App::new(&wirehose, event_rx, config);
 
@@ -994,6 +2658,95 @@ mod tests {
         assert_eq!(app.current_tab_index, 2);
     }
 
+    #[test]
+    fn invert_scroll_swaps_directions() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+        app.mouse_areas.push((
+            Rect::new(0, 0, 10, 10),
+            smallvec![MouseEventKind::ScrollUp],
+            smallvec![Action::SelectTab(1)],
+        ));
+        let scroll_down = MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        assert!(!scroll_down.handle(&mut app).unwrap());
+        app.config.invert_scroll = true;
+        assert!(scroll_down.handle(&mut app).unwrap());
+        assert_eq!(app.current_tab_index, 1);
+    }
+
+    #[test]
+    fn reload_config_keeps_tabs_and_selection() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+        let selected = current_list!(app).selected;
+        assert!(selected.is_some());
+
+        let config = Config::from_toml_str(
+            r#"
+            tabs = ["recording", "output"]
+            volume_unit = "db"
+            "#,
+        );
+        let event = Event::ReloadConfig(Ok(Box::new(config)));
+        assert!(event.handle(&mut app).unwrap());
+
+        assert_eq!(app.config.volume_unit, config::VolumeUnit::Db);
+        assert_eq!(app.config.tabs, vec![TabKind::Playback]);
+        assert_eq!(app.current_tab_index, 0);
+        assert_eq!(current_list!(app).selected, selected);
+        assert!(app.state_dirty);
+    }
+
+    #[test]
+    fn reload_config_updates_tab_titles() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+        assert_eq!(app.tabs[0].title, "Playback");
+
+        let config = Config::from_toml_str(
+            r#"tabs = [ { kind = "playback", title = "Apps" } ]"#,
+        );
+        let event = Event::ReloadConfig(Ok(Box::new(config)));
+        assert!(event.handle(&mut app).unwrap());
+        assert_eq!(app.tabs[0].title, "Apps");
+
+        let config = Config::from_toml_str("");
+        let event = Event::ReloadConfig(Ok(Box::new(config)));
+        assert!(event.handle(&mut app).unwrap());
+        assert_eq!(app.tabs[0].title, "Playback");
+    }
+
+    #[test]
+    fn reload_config_error_shows_message() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+
+        let event = Event::ReloadConfig(Err(anyhow!("bad config")));
+        assert!(event.handle(&mut app).unwrap());
+
+        assert!(!app.exit);
+        assert_eq!(
+            app.message.as_ref().map(|(message, _)| message.as_str()),
+            Some("bad config")
+        );
+    }
+
+    #[test]
+    fn hangup_exits() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+
+        assert!(Event::Hangup.handle(&mut app).unwrap());
+        assert!(app.exit);
+        assert!(app.error_message.is_none());
+    }
+
     #[test]
     fn help_underflow() {
         let wirehose = mock::WirehoseHandle::default();
@@ -1033,6 +2786,74 @@ mod tests {
         assert!(app.help_position.is_none());
     }
 
+    #[test]
+    fn remote_picker_switches_remote() {
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+        let mut app = fixture(&wirehose);
+        app.config.remotes = vec![String::from("remote-box")];
+
+        assert!(Action::PickRemote.handle(&mut app).unwrap());
+        let remote_picker = app.remote_picker.as_ref().unwrap();
+        // The current remote is highlighted.
+        assert_eq!(remote_picker.selected(), Some(&None));
+        let index = remote_picker
+            .remotes
+            .iter()
+            .position(|remote| remote.as_deref() == Some("remote-box"))
+            .unwrap();
+        assert!(!Action::SetDefault.handle(&mut app).unwrap());
+
+        commands.borrow_mut().clear();
+        assert!(Action::SelectRemote(index).handle(&mut app).unwrap());
+        assert!(app.remote_picker.is_none());
+        assert!(app.disconnected);
+        assert_eq!(app.config.remote.as_deref(), Some("remote-box"));
+        assert_eq!(
+            commands.borrow_mut().pop_front(),
+            Some(mock::MockCommand::SwitchRemote(Some(String::from(
+                "remote-box"
+            ))))
+        );
+
+        // Picking the current remote again does nothing.
+        assert!(Action::PickRemote.handle(&mut app).unwrap());
+        assert!(Action::SelectRemote(index).handle(&mut app).unwrap());
+        assert!(commands.borrow().is_empty());
+    }
+
+    #[test]
+    fn failed_remote_switch_reverts_remote() {
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+        let mut app = fixture(&wirehose);
+
+        app.switch_remote(Some(String::from("remote-box")));
+        let event = PipewireEvent::RemoteSwitchFailed {
+            remote: Some(String::from("remote-box")),
+            previous: None,
+            error: String::from("Connection refused"),
+        };
+        assert!(event.handle(&mut app).unwrap());
+        assert_eq!(app.config.remote, None);
+        assert_eq!(
+            app.message.as_ref().map(|(message, _)| message.as_str()),
+            Some(
+                "Failed to connect to remote 'remote-box': Connection refused"
+            )
+        );
+
+        // The remote can be picked again.
+        commands.borrow_mut().clear();
+        app.switch_remote(Some(String::from("remote-box")));
+        assert_eq!(
+            commands.borrow_mut().pop_front(),
+            Some(mock::MockCommand::SwitchRemote(Some(String::from(
+                "remote-box"
+            ))))
+        );
+    }
+
     #[test]
     fn help_ignore_other_actions() {
         let wirehose = mock::WirehoseHandle::default();
@@ -1046,6 +2867,85 @@ mod tests {
         assert!(!Action::SetDefault.handle(&mut app).unwrap());
     }
 
+    #[test]
+    fn details_open_scroll_close() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+
+        assert!(Action::ShowDetails.handle(&mut app).unwrap());
+        let details = app.details.as_ref().unwrap();
+        assert_eq!(details.title, "Node name: Media name");
+        assert!(details
+            .rows
+            .contains(&(String::from("node.name"), String::from("Node name"))));
+
+        assert!(Action::MoveDown.handle(&mut app).unwrap());
+        assert_eq!(app.details.as_ref().unwrap().position, 1);
+        assert!(!Action::SetDefault.handle(&mut app).unwrap());
+
+        assert!(Action::CloseDropdown.handle(&mut app).unwrap());
+        assert!(app.details.is_none());
+    }
+
+    #[test]
+    fn alt_makes_volume_steps_fine() {
+        let step = Action::SetRelativeVolume(0.01);
+        assert_eq!(fine_action(step.clone(), KeyModifiers::NONE), step);
+        assert_eq!(
+            fine_action(step, KeyModifiers::ALT),
+            Action::SetRelativeVolume(0.01 * FINE_VOLUME_SCALE)
+        );
+        assert_eq!(
+            fine_action(Action::MoveUp, KeyModifiers::ALT),
+            Action::MoveUp
+        );
+    }
+
+    #[test]
+    fn volume_steps_accelerate_while_repeating() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+        let step = Action::SetRelativeVolume(0.01);
+
+        // Disabled by default
+        assert_eq!(app.accelerate(step.clone()), step);
+        assert_eq!(app.accelerate(step.clone()), step);
+
+        app.config.volume.accel.enabled = true;
+        app.config.volume.accel.threshold_ms = 60_000.0;
+        app.config.volume.accel.max_multiplier = 3.0;
+        assert_eq!(app.accelerate(step.clone()), step);
+        assert_eq!(
+            app.accelerate(step.clone()),
+            Action::SetRelativeVolume(0.02)
+        );
+        assert_eq!(
+            app.accelerate(step.clone()),
+            Action::SetRelativeVolume(0.01 * 3.0)
+        );
+        assert_eq!(
+            app.accelerate(step.clone()),
+            Action::SetRelativeVolume(0.01 * 3.0)
+        );
+        assert_eq!(app.accelerate(Action::MoveUp), Action::MoveUp);
+
+        // Changing direction starts over
+        assert_eq!(
+            app.accelerate(Action::SetRelativeVolume(-0.01)),
+            Action::SetRelativeVolume(-0.01)
+        );
+
+        // So does pausing longer than the threshold
+        app.config.volume.accel.threshold_ms = 0.0;
+        app.volume_repeat = app.volume_repeat.map(|(last, up, count)| {
+            (last - Duration::from_millis(1), up, count)
+        });
+        assert_eq!(
+            app.accelerate(Action::SetRelativeVolume(-0.01)),
+            Action::SetRelativeVolume(-0.01)
+        );
+    }
+
     #[test]
     fn volume_limit_not_enforcing() {
         let wirehose = mock::WirehoseHandle::default();
@@ -1128,6 +3028,91 @@ mod tests {
         assert!(Action::SetAbsoluteVolume(0.90).handle(&mut app).unwrap());
     }
 
+    #[test]
+    fn reread_params_sends_command() {
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+        let mut app = fixture(&wirehose);
+        commands.borrow_mut().clear();
+
+        assert!(Action::RereadParams.handle(&mut app).unwrap());
+        assert_eq!(
+            commands.borrow_mut().pop_front(),
+            Some(mock::MockCommand::RereadParams)
+        );
+        assert!(app.message.is_some());
+    }
+
+    #[test]
+    fn apply_preset_to_matching_nodes() {
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+        let mut app = fixture(&wirehose);
+        let object_id = current_list!(app).selected.unwrap();
+        let config = r#"
+            [[presets.quiet]]
+            matches = [{ "node.name" = "Node name" }]
+            volume = 50
+            mute = true
+            [[presets.other]]
+            matches = [{ "node.name" = "Other node" }]
+            mute = true
+        "#;
+        app.config.presets = Config::from_toml_str(config).presets;
+        commands.borrow_mut().clear();
+
+        let action = Action::ApplyPreset(String::from("quiet"));
+        assert!(action.handle(&mut app).unwrap());
+        assert_eq!(
+            commands.borrow_mut().drain(..).collect::<Vec<_>>(),
+            vec![
+                mock::MockCommand::NodeVolumes(object_id, vec![0.125, 0.125]),
+                mock::MockCommand::NodeMute(object_id, true),
+            ]
+        );
+        assert_eq!(
+            app.message.as_ref().map(|(message, _)| message.as_str()),
+            Some("Applied preset 'quiet' to 1 nodes")
+        );
+
+        // Nodes which aren't present are skipped.
+        let action = Action::ApplyPreset(String::from("other"));
+        assert!(action.handle(&mut app).unwrap());
+        assert!(commands.borrow().is_empty());
+    }
+
+    #[test]
+    fn toggle_peaks_stops_and_restarts_capture() {
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+        let (_, event_rx) = mpsc::channel();
+        let config = Config::from_toml_str("lazy_capture = false");
+        let mut app = App::new(&wirehose, event_rx, config);
+
+        let id = ObjectId::from_raw_id(1);
+        add_capturable_node(&mut app, id);
+        assert!(app.capturing_objects.contains(&id));
+        commands.borrow_mut().clear();
+
+        // Auto to off
+        assert!(Action::TogglePeaks.handle(&mut app).unwrap());
+        assert_eq!(current_list!(app).peaks, Peaks::Off);
+        assert!(app.capturing_objects.is_empty());
+        assert_eq!(
+            commands.borrow_mut().pop_front(),
+            Some(mock::MockCommand::NodeCaptureStop(id))
+        );
+
+        // Off to mono
+        assert!(Action::TogglePeaks.handle(&mut app).unwrap());
+        assert!(app.tabs.iter().all(|tab| tab.list.peaks == Peaks::Mono));
+        assert!(app.capturing_objects.contains(&id));
+        assert_eq!(
+            commands.borrow_mut().pop_front(),
+            Some(mock::MockCommand::NodeCaptureStart(id))
+        );
+    }
+
     #[test]
     fn update_capturing_noop_when_lazy_disabled() {
         let commands = RefCell::new(VecDeque::new());
@@ -1271,4 +3256,159 @@ mod tests {
             Some(mock::MockCommand::NodeCaptureStop(id))
         );
     }
+
+    #[test]
+    fn pinned_sink_captures_without_links() {
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+        let (_, event_rx) = mpsc::channel();
+        let config = Config::from_toml_str("lazy_capture = false");
+        let mut app = App::new(&wirehose, event_rx, config);
+
+        let sink_id = ObjectId::from_raw_id(1);
+        let mut props = PropertyStore::default();
+        props.set_media_class(String::from("Audio/Sink"));
+        props.set_object_serial(1);
+        StateEvent::NodeProperties {
+            object_id: sink_id,
+            props,
+        }
+        .handle(&mut app)
+        .unwrap();
+        // Sinks aren't captured until something is linked to them.
+        assert!(commands.borrow().is_empty());
+
+        current_list!(app).selected = Some(sink_id);
+        Action::TogglePin.handle(&mut app).unwrap();
+        assert_eq!(
+            commands.borrow_mut().pop_front(),
+            Some(mock::MockCommand::NodeCaptureStart(sink_id))
+        );
+
+        // Unlinking doesn't stop capture of a pinned sink.
+        let link_id = ObjectId::from_raw_id(2);
+        StateEvent::Link {
+            object_id: link_id,
+            output_id: ObjectId::from_raw_id(3),
+            input_id: sink_id,
+        }
+        .handle(&mut app)
+        .unwrap();
+        StateEvent::Removed { object_id: link_id }
+            .handle(&mut app)
+            .unwrap();
+        assert!(app.capturing_objects.contains(&sink_id));
+        commands.borrow_mut().clear();
+
+        Action::TogglePin.handle(&mut app).unwrap();
+        assert_eq!(
+            commands.borrow_mut().pop_front(),
+            Some(mock::MockCommand::NodeCaptureStop(sink_id))
+        );
+    }
+
+    #[test]
+    fn view_tracks_capturing() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+        let id = current_list!(app).selected.unwrap();
+        app.capturable_objects.insert(id);
+        app.capturing_objects.clear();
+        app.update_view();
+        assert!(!app.view.nodes[&id].capturing);
+
+        app.set_capture_eligibility(CaptureEligibility::NeedsRestart(id));
+        assert!(app.view.nodes[&id].capturing);
+
+        app.set_capture_eligibility(CaptureEligibility::Ineligible(id));
+        assert!(!app.view.nodes[&id].capturing);
+    }
+
+    #[test]
+    fn oneshot_exits_after_adjustment() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+        app.config.oneshot = true;
+        let object_id = current_list!(app).selected.unwrap();
+
+        // Moving isn't an adjustment.
+        Action::MoveDown.handle(&mut app).unwrap();
+        assert!(app.adjusted.is_none());
+
+        Action::ToggleMute.handle(&mut app).unwrap();
+        assert!(app.adjusted.is_some());
+        assert!(!app.exit);
+
+        // A change to something else doesn't confirm the adjustment.
+        StateEvent::NodeVolumes {
+            object_id: ObjectId::from_raw_id(1),
+            volumes: vec![0.5, 0.5],
+        }
+        .handle(&mut app)
+        .unwrap();
+        assert!(!app.exit);
+
+        StateEvent::NodeMute {
+            object_id,
+            mute: true,
+        }
+        .handle(&mut app)
+        .unwrap();
+        assert!(app.exit);
+    }
+
+    #[test]
+    fn disconnect_clears_state_until_ready() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut app = fixture(&wirehose);
+        let id = current_list!(app).selected.unwrap();
+        app.capturing_objects.insert(id);
+
+        PipewireEvent::Disconnected.handle(&mut app).unwrap();
+        app.update_view();
+        assert!(app.disconnected);
+        assert!(app.view.nodes.is_empty());
+        assert!(app.capturing_objects.is_empty());
+
+        PipewireEvent::Ready.handle(&mut app).unwrap();
+        assert!(!app.disconnected);
+    }
+
+    #[test]
+    fn render_pacer_restart_delays_next_frame() {
+        let mut pacer = RenderPacer::new(Some(1.0));
+        assert!(pacer.is_time_to_render());
+        assert!(!pacer.is_time_to_render());
+
+        pacer.restart();
+        assert!(!pacer.is_time_to_render());
+        assert!(pacer.duration_until_next_frame() > Duration::from_millis(900));
+
+        // No limit
+        let mut pacer = RenderPacer::new(None);
+        assert!(pacer.is_time_to_render());
+        pacer.restart();
+        assert!(pacer.is_time_to_render());
+    }
+
+    #[test]
+    fn peak_processor_zero_decay_is_instantaneous() {
+        let config = Config::from_toml_str("meter = { decay_ms = 0.0 }");
+        assert!(peak_processor(&config.meter).is_none());
+    }
+
+    #[test]
+    fn peak_processor_attack_and_decay() {
+        let config = Config::from_toml_str(
+            "meter = { decay_ms = 300.0, attack_ms = 0.0 }",
+        );
+        let peak_processor = peak_processor(&config.meter).unwrap();
+
+        // Instant attack
+        assert_eq!(peak_processor.process_peak(1.0, 0.0, 480, 48000), 1.0);
+
+        // Smoothed release
+        let peak = peak_processor.process_peak(0.0, 1.0, 480, 48000);
+        assert!(peak > 0.0 && peak < 1.0);
+    }
 }
@@ -0,0 +1,216 @@
+//! A Ratatui widget representing a device with a row for each active route.
+
+use ratatui::{
+    prelude::{Alignment, Buffer, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{StatefulWidget, Widget},
+};
+
+use crossterm::event::{MouseButton, MouseEventKind};
+use smallvec::smallvec;
+
+use crate::app::{Action, MouseArea};
+use crate::config::Config;
+use crate::node_widget;
+use crate::view;
+
+pub struct CardWidget<'a> {
+    device: &'a view::Device,
+    /// Position of the selected route, if this card is selected
+    selected_route: Option<usize>,
+    config: &'a Config,
+}
+
+impl<'a> CardWidget<'a> {
+    pub fn new(
+        device: &'a view::Device,
+        selected_route: Option<usize>,
+        config: &'a Config,
+    ) -> Self {
+        Self {
+            device,
+            selected_route,
+            config,
+        }
+    }
+
+    /// Height of a card with the given number of routes. Cards without
+    /// routes still get a row saying so.
+    pub fn height(routes: usize) -> u16 {
+        let routes = u16::try_from(routes.max(1)).unwrap_or(u16::MAX);
+        routes.saturating_add(2)
+    }
+
+    /// Spacing between objects
+    pub fn spacing() -> u16 {
+        1
+    }
+
+    /// Returns the label for a route's volume.
+    fn volume_label(&self, route: &view::DeviceRoute) -> String {
+        if route.mute {
+            return String::from("muted");
+        }
+        let count = route.volumes.len().max(1) as f32;
+        let mean = route.volumes.iter().sum::<f32>() / count;
+        node_widget::format_volume(self.config.volume_unit, mean.cbrt())
+    }
+
+    fn render_route(
+        &self,
+        position: usize,
+        route: &view::DeviceRoute,
+        area: Rect,
+        buf: &mut Buffer,
+        mouse_areas: &mut Vec<MouseArea>,
+    ) {
+        mouse_areas.push((
+            area,
+            smallvec![MouseEventKind::Down(MouseButton::Left)],
+            smallvec![
+                Action::SelectObject(self.device.object_id),
+                Action::SelectRoute(position)
+            ],
+        ));
+
+        let label = self.volume_label(route);
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),                     // description_area
+                Constraint::Length(label.len() as u16), // volume_area
+                Constraint::Length(1),                  // _padding
+            ])
+            .spacing(1)
+            .split(area);
+        let description_area = layout[0];
+        let volume_area = layout[1];
+
+        let (description, style) = if route.available {
+            (route.description.clone(), self.config.theme.config_profile)
+        } else {
+            (
+                format!("{} (unavailable)", route.description),
+                self.config.theme.config_volume_disabled,
+            )
+        };
+        Line::from(vec![
+            Span::from("      "),
+            Span::styled(description, style),
+        ])
+        .render(description_area, buf);
+
+        Line::from(Span::styled(label, self.config.theme.volume))
+            .alignment(Alignment::Right)
+            .render(volume_area, buf);
+        mouse_areas.push((
+            volume_area,
+            smallvec![MouseEventKind::Down(MouseButton::Left)],
+            smallvec![
+                Action::SelectObject(self.device.object_id),
+                Action::SelectRoute(position),
+                Action::ToggleMute
+            ],
+        ));
+    }
+}
+
+impl StatefulWidget for CardWidget<'_> {
+    type State = Vec<MouseArea>;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let mouse_areas = state;
+
+        mouse_areas.push((
+            area,
+            smallvec![MouseEventKind::Down(MouseButton::Left)],
+            smallvec![Action::SelectObject(self.device.object_id)],
+        ));
+
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(1), // selected_area
+                Constraint::Min(0),    // card_area
+            ])
+            .split(area);
+        let selected_area = layout[0];
+        let card_area = layout[1];
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                (0..Self::height(self.device.routes.len()))
+                    .map(|_| Constraint::Length(1)),
+            )
+            .split(card_area);
+
+        let mut title_line = Line::from(vec![
+            Span::from("   "),
+            Span::styled(&self.device.title, self.config.theme.config_device),
+        ]);
+        if self.config.show_ids {
+            title_line.push_span(Span::from(" "));
+            title_line.push_span(Span::styled(
+                node_widget::format_ids(
+                    self.device.object_id,
+                    self.device.object_serial,
+                ),
+                self.config.theme.node_format,
+            ));
+        }
+        title_line.render(rows[0], buf);
+
+        Line::from(vec![
+            Span::from("    "),
+            Span::styled(
+                &self.config.char_set.dropdown_icon,
+                self.config.theme.dropdown_icon,
+            ),
+            Span::from(" "),
+            Span::styled(
+                &self.device.target_title,
+                self.config.theme.config_profile,
+            ),
+        ])
+        .render(rows[1], buf);
+
+        mouse_areas.push((
+            rows[1],
+            smallvec![MouseEventKind::Down(MouseButton::Left)],
+            smallvec![
+                Action::SelectObject(self.device.object_id),
+                Action::ActivateDropdown
+            ],
+        ));
+
+        if self.device.routes.is_empty() {
+            Line::from(vec![
+                Span::from("      "),
+                Span::styled(
+                    "no route",
+                    self.config.theme.config_volume_disabled,
+                ),
+            ])
+            .render(rows[2], buf);
+        }
+        for (position, (route, &row)) in
+            self.device.routes.iter().zip(&rows[2..]).enumerate()
+        {
+            self.render_route(position, route, row, buf, mouse_areas);
+        }
+
+        // Point at the selected route, or at the placeholder row.
+        if let Some(position) = self.selected_route {
+            let row = rows[2..].get(position).unwrap_or(&rows[2]);
+            let selector_area =
+                Rect::new(selected_area.x, row.y, selected_area.width, 1)
+                    .intersection(selected_area);
+            Line::from(Span::styled(
+                &self.config.char_set.selector_middle,
+                self.config.theme.selector,
+            ))
+            .render(selector_area, buf);
+        }
+    }
+}
@@ -15,6 +15,7 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::env;
 use std::fs;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
@@ -31,20 +32,72 @@ use crate::opt::Opt;
 #[cfg_attr(test, derive(PartialEq))]
 pub struct Config {
     pub remote: Option<String>,
+    /// Remotes offered by the remote picker besides the ones found running
+    pub remotes: Vec<String>,
+    pub osc_listen: Option<SocketAddr>,
+    pub on_default_change: Option<String>,
     pub fps: Option<f32>,
     pub mouse: bool,
     pub peaks: Peaks,
     pub char_set: CharSet,
     pub theme: Theme,
+    /// Name of the active char_set
+    pub char_set_name: String,
+    /// Name of the active theme
+    pub theme_name: String,
+    /// The other char sets, for cycling at runtime
+    pub char_sets: HashMap<String, CharSet>,
+    /// The other themes, for cycling at runtime
+    pub themes: HashMap<String, Theme>,
     pub max_volume_percent: f32,
+    pub min_volume_percent: f32,
     pub enforce_max_volume: bool,
-    pub keybindings: HashMap<KeyEvent, Action>,
+    pub volume_unit: VolumeUnit,
+    pub volume_overlay: bool,
+    pub min_title_width: u16,
+    pub wrap_target: bool,
+    pub show_format: bool,
+    pub show_latency: bool,
+    pub show_process: bool,
+    pub show_links: ShowLinks,
+    pub show_ids: bool,
+    pub show_monitor_sources: bool,
+    pub status_bar: bool,
+    pub hide_muted: bool,
+    pub hide_idle: bool,
+    pub meter_orientation: MeterOrientation,
+    pub node_layout: NodeLayout,
+    pub cycle_default_target: bool,
+    pub group_streams: bool,
+    pub confirm_profile_change: bool,
+    pub confirm_device_mute: bool,
+    pub remember_profile_defaults: bool,
+    pub remember_volumes: bool,
+    pub reset_volume_unmutes: bool,
+    pub wrap_navigation: bool,
+    pub invert_scroll: bool,
+    pub metadata_editing: bool,
+    pub keybindings: HashMap<KeyEvent, Vec<Action>>,
     pub help: help::Help,
     pub names: Names,
     pub tab: usize,
     pub tabs: Vec<TabKind>,
+    pub tab_titles: HashMap<TabKind, String>,
+    pub tab_peaks: HashMap<TabKind, Peaks>,
+    /// Name of the node to select once connected
+    pub select: Option<String>,
+    /// Exit after the first adjustment
+    pub oneshot: bool,
     pub lazy_capture: bool,
     pub filters: Vec<MatchCondition>,
+    pub startup: Startup,
+    /// Volumes and mute states applied by name with
+    /// [`Action::ApplyPreset`]
+    pub presets: HashMap<String, Vec<PresetRule>>,
+    pub meter: Meter,
+    pub volume: Volume,
+    pub sort: Sort,
+    pub default_keys: DefaultKeys,
 }
 
 /// Represents a configuration deserialized from a file. This gets baked into a
@@ -54,6 +107,12 @@ pub struct Config {
 #[serde(deny_unknown_fields)]
 struct ConfigFile {
     remote: Option<String>,
+    #[serde(default)]
+    remotes: Vec<String>,
+    #[serde(default)]
+    osc_listen: Option<SocketAddr>,
+    #[serde(default)]
+    on_default_change: Option<String>,
     #[serde(default = "default_fps")]
     fps: Option<f32>,
     #[serde(default = "default_mouse")]
@@ -66,13 +125,65 @@ struct ConfigFile {
     theme: String,
     #[serde(default = "default_max_volume_percent")]
     max_volume_percent: Option<f32>,
+    #[serde(default)]
+    min_volume_percent: f32,
     #[serde(default = "default_enforce_max_volume")]
     enforce_max_volume: bool,
+    #[serde(default = "default_volume_unit")]
+    volume_unit: VolumeUnit,
+    #[serde(default)]
+    volume_overlay: bool,
+    #[serde(default)]
+    min_title_width: u16,
+    #[serde(default)]
+    wrap_target: bool,
+    #[serde(default = "default_show_format")]
+    show_format: bool,
+    #[serde(default = "default_show_latency")]
+    show_latency: bool,
+    #[serde(default)]
+    show_process: bool,
+    #[serde(default)]
+    show_links: ShowLinks,
+    #[serde(default)]
+    show_ids: bool,
+    #[serde(default = "default_show_monitor_sources")]
+    show_monitor_sources: bool,
+    #[serde(default)]
+    status_bar: bool,
+    #[serde(default)]
+    hide_muted: bool,
+    #[serde(default)]
+    hide_idle: bool,
+    #[serde(default)]
+    meter_orientation: MeterOrientation,
+    #[serde(default)]
+    node_layout: NodeLayout,
+    #[serde(default)]
+    cycle_default_target: bool,
+    #[serde(default)]
+    group_streams: bool,
+    #[serde(default)]
+    confirm_profile_change: bool,
+    #[serde(default)]
+    confirm_device_mute: bool,
+    #[serde(default)]
+    remember_profile_defaults: bool,
+    #[serde(default)]
+    remember_volumes: bool,
+    #[serde(default)]
+    reset_volume_unmutes: bool,
+    #[serde(default)]
+    wrap_navigation: bool,
+    #[serde(default)]
+    invert_scroll: bool,
+    #[serde(default)]
+    metadata_editing: bool,
     #[serde(
         default = "Keybinding::defaults",
         deserialize_with = "Keybinding::merge"
     )]
-    keybindings: HashMap<KeyEvent, Action>,
+    keybindings: HashMap<KeyEvent, Vec<Action>>,
     #[serde(default)]
     names: Names,
     #[serde(
@@ -85,20 +196,92 @@ struct ConfigFile {
     #[serde(default = "default_tab")]
     tab: Option<TabKind>,
     #[serde(default = "default_tabs")]
-    tabs: Vec<TabKind>,
+    tabs: Vec<TabEntry>,
+    #[serde(default)]
+    select: Option<String>,
     #[serde(default = "default_lazy_capture")]
     lazy_capture: bool,
     #[serde(default = "Filter::defaults", deserialize_with = "Filter::merge")]
     filters: Vec<Filter>,
+    #[serde(default)]
+    startup: Startup,
+    #[serde(default)]
+    presets: HashMap<String, Vec<PresetRule>>,
+    #[serde(default)]
+    meter: Meter,
+    #[serde(default)]
+    volume: Volume,
+    #[serde(default)]
+    sort: Sort,
+    #[serde(default)]
+    default_keys: DefaultKeys,
 }
 
-#[derive(Deserialize, Default, Debug, Clone, PartialEq, clap::ValueEnum)]
+#[derive(
+    Deserialize, Default, Debug, Clone, Copy, PartialEq, clap::ValueEnum,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum Peaks {
     Off,
     Mono,
     #[default]
     Auto,
+    Channels,
+}
+
+impl Peaks {
+    /// Returns the next mode for cycling between off, mono, and auto.
+    pub fn next(self) -> Self {
+        match self {
+            Peaks::Off => Peaks::Mono,
+            Peaks::Mono => Peaks::Auto,
+            Peaks::Auto | Peaks::Channels => Peaks::Off,
+        }
+    }
+}
+
+#[derive(
+    Deserialize, Default, Debug, Clone, Copy, PartialEq, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum VolumeUnit {
+    #[default]
+    Percent,
+    Db,
+}
+
+/// How much of a node's routing to show in its header.
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ShowLinks {
+    #[default]
+    Off,
+    /// The number of nodes linked to and from
+    Count,
+    /// The names of the nodes linked to and from
+    Names,
+}
+
+/// How much room each node takes in a list.
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeLayout {
+    /// A header line above a volume bar and peak meter
+    #[default]
+    Full,
+    /// The title, volume bar, and a mono peak meter on one line
+    Compact,
+}
+
+/// Direction in which peak meters grow.
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MeterOrientation {
+    /// A bar to the right of the volume bar
+    #[default]
+    Horizontal,
+    /// A column for each channel, which makes nodes taller
+    Vertical,
 }
 
 #[derive(Deserialize, Debug)]
@@ -107,7 +290,9 @@ pub struct Keybinding {
     pub key: KeyCode,
     #[serde(default = "Keybinding::default_modifiers")]
     pub modifiers: KeyModifiers,
-    pub action: Action,
+    /// A single action or a sequence of actions run in order
+    #[serde(deserialize_with = "Keybinding::actions")]
+    pub action: Vec<Action>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -151,8 +336,13 @@ pub struct CharSet {
     pub tab_marker_left: String,
     pub tab_marker_right: String,
     pub list_more: String,
+    pub allow_wide_glyphs: bool,
     pub volume_empty: String,
     pub volume_filled: String,
+    pub volume_hardware: String,
+    pub volume_software: String,
+    pub node_virtual: String,
+    pub node_network: String,
     pub meter_left_inactive: String,
     pub meter_left_active: String,
     pub meter_left_overload: String,
@@ -163,6 +353,7 @@ pub struct CharSet {
     pub meter_center_left_active: String,
     pub meter_center_right_inactive: String,
     pub meter_center_right_active: String,
+    pub meter_hold: String,
     pub dropdown_icon: String,
     pub dropdown_selector: String,
     pub dropdown_more: String,
@@ -177,30 +368,45 @@ pub struct Theme {
     pub default_device: Style,
     pub default_stream: Style,
     pub selector: Style,
+    pub node_selected_bg: Style,
     pub tab: Style,
     pub tab_selected: Style,
     pub tab_marker: Style,
     pub list_more: Style,
     pub node_title: Style,
+    /// Styles for node titles by media.role or media.category
+    pub node_title_roles: HashMap<String, Style>,
     pub node_target: Style,
+    pub node_format: Style,
     pub volume: Style,
+    pub volume_boosted: Style,
     pub volume_empty: Style,
     pub volume_filled: Style,
+    pub volume_control: Style,
+    pub node_virtual: Style,
+    pub node_network: Style,
     pub meter_inactive: Style,
     pub meter_active: Style,
     pub meter_overload: Style,
     pub meter_center_inactive: Style,
     pub meter_center_active: Style,
+    pub meter_hold: Style,
+    pub meter_history: Style,
+    pub meter_label: Style,
     pub config_device: Style,
     pub config_profile: Style,
+    pub config_volume_disabled: Style,
     pub dropdown_icon: Style,
     pub dropdown_border: Style,
     pub dropdown_item: Style,
     pub dropdown_selected: Style,
+    pub dropdown_unavailable: Style,
     pub dropdown_more: Style,
     pub help_border: Style,
     pub help_item: Style,
     pub help_more: Style,
+    pub error: Style,
+    pub status_bar: Style,
 }
 
 #[derive(Debug, Deserialize)]
@@ -211,8 +417,247 @@ pub struct Filter {
     pub matches: Vec<MatchCondition>,
 }
 
+/// A baseline routing applied once on startup.
+#[derive(Deserialize, Default, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+#[serde(deny_unknown_fields)]
+pub struct Startup {
+    /// Nothing is applied unless this is set
+    #[serde(default)]
+    pub enabled: bool,
+    /// node.name of the sink to make the default
+    pub default_sink: Option<String>,
+    /// node.name of the source to make the default
+    pub default_source: Option<String>,
+    /// Reset all streams to follow the default sink/source
+    #[serde(default)]
+    pub clear_targets: bool,
+}
+
+/// Part of a preset: what to do to the nodes it matches.
+#[derive(Deserialize, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+#[serde(deny_unknown_fields)]
+pub struct PresetRule {
+    pub matches: Vec<MatchCondition>,
+    /// Volume percentage to set
+    pub volume: Option<f32>,
+    pub mute: Option<bool>,
+}
+
+/// Mapping of peak levels to positions along a meter.
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MeterScale {
+    /// VU-style scale between -60 and +6 dBFS
+    #[default]
+    Linear,
+    /// Equal steps in dB between -db_range and +6 dBFS
+    Log,
+}
+
+/// What scrolling sideways over a peak meter does.
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MeterScroll {
+    /// Adjust the volume, like anywhere else on the node
+    #[default]
+    Volume,
+    /// Nothing
+    None,
+    /// Reset the node's peak-hold markers and clip indicators
+    Reset,
+}
+
+/// Peak meter ballistics, scale, coloring, history, and mouse handling.
+#[derive(Deserialize, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+#[serde(deny_unknown_fields)]
+pub struct Meter {
+    /// Release time constant in milliseconds, or 0 for no smoothing
+    #[serde(default = "default_meter_decay_ms")]
+    pub decay_ms: f32,
+    /// Attack time constant in milliseconds, defaulting to decay_ms
+    pub attack_ms: Option<f32>,
+    /// How long to hold the peak marker in milliseconds, or 0 to disable it
+    #[serde(default)]
+    pub hold_ms: f32,
+    /// How long a clip indicator stays lit in milliseconds, or 0 to keep it
+    /// lit until cleared
+    #[serde(default)]
+    pub clip_ms: f32,
+    /// Number of recent peaks to show in a sparkline for the selected node,
+    /// or 0 to hide it
+    #[serde(default)]
+    pub history: usize,
+    #[serde(default)]
+    pub scale: MeterScale,
+    /// Decibels below full scale at the bottom of a meter in log scale
+    #[serde(default = "default_meter_db_range")]
+    pub db_range: f32,
+    #[serde(default)]
+    pub scroll: MeterScroll,
+    /// Shade lit cells from meter_active toward meter_overload by level
+    #[serde(default)]
+    pub gradient: bool,
+    /// Show the selected node's current peaks in dBFS after its title
+    #[serde(default)]
+    pub values: bool,
+}
+
+impl Default for Meter {
+    fn default() -> Self {
+        Self {
+            decay_ms: default_meter_decay_ms(),
+            attack_ms: None,
+            hold_ms: 0.0,
+            clip_ms: 0.0,
+            history: 0,
+            scale: MeterScale::default(),
+            db_range: default_meter_db_range(),
+            scroll: MeterScroll::default(),
+            gradient: false,
+            values: false,
+        }
+    }
+}
+
+/// Relative volume changes.
+#[derive(Deserialize, Debug, Default)]
+#[cfg_attr(test, derive(PartialEq))]
+#[serde(deny_unknown_fields)]
+pub struct Volume {
+    #[serde(default)]
+    pub accel: VolumeAccel,
+}
+
+/// Growth of relative volume steps while a volume key repeats.
+#[derive(Deserialize, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+#[serde(deny_unknown_fields)]
+pub struct VolumeAccel {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Steps closer together than this many milliseconds count as repeats
+    #[serde(default = "default_accel_threshold_ms")]
+    pub threshold_ms: f32,
+    /// Largest multiple of the configured step a repeat can reach
+    #[serde(default = "default_accel_max_multiplier")]
+    pub max_multiplier: f32,
+}
+
+impl Default for VolumeAccel {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_ms: default_accel_threshold_ms(),
+            max_multiplier: default_accel_max_multiplier(),
+        }
+    }
+}
+
+/// Metadata keys written to change the default sink and source.
+#[derive(Deserialize, Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[serde(deny_unknown_fields)]
+pub struct DefaultKeys {
+    #[serde(default = "default_sink_key")]
+    pub sink: String,
+    #[serde(default = "default_source_key")]
+    pub source: String,
+}
+
+impl Default for DefaultKeys {
+    fn default() -> Self {
+        Self {
+            sink: default_sink_key(),
+            source: default_source_key(),
+        }
+    }
+}
+
+/// Order of objects within a tab.
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortMode {
+    /// Order of creation
+    #[default]
+    Serial,
+    /// Alphabetical by title
+    Name,
+    /// Loudest first, by mean channel volume
+    Volume,
+}
+
+impl SortMode {
+    /// Returns the next mode, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Serial => SortMode::Name,
+            SortMode::Name => SortMode::Volume,
+            SortMode::Volume => SortMode::Serial,
+        }
+    }
+}
+
+/// Sort mode for each tab.
+#[derive(Deserialize, Default, Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[serde(deny_unknown_fields)]
+pub struct Sort {
+    #[serde(default)]
+    pub playback: SortMode,
+    #[serde(default)]
+    pub recording: SortMode,
+    #[serde(default)]
+    pub output: SortMode,
+    #[serde(default)]
+    pub input: SortMode,
+    #[serde(default)]
+    pub configuration: SortMode,
+    #[serde(default)]
+    pub all: SortMode,
+    #[serde(default)]
+    pub streams: SortMode,
+}
+
+impl Sort {
+    pub fn get(&self, tab_kind: TabKind) -> SortMode {
+        match tab_kind {
+            TabKind::Playback => self.playback,
+            TabKind::Recording => self.recording,
+            TabKind::Output => self.output,
+            TabKind::Input => self.input,
+            // Cards list the same devices as the configuration tab.
+            TabKind::Configuration | TabKind::Cards => self.configuration,
+            TabKind::All => self.all,
+            TabKind::Streams => self.streams,
+        }
+    }
+
+    pub fn get_mut(&mut self, tab_kind: TabKind) -> &mut SortMode {
+        match tab_kind {
+            TabKind::Playback => &mut self.playback,
+            TabKind::Recording => &mut self.recording,
+            TabKind::Output => &mut self.output,
+            TabKind::Input => &mut self.input,
+            TabKind::Configuration | TabKind::Cards => &mut self.configuration,
+            TabKind::All => &mut self.all,
+            TabKind::Streams => &mut self.streams,
+        }
+    }
+}
+
 #[derive(
-    Deserialize, Default, Debug, Clone, Copy, PartialEq, clap::ValueEnum,
+    Deserialize,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    clap::ValueEnum,
 )]
 #[serde(rename_all = "lowercase")]
 pub enum TabKind {
@@ -222,6 +667,41 @@ pub enum TabKind {
     Output,
     Input,
     Configuration,
+    /// Devices with the volume and mute of each active route
+    Cards,
+    /// Every stream and endpoint in one list
+    All,
+    /// Every playback and recording stream in one list
+    Streams,
+}
+
+/// An entry in the tabs list - either just the kind of tab, or a table
+/// giving it a custom title or peak meter mode.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum TabEntry {
+    Kind(TabKind),
+    Table {
+        kind: TabKind,
+        #[serde(default)]
+        title: Option<String>,
+        #[serde(default)]
+        peaks: Option<Peaks>,
+    },
+}
+
+impl TabEntry {
+    pub fn kind(&self) -> TabKind {
+        match self {
+            TabEntry::Kind(kind) | TabEntry::Table { kind, .. } => *kind,
+        }
+    }
+}
+
+impl From<TabKind> for TabEntry {
+    fn from(kind: TabKind) -> Self {
+        TabEntry::Kind(kind)
+    }
 }
 
 fn default_fps() -> Option<f32> {
@@ -240,13 +720,13 @@ fn default_tab() -> Option<TabKind> {
     Some(TabKind::default())
 }
 
-fn default_tabs() -> Vec<TabKind> {
+fn default_tabs() -> Vec<TabEntry> {
     vec![
-        TabKind::Playback,
-        TabKind::Recording,
-        TabKind::Output,
-        TabKind::Input,
-        TabKind::Configuration,
+        TabKind::Playback.into(),
+        TabKind::Recording.into(),
+        TabKind::Output.into(),
+        TabKind::Input.into(),
+        TabKind::Configuration.into(),
     ]
 }
 
@@ -266,6 +746,46 @@ fn default_enforce_max_volume() -> bool {
     false
 }
 
+fn default_volume_unit() -> VolumeUnit {
+    VolumeUnit::default()
+}
+
+fn default_show_format() -> bool {
+    false
+}
+
+fn default_show_latency() -> bool {
+    false
+}
+
+fn default_show_monitor_sources() -> bool {
+    true
+}
+
+fn default_meter_decay_ms() -> f32 {
+    300.0
+}
+
+fn default_meter_db_range() -> f32 {
+    60.0
+}
+
+fn default_accel_threshold_ms() -> f32 {
+    100.0
+}
+
+fn default_accel_max_multiplier() -> f32 {
+    4.0
+}
+
+fn default_sink_key() -> String {
+    String::from("default.configured.audio.sink")
+}
+
+fn default_source_key() -> String {
+    String::from("default.configured.audio.source")
+}
+
 fn default_lazy_capture() -> bool {
     false
 }
@@ -305,8 +825,22 @@ impl ConfigFile {
             self.tab = Some(*tab);
         }
 
+        if let Some(select) = &opt.select {
+            self.select = Some(select.clone());
+        }
+
         if let Some(tabs) = &opt.tabs {
-            self.tabs = tabs.clone();
+            // Keep any titles configured for the selected tabs.
+            self.tabs = tabs
+                .iter()
+                .map(|&kind| {
+                    self.tabs
+                        .iter()
+                        .find(|entry| entry.kind() == kind)
+                        .cloned()
+                        .unwrap_or(kind.into())
+                })
+                .collect();
         }
 
         if let Some(max_volume_percent) = &opt.max_volume_percent {
@@ -321,6 +855,10 @@ impl ConfigFile {
             self.enforce_max_volume = true;
         }
 
+        if let Some(volume_unit) = opt.volume_unit {
+            self.volume_unit = volume_unit;
+        }
+
         if opt.no_lazy_capture {
             self.lazy_capture = false;
         }
@@ -364,16 +902,84 @@ impl TryFrom<ConfigFile> for Config {
             }
         }
 
+        let min_volume_percent = config_file.min_volume_percent;
+        if min_volume_percent < 0.0 {
+            anyhow::bail!(
+                "min_volume_percent {min_volume_percent} is negative"
+            );
+        }
+        if config_file
+            .max_volume_percent
+            .is_some_and(|max| min_volume_percent > max)
+        {
+            anyhow::bail!("min_volume_percent exceeds max_volume_percent");
+        }
+
+        let meter = &config_file.meter;
+        // NaN isn't negative, so check for valid times instead.
+        let is_valid_time = |ms: f32| ms >= 0.0 && ms.is_finite();
+        if !is_valid_time(meter.decay_ms)
+            || !meter.attack_ms.map_or(true, is_valid_time)
+            || !is_valid_time(meter.hold_ms)
+            || !is_valid_time(meter.clip_ms)
+        {
+            anyhow::bail!("meter times must not be negative");
+        }
+        if meter.db_range <= 0.0 {
+            anyhow::bail!("meter db_range must be positive");
+        }
+
+        let accel = &config_file.volume.accel;
+        if accel.threshold_ms < 0.0 {
+            anyhow::bail!("volume.accel threshold_ms must not be negative");
+        }
+        if accel.max_multiplier < 1.0 {
+            anyhow::bail!("volume.accel max_multiplier must be at least 1");
+        }
+
         if config_file.tabs.is_empty() {
             anyhow::bail!("tabs must be non-empty");
         }
 
+        for (name, rules) in &config_file.presets {
+            for rule in rules {
+                if rule.volume.is_none() && rule.mute.is_none() {
+                    anyhow::bail!(
+                        "preset '{name}' has a rule without volume or mute"
+                    );
+                }
+                if rule.volume.is_some_and(|volume| volume < 0.0) {
+                    anyhow::bail!("preset '{name}' has a negative volume");
+                }
+            }
+        }
+
+        if config_file.default_keys.sink.is_empty()
+            || config_file.default_keys.source.is_empty()
+        {
+            anyhow::bail!("default_keys must be non-empty");
+        }
+
         let tab = config_file
             .tabs
             .iter()
-            .position(|&t| t == config_file.tab.unwrap_or_default())
+            .position(|t| t.kind() == config_file.tab.unwrap_or_default())
             .context("initial tab not found in tabs")?;
 
+        let tabs = config_file.tabs.iter().map(TabEntry::kind).collect();
+        let mut tab_titles = HashMap::new();
+        let mut tab_peaks = HashMap::new();
+        for entry in config_file.tabs {
+            if let TabEntry::Table { kind, title, peaks } = entry {
+                if let Some(title) = title {
+                    tab_titles.insert(kind, title);
+                }
+                if let Some(peaks) = peaks {
+                    tab_peaks.insert(kind, peaks);
+                }
+            }
+        }
+
         // Emulate signals. This is intentionally done after generating help.
         config_file
             .keybindings
@@ -381,22 +987,65 @@ impl TryFrom<ConfigFile> for Config {
 
         Ok(Self {
             remote: config_file.remote,
+            remotes: config_file.remotes,
+            osc_listen: config_file.osc_listen,
+            on_default_change: config_file.on_default_change,
             fps: config_file.fps.filter(|&fps| fps != 0.0),
             mouse: config_file.mouse,
             peaks: config_file.peaks.unwrap_or_default(),
             max_volume_percent: config_file
                 .max_volume_percent
                 .unwrap_or_default(),
+            min_volume_percent: config_file.min_volume_percent,
             enforce_max_volume: config_file.enforce_max_volume,
+            volume_unit: config_file.volume_unit,
+            volume_overlay: config_file.volume_overlay,
+            min_title_width: config_file.min_title_width,
+            wrap_target: config_file.wrap_target,
+            show_format: config_file.show_format,
+            show_latency: config_file.show_latency,
+            show_process: config_file.show_process,
+            show_links: config_file.show_links,
+            show_ids: config_file.show_ids,
+            show_monitor_sources: config_file.show_monitor_sources,
+            status_bar: config_file.status_bar,
+            hide_muted: config_file.hide_muted,
+            hide_idle: config_file.hide_idle,
+            meter_orientation: config_file.meter_orientation,
+            node_layout: config_file.node_layout,
+            cycle_default_target: config_file.cycle_default_target,
+            group_streams: config_file.group_streams,
+            confirm_profile_change: config_file.confirm_profile_change,
+            confirm_device_mute: config_file.confirm_device_mute,
+            remember_profile_defaults: config_file.remember_profile_defaults,
+            remember_volumes: config_file.remember_volumes,
+            reset_volume_unmutes: config_file.reset_volume_unmutes,
+            wrap_navigation: config_file.wrap_navigation,
+            invert_scroll: config_file.invert_scroll,
+            metadata_editing: config_file.metadata_editing,
             char_set,
             theme,
+            char_set_name: config_file.char_set,
+            theme_name: config_file.theme,
+            char_sets: config_file.char_sets,
+            themes: config_file.themes,
             keybindings: config_file.keybindings,
             help,
             names: config_file.names,
             tab,
-            tabs: config_file.tabs,
+            tabs,
+            tab_titles,
+            tab_peaks,
+            select: config_file.select,
+            oneshot: false,
             lazy_capture: config_file.lazy_capture,
             filters,
+            startup: config_file.startup,
+            presets: config_file.presets,
+            meter: config_file.meter,
+            volume: config_file.volume,
+            sort: config_file.sort,
+            default_keys: config_file.default_keys,
         })
     }
 }
@@ -440,7 +1089,51 @@ impl Config {
         config_file.apply_opt(opt);
         let config_file = config_file;
 
-        Self::try_from(config_file)
+        let mut config = Self::try_from(config_file)?;
+        config.oneshot = opt.oneshot;
+        // See https://no-color.org
+        let no_color =
+            env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        if opt.no_color || no_color {
+            config.theme.strip_colors();
+            for theme in config.themes.values_mut() {
+                theme.strip_colors();
+            }
+        }
+        Ok(config)
+    }
+
+    /// Returns the peak meter mode for a tab.
+    pub fn peaks_for(&self, tab_kind: TabKind) -> Peaks {
+        self.tab_peaks.get(&tab_kind).copied().unwrap_or(self.peaks)
+    }
+
+    /// Are peaks shown on any tab?
+    pub fn any_peaks(&self) -> bool {
+        self.tabs
+            .iter()
+            .any(|&tab_kind| self.peaks_for(tab_kind) != Peaks::Off)
+    }
+
+    /// Returns the key bound to action alone, for showing as a hint.
+    pub fn key_for(&self, action: &Action) -> Option<String> {
+        help::Help::key_for(&self.keybindings, action)
+    }
+
+    /// Switches to the next theme in order of name. Returns false if there
+    /// is only one.
+    pub fn next_theme(&mut self) -> bool {
+        cycle(&mut self.theme_name, &mut self.theme, &mut self.themes)
+    }
+
+    /// Switches to the next char set in order of name. Returns false if there
+    /// is only one.
+    pub fn next_char_set(&mut self) -> bool {
+        cycle(
+            &mut self.char_set_name,
+            &mut self.char_set,
+            &mut self.char_sets,
+        )
     }
 
     #[cfg(test)]
@@ -450,6 +1143,28 @@ impl Config {
     }
 }
 
+/// Swaps the active value with the next one from others, wrapping around to
+/// the first name, and puts the previously active value back into others.
+fn cycle<T>(
+    name: &mut String,
+    active: &mut T,
+    others: &mut HashMap<String, T>,
+) -> bool {
+    let next = others
+        .keys()
+        .filter(|other| other.as_str() > name.as_str())
+        .min()
+        .or_else(|| others.keys().min())
+        .cloned();
+    let Some((next, value)) = next.and_then(|next| others.remove_entry(&next))
+    else {
+        return false;
+    };
+    let previous = std::mem::replace(active, value);
+    others.insert(std::mem::replace(name, next), previous);
+    true
+}
+
 #[cfg(test)]
 /// Parse a config file without applying any defaults.
 pub mod strict {
@@ -464,52 +1179,124 @@ pub mod strict {
     #[serde(deny_unknown_fields)]
     pub struct ConfigFile {
         remote: Option<String>,
+        remotes: Vec<String>,
+        osc_listen: Option<SocketAddr>,
+        on_default_change: Option<String>,
         fps: Option<f32>,
         mouse: bool,
         peaks: Option<Peaks>,
         char_set: String,
         theme: String,
         max_volume_percent: Option<f32>,
+        min_volume_percent: f32,
         enforce_max_volume: bool,
+        volume_unit: VolumeUnit,
+        volume_overlay: bool,
+        min_title_width: u16,
+        wrap_target: bool,
+        show_format: bool,
+        show_latency: bool,
+        show_process: bool,
+        show_links: ShowLinks,
+        show_ids: bool,
+        show_monitor_sources: bool,
+        status_bar: bool,
+        hide_muted: bool,
+        hide_idle: bool,
+        meter_orientation: MeterOrientation,
+        node_layout: NodeLayout,
+        cycle_default_target: bool,
+        group_streams: bool,
+        confirm_profile_change: bool,
+        confirm_device_mute: bool,
+        remember_profile_defaults: bool,
+        remember_volumes: bool,
+        reset_volume_unmutes: bool,
+        wrap_navigation: bool,
+        invert_scroll: bool,
+        metadata_editing: bool,
         #[serde(deserialize_with = "keybindings")]
-        keybindings: HashMap<KeyEvent, Action>,
+        keybindings: HashMap<KeyEvent, Vec<Action>>,
         names: Names,
         #[serde(deserialize_with = "charsets")]
         char_sets: HashMap<String, CharSet>,
         #[serde(deserialize_with = "themes")]
         themes: HashMap<String, Theme>,
         tab: Option<TabKind>,
-        tabs: Vec<TabKind>,
+        tabs: Vec<TabEntry>,
+        select: Option<String>,
         lazy_capture: bool,
         filters: Vec<Filter>,
+        startup: Startup,
+        presets: HashMap<String, Vec<PresetRule>>,
+        meter: Meter,
+        volume: Volume,
+        sort: Sort,
+        default_keys: DefaultKeys,
     }
 
     impl From<ConfigFile> for super::ConfigFile {
         fn from(strict: ConfigFile) -> Self {
             super::ConfigFile {
                 remote: strict.remote,
+                remotes: strict.remotes,
+                osc_listen: strict.osc_listen,
+                on_default_change: strict.on_default_change,
                 fps: strict.fps,
                 mouse: strict.mouse,
                 peaks: strict.peaks,
                 char_set: strict.char_set,
                 theme: strict.theme,
                 max_volume_percent: strict.max_volume_percent,
+                min_volume_percent: strict.min_volume_percent,
                 enforce_max_volume: strict.enforce_max_volume,
+                volume_unit: strict.volume_unit,
+                volume_overlay: strict.volume_overlay,
+                min_title_width: strict.min_title_width,
+                wrap_target: strict.wrap_target,
+                show_format: strict.show_format,
+                show_latency: strict.show_latency,
+                show_process: strict.show_process,
+                show_links: strict.show_links,
+                show_ids: strict.show_ids,
+                show_monitor_sources: strict.show_monitor_sources,
+                status_bar: strict.status_bar,
+                hide_muted: strict.hide_muted,
+                hide_idle: strict.hide_idle,
+                meter_orientation: strict.meter_orientation,
+                node_layout: strict.node_layout,
+                cycle_default_target: strict.cycle_default_target,
+                group_streams: strict.group_streams,
+                confirm_profile_change: strict.confirm_profile_change,
+                confirm_device_mute: strict.confirm_device_mute,
+                remember_profile_defaults: strict.remember_profile_defaults,
+                remember_volumes: strict.remember_volumes,
+                reset_volume_unmutes: strict.reset_volume_unmutes,
+                wrap_navigation: strict.wrap_navigation,
+                invert_scroll: strict.invert_scroll,
+                metadata_editing: strict.metadata_editing,
                 keybindings: strict.keybindings,
                 names: strict.names,
                 char_sets: strict.char_sets,
                 themes: strict.themes,
                 tab: strict.tab,
                 tabs: strict.tabs,
+                select: strict.select,
                 lazy_capture: strict.lazy_capture,
                 filters: strict.filters,
+                startup: strict.startup,
+                presets: strict.presets,
+                meter: strict.meter,
+                volume: strict.volume,
+                sort: strict.sort,
+                default_keys: strict.default_keys,
             }
         }
     }
 
     fn keybindings<'de, D>(
         deserializer: D,
-    ) -> Result<HashMap<KeyEvent, Action>, D::Error>
+    ) -> Result<HashMap<KeyEvent, Vec<Action>>, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
@@ -521,7 +1308,7 @@ pub mod strict {
                     keybinding.action,
                 )
             })
-            .collect::<HashMap<KeyEvent, Action>>())
+            .collect::<HashMap<KeyEvent, Vec<Action>>>())
     }
 
     fn charsets<'de, D>(
@@ -708,6 +1495,63 @@ mod tests {
         assert_eq!(config.tabs, vec![TabKind::Playback, TabKind::Input]);
     }
 
+    #[test]
+    fn tabs_with_titles() {
+        let config = Config::from_toml_str(
+            r#"
+            tab = "all"
+            tabs = [
+              "playback",
+              { kind = "all", title = "Everything" },
+            ]
+            "#,
+        );
+        assert_eq!(config.tabs, vec![TabKind::Playback, TabKind::All]);
+        assert_eq!(config.tab, 1);
+        assert_eq!(
+            config.tab_titles.get(&TabKind::All).map(String::as_str),
+            Some("Everything")
+        );
+        assert!(!config.tab_titles.contains_key(&TabKind::Playback));
+    }
+
+    #[test]
+    fn tabs_with_peaks() {
+        let config = Config::from_toml_str(
+            r#"
+            peaks = "mono"
+            tabs = [
+              "playback",
+              { kind = "configuration", peaks = "off" },
+              { kind = "all", title = "Everything", peaks = "channels" },
+            ]
+            "#,
+        );
+        assert_eq!(config.peaks_for(TabKind::Playback), Peaks::Mono);
+        assert_eq!(config.peaks_for(TabKind::Configuration), Peaks::Off);
+        assert_eq!(config.peaks_for(TabKind::All), Peaks::Channels);
+        assert!(!config.tab_titles.contains_key(&TabKind::Configuration));
+    }
+
+    #[test]
+    fn opt_tabs_keep_configured_titles() {
+        let mut config_file: ConfigFile =
+            toml::from_str(r#"tabs = [ { kind = "input", title = "Mics" } ]"#)
+                .unwrap();
+        let opt = Opt {
+            tab: Some(TabKind::Input),
+            tabs: Some(vec![TabKind::Output, TabKind::Input]),
+            ..Default::default()
+        };
+        config_file.apply_opt(&opt);
+        let config = Config::try_from(config_file).unwrap();
+        assert_eq!(config.tabs, vec![TabKind::Output, TabKind::Input]);
+        assert_eq!(
+            config.tab_titles.get(&TabKind::Input).map(String::as_str),
+            Some("Mics")
+        );
+    }
+
     #[test]
     fn name_override_with_matches() {
         let config = r#"
@@ -718,4 +1562,261 @@ mod tests {
         "#;
         assert_eq!(Config::from_toml_str(config).names.overrides.len(), 1);
     }
+
+    #[test]
+    fn volume_unit_defaults_to_percent() {
+        let config = Config::from_toml_str("");
+        assert_eq!(config.volume_unit, VolumeUnit::Percent);
+    }
+
+    #[test]
+    fn volume_unit_db() {
+        let config = Config::from_toml_str(r#"volume_unit = "db""#);
+        assert_eq!(config.volume_unit, VolumeUnit::Db);
+    }
+
+    #[test]
+    fn meter_orientation_vertical() {
+        let config = Config::from_toml_str(r#"meter_orientation = "vertical""#);
+        assert_eq!(config.meter_orientation, MeterOrientation::Vertical);
+    }
+
+    #[test]
+    fn meter_defaults() {
+        let config = Config::from_toml_str("");
+        assert_eq!(config.meter.decay_ms, 300.0);
+        assert_eq!(config.meter.attack_ms, None);
+        assert_eq!(config.meter.hold_ms, 0.0);
+        assert_eq!(config.meter.clip_ms, 0.0);
+        assert_eq!(config.meter.history, 0);
+        assert_eq!(config.meter.scale, MeterScale::Linear);
+        assert_eq!(config.meter.db_range, 60.0);
+        assert_eq!(config.meter.scroll, MeterScroll::Volume);
+    }
+
+    #[test]
+    fn meter_negative_decay() {
+        let config = r#"
+            [meter]
+            decay_ms = -1.0
+        "#;
+        let config_file: ConfigFile = toml::from_str(config).unwrap();
+        assert!(Config::try_from(config_file).is_err());
+    }
+
+    #[test]
+    fn meter_nan_time() {
+        for key in ["decay_ms", "attack_ms", "hold_ms", "clip_ms"] {
+            let config = format!("meter = {{ {key} = nan }}");
+            let config_file: ConfigFile = toml::from_str(&config).unwrap();
+            assert!(Config::try_from(config_file).is_err(), "{key}");
+        }
+    }
+
+    #[test]
+    fn volume_accel_section() {
+        let config = Config::from_toml_str("");
+        assert!(!config.volume.accel.enabled);
+
+        let config = Config::from_toml_str(
+            r#"
+            [volume.accel]
+            enabled = true
+            max_multiplier = 8.0
+        "#,
+        );
+        let accel = &config.volume.accel;
+        assert!(accel.enabled);
+        assert_eq!(accel.threshold_ms, 100.0);
+        assert_eq!(accel.max_multiplier, 8.0);
+
+        let config = r#"
+            [volume.accel]
+            max_multiplier = 0.5
+        "#;
+        let config_file: ConfigFile = toml::from_str(config).unwrap();
+        assert!(Config::try_from(config_file).is_err());
+    }
+
+    #[test]
+    fn min_volume_exceeds_max() {
+        let config = r#"
+            max_volume_percent = 100.0
+            min_volume_percent = 110.0
+        "#;
+        let config_file: ConfigFile = toml::from_str(config).unwrap();
+        assert!(Config::try_from(config_file).is_err());
+    }
+
+    #[test]
+    fn default_keys() {
+        let config = r#"
+            [default_keys]
+            sink = "default.audio.sink"
+        "#;
+        let config_file: ConfigFile = toml::from_str(config).unwrap();
+        let config = Config::try_from(config_file).unwrap();
+        assert_eq!(config.default_keys.sink, "default.audio.sink");
+        assert_eq!(
+            config.default_keys.source,
+            "default.configured.audio.source"
+        );
+
+        let config = r#"
+            [default_keys]
+            source = ""
+        "#;
+        let config_file: ConfigFile = toml::from_str(config).unwrap();
+        assert!(Config::try_from(config_file).is_err());
+    }
+
+    #[test]
+    fn sort_per_tab() {
+        let config = r#"
+            [sort]
+            playback = "name"
+            output = "volume"
+        "#;
+        let config = Config::from_toml_str(config);
+        assert_eq!(config.sort.get(TabKind::Playback), SortMode::Name);
+        assert_eq!(config.sort.get(TabKind::Recording), SortMode::Serial);
+        assert_eq!(config.sort.get(TabKind::Output), SortMode::Volume);
+    }
+
+    #[test]
+    fn peaks_cycle() {
+        assert_eq!(Peaks::Off.next(), Peaks::Mono);
+        assert_eq!(Peaks::Mono.next(), Peaks::Auto);
+        assert_eq!(Peaks::Auto.next(), Peaks::Off);
+        assert_eq!(Peaks::Channels.next(), Peaks::Off);
+    }
+
+    #[test]
+    fn sort_mode_cycles() {
+        assert_eq!(SortMode::Serial.next(), SortMode::Name);
+        assert_eq!(SortMode::Name.next(), SortMode::Volume);
+        assert_eq!(SortMode::Volume.next(), SortMode::Serial);
+    }
+
+    #[test]
+    fn startup_disabled_by_default() {
+        let config = Config::from_toml_str("");
+        assert!(!config.startup.enabled);
+        assert!(!config.startup.clear_targets);
+        assert_eq!(config.startup.default_sink, None);
+    }
+
+    #[test]
+    fn presets_section() {
+        let config = r#"
+            [[presets.meeting]]
+            matches = [{ "node.name" = "~^Firefox" }]
+            volume = 30
+            [[presets.meeting]]
+            matches = [{ "media.class" = "Audio/Source" }]
+            mute = false
+        "#;
+        let config = Config::from_toml_str(config);
+        let rules = &config.presets["meeting"];
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].volume, Some(30.0));
+        assert_eq!(rules[0].mute, None);
+        assert_eq!(rules[1].mute, Some(false));
+    }
+
+    #[test]
+    fn preset_rule_without_change_is_error() {
+        let config = r#"
+            [[presets.meeting]]
+            matches = [{ "node.name" = "spotify" }]
+        "#;
+        let config_file: ConfigFile = toml::from_str(config).unwrap();
+        assert!(Config::try_from(config_file).is_err());
+    }
+
+    #[test]
+    fn startup_section() {
+        let config = r#"
+            [startup]
+            enabled = true
+            default_sink = "alsa_output.speakers"
+            clear_targets = true
+        "#;
+        let config = Config::from_toml_str(config);
+        assert!(config.startup.enabled);
+        assert!(config.startup.clear_targets);
+        assert_eq!(
+            config.startup.default_sink.as_deref(),
+            Some("alsa_output.speakers")
+        );
+        assert_eq!(config.startup.default_source, None);
+    }
+
+    #[test]
+    fn configured_tab_keys_override_defaults() {
+        let config = Config::from_toml_str(
+            r#"
+            keybindings = [
+              { key = { Char = "1" }, modifiers = "ALT", action = "Nothing" },
+            ]
+            "#,
+        );
+        let alt = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+        assert_eq!(
+            config.keybindings.get(&alt('1')),
+            Some(&vec![Action::Nothing])
+        );
+        assert_eq!(
+            config.keybindings.get(&alt('2')),
+            Some(&vec![Action::SelectTab(1)])
+        );
+        // Plain number keys still set the volume.
+        assert_eq!(
+            config
+                .keybindings
+                .get(&KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE)),
+            Some(&vec![Action::SetAbsoluteVolume(0.10)])
+        );
+    }
+
+    #[test]
+    fn keybinding_action_sequence() {
+        let config = Config::from_toml_str(
+            r#"
+            keybindings = [
+              { key = { Char = "n" }, action = ["MoveDown", "SetDefault"] },
+            ]
+            "#,
+        );
+        let n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(
+            config.keybindings.get(&n),
+            Some(&vec![Action::MoveDown, Action::SetDefault])
+        );
+    }
+
+    #[test]
+    fn cycle_themes() {
+        let mut config = Config::from_toml_str(r#"theme = "nocolor""#);
+        assert!(config.next_theme());
+        assert_eq!(config.theme_name, "plain");
+        assert!(config.next_theme());
+        assert_eq!(config.theme_name, "default");
+        assert!(config.themes.contains_key("plain"));
+        assert!(!config.themes.contains_key("default"));
+
+        let mut config = Config::from_toml_str(r#"char_set = "compat""#);
+        assert!(config.next_char_set());
+        assert_eq!(config.char_set_name, "default");
+        assert!(config.next_char_set());
+        assert_eq!(config.char_set_name, "extracompat");
+    }
+
+    #[test]
+    fn unknown_field_startup() {
+        let config = r#"
+        unknown = "unknown"
+        "#;
+        assert!(toml::from_str::<Startup>(config).is_err());
+    }
 }
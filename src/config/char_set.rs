@@ -21,8 +21,13 @@ pub struct CharSetOverlay {
     tab_marker_left: Option<String>,
     tab_marker_right: Option<String>,
     list_more: Option<String>,
+    allow_wide_glyphs: Option<bool>,
     volume_empty: Option<String>,
     volume_filled: Option<String>,
+    volume_hardware: Option<String>,
+    volume_software: Option<String>,
+    node_virtual: Option<String>,
+    node_network: Option<String>,
     meter_left_inactive: Option<String>,
     meter_left_active: Option<String>,
     meter_left_overload: Option<String>,
@@ -33,6 +38,7 @@ pub struct CharSetOverlay {
     meter_center_left_active: Option<String>,
     meter_center_right_inactive: Option<String>,
     meter_center_right_active: Option<String>,
+    meter_hold: Option<String>,
     dropdown_icon: Option<String>,
     dropdown_selector: Option<String>,
     dropdown_more: Option<String>,
@@ -78,7 +84,32 @@ impl TryFrom<CharSetOverlay> for CharSet {
             None => CharSet::default(),
         };
 
+        if let Some(allow_wide_glyphs) = overlay.allow_wide_glyphs {
+            char_set.allow_wide_glyphs = allow_wide_glyphs;
+        }
+
         macro_rules! validate_and_set {
+            // Volume and meter bar glyphs may be 2 columns wide if
+            // allow_wide_glyphs is set.
+            ($field:ident, wide) => {
+                if let Some(value) = overlay.$field {
+                    let width = Span::raw(&value).width();
+                    if char_set.allow_wide_glyphs {
+                        if width != 1 && width != 2 {
+                            anyhow::bail!(
+                                "{} must be 1 or 2 characters wide",
+                                stringify!($field)
+                            );
+                        }
+                    } else if width != 1 {
+                        anyhow::bail!(
+                            "{} must be 1 characters wide",
+                            stringify!($field)
+                        );
+                    }
+                    char_set.$field = value;
+                }
+            };
             // Overwrite default char with char from overlay while validating
             // width. Length of 0 means don't check width.
             ($field:ident, $length:expr) => {
@@ -103,23 +134,51 @@ impl TryFrom<CharSetOverlay> for CharSet {
         validate_and_set!(tab_marker_left, 1);
         validate_and_set!(tab_marker_right, 1);
         validate_and_set!(list_more, 0);
-        validate_and_set!(volume_empty, 1);
-        validate_and_set!(volume_filled, 1);
-        validate_and_set!(meter_left_inactive, 1);
-        validate_and_set!(meter_left_active, 1);
-        validate_and_set!(meter_left_overload, 1);
-        validate_and_set!(meter_right_inactive, 1);
-        validate_and_set!(meter_right_active, 1);
-        validate_and_set!(meter_right_overload, 1);
+        validate_and_set!(volume_empty, wide);
+        validate_and_set!(volume_filled, wide);
+        validate_and_set!(volume_hardware, 0);
+        validate_and_set!(volume_software, 0);
+        validate_and_set!(node_virtual, 0);
+        validate_and_set!(node_network, 0);
+        validate_and_set!(meter_left_inactive, wide);
+        validate_and_set!(meter_left_active, wide);
+        validate_and_set!(meter_left_overload, wide);
+        validate_and_set!(meter_right_inactive, wide);
+        validate_and_set!(meter_right_active, wide);
+        validate_and_set!(meter_right_overload, wide);
         validate_and_set!(meter_center_left_inactive, 1);
         validate_and_set!(meter_center_left_active, 1);
         validate_and_set!(meter_center_right_inactive, 1);
         validate_and_set!(meter_center_right_active, 1);
+        validate_and_set!(meter_hold, wide);
         validate_and_set!(dropdown_icon, 1);
         validate_and_set!(dropdown_selector, 1);
         validate_and_set!(dropdown_more, 0);
         validate_and_set!(help_more, 0);
 
+        // Bars are drawn in steps of one glyph, so each bar's glyphs need to
+        // be the same width.
+        if Span::raw(&char_set.volume_empty).width()
+            != Span::raw(&char_set.volume_filled).width()
+        {
+            anyhow::bail!("volume glyphs must all be the same width");
+        }
+        let meter_width = char_set.meter_glyph_width();
+        if [
+            &char_set.meter_left_inactive,
+            &char_set.meter_left_active,
+            &char_set.meter_left_overload,
+            &char_set.meter_right_inactive,
+            &char_set.meter_right_active,
+            &char_set.meter_right_overload,
+            &char_set.meter_hold,
+        ]
+        .iter()
+        .any(|glyph| usize::from(meter_width) != Span::raw(*glyph).width())
+        {
+            anyhow::bail!("meter glyphs must all be the same width");
+        }
+
         if let Some(dropdown_border) = overlay.dropdown_border {
             char_set.dropdown_border = dropdown_border.into();
         }
@@ -143,8 +202,13 @@ impl Default for CharSet {
             tab_marker_left: String::from("["),
             tab_marker_right: String::from("]"),
             list_more: String::from("•••"),
+            allow_wide_glyphs: false,
             volume_empty: String::from("╌"),
             volume_filled: String::from("━"),
+            volume_hardware: String::from("▣"),
+            volume_software: String::from("▢"),
+            node_virtual: String::new(),
+            node_network: String::new(),
             meter_left_inactive: String::from("▮"),
             meter_left_active: String::from("▮"),
             meter_left_overload: String::from("▮"),
@@ -155,6 +219,7 @@ impl Default for CharSet {
             meter_center_left_active: String::from("▮"),
             meter_center_right_inactive: String::from("▮"),
             meter_center_right_active: String::from("▮"),
+            meter_hold: String::from("▮"),
             dropdown_icon: String::from("▼"),
             dropdown_selector: String::from(">"),
             dropdown_more: String::from("•••"),
@@ -166,6 +231,16 @@ impl Default for CharSet {
 }
 
 impl CharSet {
+    /// Returns the width in columns of each step of a volume bar.
+    pub fn volume_glyph_width(&self) -> u16 {
+        glyph_width(&self.volume_filled)
+    }
+
+    /// Returns the width in columns of each step of a peak meter.
+    pub fn meter_glyph_width(&self) -> u16 {
+        glyph_width(&self.meter_right_active)
+    }
+
     pub fn defaults() -> HashMap<String, CharSet> {
         HashMap::from([
             (String::from("default"), CharSet::default()),
@@ -184,8 +259,13 @@ impl CharSet {
             tab_marker_left: String::from("["),
             tab_marker_right: String::from("]"),
             list_more: String::from("•••"),
+            allow_wide_glyphs: false,
             volume_empty: String::from("─"),
             volume_filled: String::from("━"),
+            volume_hardware: String::from("■"),
+            volume_software: String::from("□"),
+            node_virtual: String::new(),
+            node_network: String::new(),
             meter_left_inactive: String::from("┃"),
             meter_left_active: String::from("┃"),
             meter_left_overload: String::from("┃"),
@@ -196,6 +276,7 @@ impl CharSet {
             meter_center_left_active: String::from("█"),
             meter_center_right_inactive: String::from("█"),
             meter_center_right_active: String::from("█"),
+            meter_hold: String::from("┃"),
             dropdown_icon: String::from("▼"),
             dropdown_selector: String::from(">"),
             dropdown_more: String::from("•••"),
@@ -215,8 +296,13 @@ impl CharSet {
             tab_marker_left: String::from("["),
             tab_marker_right: String::from("]"),
             list_more: String::from("~~~"),
+            allow_wide_glyphs: false,
             volume_empty: String::from("-"),
             volume_filled: String::from("="),
+            volume_hardware: String::from("hw"),
+            volume_software: String::from("sw"),
+            node_virtual: String::new(),
+            node_network: String::new(),
             meter_left_inactive: String::from("="),
             meter_left_active: String::from("#"),
             meter_left_overload: String::from("!"),
@@ -227,6 +313,7 @@ impl CharSet {
             meter_center_left_active: String::from("["),
             meter_center_right_inactive: String::from("]"),
             meter_center_right_active: String::from("]"),
+            meter_hold: String::from("|"),
             dropdown_icon: String::from("\\"),
             dropdown_selector: String::from(">"),
             dropdown_more: String::from("~~~"),
@@ -266,6 +353,12 @@ impl CharSet {
     }
 }
 
+/// Returns the width of a bar glyph, which is at least 1 so that it can be
+/// divided by.
+fn glyph_width(glyph: &str) -> u16 {
+    Span::raw(glyph).width().clamp(1, 2) as u16
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,6 +453,34 @@ mod tests {
         assert!(char_set.is_err());
     }
 
+    #[test]
+    fn allow_wide_glyphs() {
+        let config = r#"
+        allow_wide_glyphs = true
+        meter_left_inactive = "⚫"
+        meter_left_active = "🟢"
+        meter_left_overload = "🔴"
+        meter_right_inactive = "⚫"
+        meter_right_active = "🟢"
+        meter_right_overload = "🔴"
+        meter_hold = "🟡"
+        "#;
+
+        let overlay = toml::from_str::<CharSetOverlay>(config).unwrap();
+        let char_set = CharSet::try_from(overlay).unwrap();
+        assert_eq!(char_set.meter_glyph_width(), 2);
+        assert_eq!(char_set.volume_glyph_width(), 1);
+
+        // Mixing widths within a meter isn't allowed.
+        let config = r#"
+        allow_wide_glyphs = true
+        meter_right_active = "🟢"
+        "#;
+
+        let overlay = toml::from_str::<CharSetOverlay>(config).unwrap();
+        assert!(CharSet::try_from(overlay).is_err());
+    }
+
     #[test]
     fn width_unlimited() {
         let config = r#"
@@ -16,15 +16,19 @@ pub struct Help {
     pub widths: [usize; 2],
 }
 
-impl From<&HashMap<KeyEvent, Action>> for Help {
-    fn from(keybindings: &HashMap<KeyEvent, Action>) -> Self {
+impl From<&HashMap<KeyEvent, Vec<Action>>> for Help {
+    fn from(keybindings: &HashMap<KeyEvent, Vec<Action>>) -> Self {
         let mut sorted: Vec<_> = keybindings
             .iter()
-            .filter(|(_, action)| !matches!(action, Action::Nothing))
+            .filter(|(_, actions)| {
+                !actions
+                    .iter()
+                    .all(|action| matches!(action, Action::Nothing))
+            })
             .collect();
-        sorted.sort_by(|(a_key, a_action), (b_key, b_action)| {
-            a_action
-                .partial_cmp(b_action)
+        sorted.sort_by(|(a_key, a_actions), (b_key, b_actions)| {
+            a_actions
+                .partial_cmp(b_actions)
                 .unwrap_or(std::cmp::Ordering::Equal)
                 .then_with(|| {
                     a_key
@@ -42,13 +46,38 @@ impl From<&HashMap<KeyEvent, Action>> for Help {
 }
 
 impl Help {
-    fn generate_rows(bindings: &[(&KeyEvent, &Action)]) -> Vec<[String; 2]> {
+    /// Returns the key bound to action alone, for showing as a hint. The
+    /// first key in sort order is used if there are several.
+    pub fn key_for(
+        keybindings: &HashMap<KeyEvent, Vec<Action>>,
+        action: &Action,
+    ) -> Option<String> {
+        keybindings
+            .iter()
+            .filter(|(_, actions)| {
+                actions.as_slice() == std::slice::from_ref(action)
+            })
+            .map(|(key, _)| key)
+            .min_by(|a, b| {
+                a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(Self::format_key)
+    }
+
+    fn generate_rows(
+        bindings: &[(&KeyEvent, &Vec<Action>)],
+    ) -> Vec<[String; 2]> {
         let mut rows = Vec::new();
         let mut last_action = String::new();
 
-        for (key, action) in bindings {
+        for (key, actions) in bindings {
             let key_string = Self::format_key(key);
-            let action_string = action.to_string();
+            let action_string = actions
+                .iter()
+                .filter(|action| !matches!(action, Action::Nothing))
+                .map(|action| action.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
 
             let action_display = if last_action == action_string {
                 String::new() // Don't repeat the action name
@@ -119,6 +148,7 @@ mod tests {
             Action::SetAbsoluteVolume(0.5).to_string(),
             "Set volume to 50%",
         );
+        assert_eq!(Action::SelectTab(0).to_string(), "Select tab 1");
     }
 
     #[test]
@@ -134,7 +164,7 @@ mod tests {
         let mut keybindings = HashMap::new();
         keybindings.insert(
             KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
-            Action::Help,
+            vec![Action::Help],
         );
 
         let help = Help::from(&keybindings);
@@ -152,11 +182,11 @@ mod tests {
         let mut keybindings = HashMap::new();
         keybindings.insert(
             KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
-            Action::Help,
+            vec![Action::Help],
         );
         keybindings.insert(
             KeyEvent::new(KeyCode::Char('.'), KeyModifiers::NONE),
-            Action::Nothing,
+            vec![Action::Nothing],
         );
 
         let help = Help::from(&keybindings);
@@ -174,11 +204,11 @@ mod tests {
         let mut keybindings = HashMap::new();
         keybindings.insert(
             KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
-            Action::Help,
+            vec![Action::Help],
         );
         keybindings.insert(
             KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE),
-            Action::Help,
+            vec![Action::Help],
         );
 
         let help = Help::from(&keybindings);
@@ -194,4 +224,41 @@ mod tests {
         assert_eq!(help.widths[0], "Show/hide help".len());
         assert_eq!(help.widths[1], "F1".len());
     }
+
+    #[test]
+    fn help_action_sequence() {
+        let mut keybindings = HashMap::new();
+        keybindings.insert(
+            KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE),
+            vec![Action::MoveDown, Action::SetDefault],
+        );
+
+        let help = Help::from(&keybindings);
+        assert_eq!(
+            help.rows,
+            vec![[
+                String::from("Move cursor down, Set default"),
+                String::from("n")
+            ]]
+        );
+    }
+
+    #[test]
+    fn key_for_single_action() {
+        let mut keybindings = HashMap::new();
+        keybindings.insert(
+            KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE),
+            vec![Action::MoveDown, Action::SetDefault],
+        );
+        keybindings.insert(
+            KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
+            vec![Action::SetDefault],
+        );
+
+        assert_eq!(
+            Help::key_for(&keybindings, &Action::SetDefault).as_deref(),
+            Some("d")
+        );
+        assert_eq!(Help::key_for(&keybindings, &Action::MoveDown), None);
+    }
 }
@@ -11,13 +11,46 @@ use serde::Deserialize;
 use crate::config::{Action, Keybinding};
 
 impl Keybinding {
-    pub fn defaults() -> HashMap<KeyEvent, Action> {
+    pub fn defaults() -> HashMap<KeyEvent, Vec<Action>> {
         let event = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let alt = |code| KeyEvent::new(code, KeyModifiers::ALT);
 
-        HashMap::from([
+        [
             (event(KeyCode::Char('q')), Action::Exit),
             (event(KeyCode::Char('m')), Action::ToggleMute),
             (event(KeyCode::Char('d')), Action::SetDefault),
+            (event(KeyCode::Char('D')), Action::CycleDefault),
+            (event(KeyCode::Char('o')), Action::SelectDefault),
+            (event(KeyCode::Char('t')), Action::ToggleTarget),
+            (event(KeyCode::Char(']')), Action::NextTarget),
+            (event(KeyCode::Char('[')), Action::PrevTarget),
+            (event(KeyCode::Char('e')), Action::ToggleExpanded),
+            (event(KeyCode::Char('E')), Action::Focus),
+            (event(KeyCode::Char('<')), Action::SetBalance(-0.01)),
+            (event(KeyCode::Char('>')), Action::SetBalance(0.01)),
+            (event(KeyCode::Char('b')), Action::ToggleBalanceLink),
+            (event(KeyCode::Char('|')), Action::CenterBalance),
+            (event(KeyCode::Char('/')), Action::StartSearch),
+            (event(KeyCode::Char('s')), Action::CycleSort),
+            (event(KeyCode::Char('v')), Action::EnterVolume),
+            (event(KeyCode::Char('x')), Action::ClearClip),
+            (event(KeyCode::Char('C')), Action::ResetPeakHold),
+            (event(KeyCode::Char('g')), Action::ToggleGroup),
+            (event(KeyCode::Char('=')), Action::ResetVolume),
+            (event(KeyCode::Char('u')), Action::ToggleLock),
+            (event(KeyCode::Char('P')), Action::TogglePin),
+            (event(KeyCode::Char('V')), Action::TogglePeaks),
+            (event(KeyCode::Char('M')), Action::MuteAll),
+            (event(KeyCode::Char('N')), Action::NormalizeGroup),
+            (event(KeyCode::Char('i')), Action::ToggleMonitorSources),
+            (event(KeyCode::Char('z')), Action::ToggleHiddenStreams),
+            (event(KeyCode::Char('f')), Action::ToggleStatusBar),
+            (event(KeyCode::Char('p')), Action::ShowDetails),
+            (event(KeyCode::Char('y')), Action::CopyName),
+            (event(KeyCode::Char('T')), Action::NextTheme),
+            (event(KeyCode::Char('S')), Action::NextCharSet),
+            (event(KeyCode::Char('R')), Action::PickRemote),
+            (event(KeyCode::Char('r')), Action::RereadParams),
             (event(KeyCode::Char('l')), Action::SetRelativeVolume(0.01)),
             (event(KeyCode::Right), Action::SetRelativeVolume(0.01)),
             (event(KeyCode::Char('h')), Action::SetRelativeVolume(-0.01)),
@@ -36,6 +69,11 @@ impl Keybinding {
                 Action::TabLeft,
             ),
             (event(KeyCode::Tab), Action::TabRight),
+            (alt(KeyCode::Char('1')), Action::SelectTab(0)),
+            (alt(KeyCode::Char('2')), Action::SelectTab(1)),
+            (alt(KeyCode::Char('3')), Action::SelectTab(2)),
+            (alt(KeyCode::Char('4')), Action::SelectTab(3)),
+            (alt(KeyCode::Char('5')), Action::SelectTab(4)),
             (event(KeyCode::Char('`')), Action::SetAbsoluteVolume(0.00)),
             (event(KeyCode::Char('1')), Action::SetAbsoluteVolume(0.10)),
             (event(KeyCode::Char('2')), Action::SetAbsoluteVolume(0.20)),
@@ -48,17 +86,38 @@ impl Keybinding {
             (event(KeyCode::Char('9')), Action::SetAbsoluteVolume(0.90)),
             (event(KeyCode::Char('0')), Action::SetAbsoluteVolume(1.00)),
             (event(KeyCode::Char('?')), Action::Help),
-        ])
+        ]
+        .into_iter()
+        .map(|(key, action)| (key, vec![action]))
+        .collect()
     }
 
     pub fn default_modifiers() -> KeyModifiers {
         KeyModifiers::NONE
     }
 
+    /// Deserialize either a single action or a sequence of actions
+    pub fn actions<'de, D>(deserializer: D) -> Result<Vec<Action>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Actions {
+            One(Action),
+            Many(Vec<Action>),
+        }
+
+        Ok(match Actions::deserialize(deserializer)? {
+            Actions::One(action) => vec![action],
+            Actions::Many(actions) => actions,
+        })
+    }
+
     /// Merge deserialized keybindings with defaults
     pub fn merge<'de, D>(
         deserializer: D,
-    ) -> Result<HashMap<KeyEvent, Action>, D::Error>
+    ) -> Result<HashMap<KeyEvent, Vec<Action>>, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
@@ -78,7 +137,7 @@ impl Keybinding {
 
     /// Return keybindings emulating effects of certain terminal special
     /// characters
-    pub fn control_char_keybindings() -> HashMap<KeyEvent, Action> {
+    pub fn control_char_keybindings() -> HashMap<KeyEvent, Vec<Action>> {
         let mut bindings = HashMap::new();
 
         let Ok(termios) = termios::tcgetattr(std::io::stdin().as_fd()) else {
@@ -117,7 +176,7 @@ impl Keybinding {
                 _ => continue,
             };
 
-            bindings.insert(key_event, Action::Exit);
+            bindings.insert(key_event, vec![Action::Exit]);
         }
 
         bindings
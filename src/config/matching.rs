@@ -17,9 +17,9 @@ impl MatchCondition {
         state: &state::State,
         resolver: &(impl PropertyResolver + ?Sized),
     ) -> bool {
-        self.0
-            .iter()
-            .all(|(key, value)| value.matches(resolver.resolve_key(state, key)))
+        self.0.iter().all(|(key, value)| {
+            value.matches(resolver.resolve_key(state, key).as_deref())
+        })
     }
 }
 
@@ -4,6 +4,7 @@
 
 use std::collections::HashMap;
 
+use regex::Regex;
 use serde::Deserialize;
 
 use crate::config::{
@@ -28,22 +29,43 @@ struct NameOverrideRaw {
     // Legacy fields
     property: Option<property_key::PropertyKey>,
     value: Option<String>,
+    #[serde(rename = "match")]
+    match_mode: Option<MatchMode>,
     // New fields
     matches: Option<Vec<matching::MatchCondition>>,
 
     templates: Vec<names::NameTemplate>,
 }
 
+/// How a legacy `value` is compared with the property
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum MatchMode {
+    #[default]
+    Exact,
+    Regex,
+}
+
 impl TryFrom<NameOverrideRaw> for NameOverride {
     type Error = String;
 
     fn try_from(raw: NameOverrideRaw) -> Result<Self, Self::Error> {
+        if raw.match_mode.is_some() && raw.value.is_none() {
+            return Err("`match` requires `property`/`value`".into());
+        }
         let matches = match (raw.matches, raw.property, raw.value) {
             (Some(matches), None, None) => matches,
             (None, Some(property), Some(value)) => {
+                let value = match raw.match_mode.unwrap_or_default() {
+                    MatchMode::Exact => matching::MatchValue::Literal(value),
+                    MatchMode::Regex => matching::MatchValue::Regex(
+                        Regex::new(&value).map_err(|err| {
+                            format!("invalid regex in `value`: {err}")
+                        })?,
+                    ),
+                };
                 vec![matching::MatchCondition(HashMap::from([(
-                    property,
-                    matching::MatchValue::Literal(value),
+                    property, value,
                 )]))]
             }
             (None, None, None) => {
@@ -142,6 +164,52 @@ mod tests {
         assert!(toml::from_str::<NameOverride>(toml).is_err());
     }
 
+    #[test]
+    fn legacy_regex() {
+        let legacy = r#"
+            types = ["stream"]
+            property = "node:node.name"
+            value = "firefox.*"
+            match = "regex"
+            templates = ["{node:node.name}"]
+        "#;
+
+        let new = r#"
+            types = ["stream"]
+            matches = [{ "node:node.name" = "~firefox.*" }]
+            templates = ["{node:node.name}"]
+        "#;
+
+        assert_eq!(
+            toml::from_str::<NameOverride>(legacy).unwrap(),
+            toml::from_str::<NameOverride>(new).unwrap()
+        );
+    }
+
+    #[test]
+    fn legacy_invalid_regex_is_error() {
+        let toml = r#"
+            types = ["stream"]
+            property = "node:node.name"
+            value = "firefox("
+            match = "regex"
+            templates = ["{node:node.name}"]
+        "#;
+        let err = toml::from_str::<NameOverride>(toml).unwrap_err();
+        assert!(err.to_string().contains("invalid regex in `value`"));
+    }
+
+    #[test]
+    fn match_without_value_is_error() {
+        let toml = r#"
+            types = ["stream"]
+            matches = [{ "node:node.name" = "spotify" }]
+            match = "regex"
+            templates = ["{node:node.name}"]
+        "#;
+        assert!(toml::from_str::<NameOverride>(toml).is_err());
+    }
+
     #[test]
     fn legacy_equivalent_to_new() {
         let legacy = r#"
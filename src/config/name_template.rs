@@ -4,10 +4,15 @@
 //! be parsable into PropertyKeys in order for the string to be accepted.
 //! { without a matching } or } without a matching { are invalid.
 //! { and } can be escaped with {{ and }}.
+//! Segments enclosed in {? and } are optional. They are left out of the
+//! rendered string if any tag inside them can't be resolved.
+//! Tags can start with upper:, lower:, or title: to change the case of the
+//! resolved property, or trunc(N): to shorten it to N columns.
 use anyhow::{anyhow, bail};
 use serde_with::DeserializeFromStr;
 
 use crate::config::property_key::PropertyKey;
+use crate::truncate;
 
 #[derive(Debug, DeserializeFromStr)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -20,6 +25,78 @@ pub struct NameTemplate {
 enum Part {
     Literal(String),
     Tag(PropertyKey),
+    /// A tag with transformations applied to the property, innermost last
+    Transformed(Vec<Transform>, PropertyKey),
+    /// A {? } segment which is left out if any of its tags are unresolved
+    Optional(Vec<Part>),
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(test, derive(PartialEq))]
+enum Transform {
+    Upper,
+    Lower,
+    /// Capitalize the first letter of each word
+    Title,
+    /// Shorten to a number of display columns with ellipses
+    Truncate(usize),
+}
+
+impl Transform {
+    /// Splits any transform prefixes off of tag content.
+    fn split(
+        mut tag_content: &str,
+    ) -> Result<(Vec<Self>, &str), anyhow::Error> {
+        let mut transforms = Vec::new();
+        'prefixes: loop {
+            for (prefix, transform) in [
+                ("upper:", Transform::Upper),
+                ("lower:", Transform::Lower),
+                ("title:", Transform::Title),
+            ] {
+                if let Some(rest) = tag_content.strip_prefix(prefix) {
+                    transforms.push(transform);
+                    tag_content = rest;
+                    continue 'prefixes;
+                }
+            }
+            if let Some(rest) = tag_content.strip_prefix("trunc(") {
+                let (width, rest) = rest
+                    .split_once("):")
+                    .ok_or_else(|| anyhow!("\"trunc(\" without \"):\""))?;
+                let width = width.parse().map_err(|_| {
+                    anyhow!("\"{width}\" is not a valid trunc width")
+                })?;
+                transforms.push(Transform::Truncate(width));
+                tag_content = rest;
+                continue;
+            }
+            return Ok((transforms, tag_content));
+        }
+    }
+
+    fn apply(&self, s: &str) -> String {
+        match self {
+            Transform::Truncate(width) => {
+                truncate::with_ellipses(s, *width).into_owned()
+            }
+            Transform::Upper => s.to_uppercase(),
+            Transform::Lower => s.to_lowercase(),
+            Transform::Title => {
+                let mut result = String::with_capacity(s.len());
+                let mut word_start = true;
+                for ch in s.chars() {
+                    if word_start {
+                        result.extend(ch.to_uppercase());
+                    } else {
+                        result.extend(ch.to_lowercase());
+                    }
+                    word_start = ch.is_whitespace();
+                }
+                result
+            }
+        }
+    }
 }
 
 impl std::str::FromStr for NameTemplate {
@@ -32,10 +109,19 @@ impl std::str::FromStr for NameTemplate {
 
 impl NameTemplate {
     fn parse_string(s: &str) -> Result<Self, anyhow::Error> {
-        // Sort string into literal and tag parts while unescaping {{ and }}
-        // to { and }.
-        let mut parts = Vec::new();
         let mut chars = s.chars().peekable();
+        let parts = Self::parse_parts(&mut chars, false)?;
+        Ok(NameTemplate { parts })
+    }
+
+    /// Sorts a string into literal, tag, and optional parts while unescaping
+    /// {{ and }} to { and }. If optional is true, this is the inside of a
+    /// {? } segment and parsing stops at its closing }.
+    fn parse_parts(
+        chars: &mut std::iter::Peekable<std::str::Chars>,
+        optional: bool,
+    ) -> Result<Vec<Part>, anyhow::Error> {
+        let mut parts = Vec::new();
         let mut current_part = String::new();
 
         while let Some(ch) = chars.next() {
@@ -46,21 +132,36 @@ impl NameTemplate {
                         current_part.push('{');
                         chars.next(); // Consume the extra.
                         continue;
+                    }
+
+                    if !current_part.is_empty() {
+                        parts.push(Part::Literal(current_part));
+                        current_part = String::new();
+                    }
+
+                    if chars.peek() == Some(&'?') {
+                        // Start of an optional segment.
+                        chars.next(); // Consume the ?.
+                        parts.push(Part::Optional(Self::parse_parts(
+                            chars, true,
+                        )?));
                     } else {
                         // Start of a tag.
-                        if !current_part.is_empty() {
-                            parts.push(Part::Literal(current_part));
-                            current_part = String::new();
-                        }
+                        let tag_content = Self::parse_tag(chars)?;
+                        let (transforms, key) = Transform::split(&tag_content)?;
+                        let property_key =
+                            key.parse::<PropertyKey>().map_err(|_| {
+                                anyhow!(
+                                    "\"{}\" is not implemented",
+                                    tag_content
+                                )
+                            })?;
 
-                        let tag_content = Self::parse_tag(&mut chars)?;
-                        let property_key = tag_content
-                            .parse::<PropertyKey>()
-                            .map_err(|_| {
-                            anyhow!("\"{}\" is not implemented", tag_content)
-                        })?;
-
-                        parts.push(Part::Tag(property_key));
+                        parts.push(if transforms.is_empty() {
+                            Part::Tag(property_key)
+                        } else {
+                            Part::Transformed(transforms, property_key)
+                        });
                     }
                 }
                 '}' => {
@@ -68,6 +169,12 @@ impl NameTemplate {
                     if chars.peek() == Some(&'}') {
                         current_part.push('}');
                         chars.next(); // Consume the extra.
+                    } else if optional {
+                        // End of the optional segment.
+                        if !current_part.is_empty() {
+                            parts.push(Part::Literal(current_part));
+                        }
+                        return Ok(parts);
                     } else {
                         bail!("'}}' without '{{'");
                     }
@@ -76,11 +183,15 @@ impl NameTemplate {
             }
         }
 
+        if optional {
+            bail!("'{{?' without '}}'");
+        }
+
         if !current_part.is_empty() {
             parts.push(Part::Literal(current_part));
         }
 
-        Ok(NameTemplate { parts })
+        Ok(parts)
     }
 
     fn parse_tag(
@@ -106,14 +217,36 @@ impl NameTemplate {
     pub fn render<T: AsRef<str>>(
         &self,
         lookup: impl Fn(&PropertyKey) -> Option<T>,
+    ) -> Option<String> {
+        Self::render_parts(&self.parts, &lookup)
+    }
+
+    /// Renders parts, failing if any tag can't be resolved. Optional
+    /// segments with unresolved tags are left out instead.
+    fn render_parts<T: AsRef<str>>(
+        parts: &[Part],
+        lookup: &impl Fn(&PropertyKey) -> Option<T>,
     ) -> Option<String> {
         let mut result = String::new();
-        for part in &self.parts {
+        for part in parts {
             match part {
                 Part::Literal(literal) => result.push_str(literal),
                 Part::Tag(property_key) => {
                     result.push_str(lookup(property_key)?.as_ref())
                 }
+                Part::Transformed(transforms, property_key) => {
+                    let value = lookup(property_key)?;
+                    let value = transforms.iter().rev().fold(
+                        String::from(value.as_ref()),
+                        |value, transform| transform.apply(&value),
+                    );
+                    result.push_str(&value);
+                }
+                Part::Optional(parts) => {
+                    if let Some(segment) = Self::render_parts(parts, lookup) {
+                        result.push_str(&segment);
+                    }
+                }
             }
         }
 
@@ -261,4 +394,158 @@ mod tests {
         });
         assert_eq!(rendered, Some(String::from("let foo = bar;")));
     }
+
+    #[test]
+    fn optional_segment() {
+        let s = String::from("{node:node.name}{? : {node:media.name}}");
+        let template: Result<NameTemplate, _> = s.parse();
+        assert!(template.is_ok());
+        assert_eq!(
+            template.unwrap(),
+            NameTemplate {
+                parts: vec![
+                    Part::Tag(PropertyKey::Node(String::from("node.name"))),
+                    Part::Optional(vec![
+                        Part::Literal(String::from(" : ")),
+                        Part::Tag(PropertyKey::Node(String::from(
+                            "media.name"
+                        ))),
+                    ]),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn unclosed_optional_segment() {
+        let s = String::from("{node:node.name}{? : {node:media.name}");
+        let template: Result<NameTemplate, _> = s.parse();
+        assert!(template.is_err());
+    }
+
+    #[test]
+    fn render_optional_segment() {
+        let template: NameTemplate =
+            "{node:node.name}{? : {node:media.name}}!".parse().unwrap();
+        let rendered = template.render(|tag| match tag {
+            PropertyKey::Node(ref s) if s == "node.name" => {
+                Some(String::from("foo"))
+            }
+            PropertyKey::Node(ref s) if s == "media.name" => {
+                Some(String::from("bar"))
+            }
+            _ => None,
+        });
+        assert_eq!(rendered, Some(String::from("foo : bar!")));
+    }
+
+    #[test]
+    fn render_optional_segment_missing_tag() {
+        let template: NameTemplate =
+            "{node:node.name}{? : {node:media.name}}!".parse().unwrap();
+        let rendered = template.render(|tag| match tag {
+            PropertyKey::Node(ref s) if s == "node.name" => {
+                Some(String::from("foo"))
+            }
+            _ => None,
+        });
+        assert_eq!(rendered, Some(String::from("foo!")));
+    }
+
+    #[test]
+    fn transformed_tag() {
+        let s = String::from("{upper:node:node.name}");
+        let template: Result<NameTemplate, _> = s.parse();
+        assert!(template.is_ok());
+        assert_eq!(
+            template.unwrap(),
+            NameTemplate {
+                parts: vec![Part::Transformed(
+                    vec![Transform::Upper],
+                    PropertyKey::Node(String::from("node.name"))
+                )],
+            }
+        );
+    }
+
+    #[test]
+    fn empty_transformed_tag() {
+        let s = String::from("{upper:}");
+        let template: Result<NameTemplate, _> = s.parse();
+        assert!(template.is_err());
+    }
+
+    #[test]
+    fn render_transforms() {
+        let template: NameTemplate =
+            "{upper:node.name} {lower:node.name} {title:node.name}"
+                .parse()
+                .unwrap();
+        let rendered = template.render(|tag| match tag {
+            PropertyKey::Bare(ref s) if s == "node.name" => {
+                Some(String::from("hello wORLD"))
+            }
+            _ => None,
+        });
+        assert_eq!(
+            rendered,
+            Some(String::from("HELLO WORLD hello world Hello World"))
+        );
+    }
+
+    #[test]
+    fn render_transformed_missing_tag() {
+        let template: NameTemplate =
+            "{node:node.name}{upper:device:device.name}"
+                .parse()
+                .unwrap();
+        let rendered = template.render(|tag| match tag {
+            PropertyKey::Node(ref s) if s == "node.name" => {
+                Some(String::from("hello"))
+            }
+            _ => None,
+        });
+        assert_eq!(rendered, None)
+    }
+
+    #[test]
+    fn truncated_tag() {
+        let s = String::from("{upper:trunc(20):node:media.name}");
+        let template: Result<NameTemplate, _> = s.parse();
+        assert!(template.is_ok());
+        assert_eq!(
+            template.unwrap(),
+            NameTemplate {
+                parts: vec![Part::Transformed(
+                    vec![Transform::Upper, Transform::Truncate(20)],
+                    PropertyKey::Node(String::from("media.name"))
+                )],
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_trunc_width() {
+        assert!("{trunc(x):node:media.name}"
+            .parse::<NameTemplate>()
+            .is_err());
+        assert!("{trunc(20)node:media.name}"
+            .parse::<NameTemplate>()
+            .is_err());
+    }
+
+    #[test]
+    fn render_truncated() {
+        let template: NameTemplate =
+            "{trunc(8):media.name} - {upper:trunc(8):media.name}"
+                .parse()
+                .unwrap();
+        let rendered = template.render(|tag| match tag {
+            PropertyKey::Bare(ref s) if s == "media.name" => {
+                Some(String::from("A very long title"))
+            }
+            _ => None,
+        });
+        assert_eq!(rendered, Some(String::from("A ver... - A VER...")));
+    }
 }
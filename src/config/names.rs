@@ -11,7 +11,13 @@ use crate::wirehose::media_class;
 
 impl Names {
     pub fn default_stream() -> Vec<NameTemplate> {
-        vec!["{node:node.name}: {node:media.name}".parse().unwrap()]
+        vec![
+            "{node:node.name}: {node:media.artist} - {node:media.title}"
+                .parse()
+                .unwrap(),
+            "{node:node.name}: {node:media.title}".parse().unwrap(),
+            "{node:node.name}: {node:media.name}".parse().unwrap(),
+        ]
     }
 
     pub fn default_endpoint() -> Vec<NameTemplate> {
@@ -371,6 +377,42 @@ mod tests {
         assert_eq!(result, Some(String::from("Node nick")))
     }
 
+    #[test]
+    fn render_stream_media_metadata() {
+        let mut fixture = Fixture::new();
+        let names = Names::default();
+
+        fixture
+            .node_props
+            .set_media_name(String::from("Media name"));
+        fixture.state.update(StateEvent::NodeProperties {
+            object_id: fixture.node_id,
+            props: fixture.node_props.clone(),
+        });
+        let node = fixture.state.nodes.get(&fixture.node_id).unwrap();
+        let result = names.resolve(&fixture.state, node);
+        assert_eq!(result, Some(String::from("Node name: Media name")));
+
+        // Only some metadata is present
+        fixture.node_props.set_media_title(String::from("Title"));
+        fixture.state.update(StateEvent::NodeProperties {
+            object_id: fixture.node_id,
+            props: fixture.node_props.clone(),
+        });
+        let node = fixture.state.nodes.get(&fixture.node_id).unwrap();
+        let result = names.resolve(&fixture.state, node);
+        assert_eq!(result, Some(String::from("Node name: Title")));
+
+        fixture.node_props.set_media_artist(String::from("Artist"));
+        fixture.state.update(StateEvent::NodeProperties {
+            object_id: fixture.node_id,
+            props: fixture.node_props,
+        });
+        let node = fixture.state.nodes.get(&fixture.node_id).unwrap();
+        let result = names.resolve(&fixture.state, node);
+        assert_eq!(result, Some(String::from("Node name: Artist - Title")));
+    }
+
     #[test]
     fn render_stream_linked_client() {
         let mut fixture = Fixture::new();
@@ -408,6 +450,42 @@ mod tests {
         assert_eq!(result, Some(String::from("Node nick")))
     }
 
+    #[test]
+    fn render_port_tags() {
+        let mut fixture = Fixture::new();
+
+        fixture
+            .node_props
+            .set_node_channel_names(String::from("left,right"));
+        fixture.state.update(StateEvent::NodeProperties {
+            object_id: fixture.node_id,
+            props: fixture.node_props,
+        });
+
+        let names = Names {
+            stream: vec![
+                "{port:channels}ch {port:positions} ({port:names})"
+                    .parse()
+                    .unwrap(),
+                "{node:node.nick}".parse().unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        // Positions aren't known yet, so the next template is used.
+        let node = fixture.state.nodes.get(&fixture.node_id).unwrap();
+        let result = names.resolve(&fixture.state, node);
+        assert_eq!(result, Some(String::from("Node nick")));
+
+        fixture.state.update(StateEvent::NodePositions {
+            object_id: fixture.node_id,
+            positions: vec![3, 4],
+        });
+        let node = fixture.state.nodes.get(&fixture.node_id).unwrap();
+        let result = names.resolve(&fixture.state, node);
+        assert_eq!(result, Some(String::from("2ch FL,FR (left, right)")));
+    }
+
     #[test]
     fn render_override_match() {
         let fixture = Fixture::new();
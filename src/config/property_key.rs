@@ -1,5 +1,7 @@
 //! An identifier for a property on an object or a linked object
 
+use std::borrow::Cow;
+
 use serde_with::DeserializeFromStr;
 
 use crate::wirehose::state;
@@ -9,9 +11,94 @@ pub enum PropertyKey {
     Device(String),
     Node(String),
     Client(String),
+    /// Channel layout of a node, derived from its properties rather than
+    /// looked up directly
+    Port(PortKey),
     Bare(String),
 }
 
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum PortKey {
+    /// Number of channels
+    Channels,
+    /// Comma-separated channel positions, e.g. "FL,FR"
+    Positions,
+    /// The node.channel-names property with ", " between names
+    Names,
+}
+
+impl PortKey {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PortKey::Channels => "channels",
+            PortKey::Positions => "positions",
+            PortKey::Names => "names",
+        }
+    }
+
+    fn resolve(&self, node: &state::Node) -> Option<String> {
+        match self {
+            PortKey::Channels => node
+                .positions
+                .as_ref()
+                .map(|positions| positions.len())
+                .or(node.volumes.as_ref().map(|volumes| volumes.len()))
+                .map(|channels| channels.to_string()),
+            PortKey::Positions => node.positions.as_ref().map(|positions| {
+                positions
+                    .iter()
+                    .map(|&position| channel_position_name(position))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }),
+            PortKey::Names => node.props.node_channel_names().map(|names| {
+                names
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }),
+        }
+    }
+}
+
+impl std::str::FromStr for PortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "channels" => Ok(PortKey::Channels),
+            "positions" => Ok(PortKey::Positions),
+            "names" => Ok(PortKey::Names),
+            _ => Err(format!(
+                "Unknown port property \"{s}\" (expected channels, positions, \
+                 or names)"
+            )),
+        }
+    }
+}
+
+/// Returns the short name of an SPA audio channel position.
+pub fn channel_position_name(position: u32) -> String {
+    const NAMES: [&str; 38] = [
+        "UNK", "NA", "MONO", "FL", "FR", "FC", "LFE", "SL", "SR", "FLC", "FRC",
+        "RC", "RL", "RR", "TC", "TFL", "TFC", "TFR", "TRL", "TRC", "TRR",
+        "RLC", "RRC", "FLW", "FRW", "LFE2", "FLH", "FCH", "FRH", "TFLC",
+        "TFRC", "TSL", "TSR", "LLFE", "RLFE", "BC", "BLC", "BRC",
+    ];
+    // SPA_AUDIO_CHANNEL_START_Aux
+    const AUX: u32 = 0x1000;
+
+    match NAMES.get(position as usize) {
+        Some(name) => String::from(*name),
+        None if (AUX..AUX + 0x1000).contains(&position) => {
+            format!("AUX{}", position - AUX)
+        }
+        None => String::from("UNK"),
+    }
+}
+
 #[allow(clippy::to_string_trait_impl)] // This is not for display.
 impl ToString for PropertyKey {
     fn to_string(&self) -> String {
@@ -25,6 +112,9 @@ impl ToString for PropertyKey {
             PropertyKey::Client(s) => {
                 format!("client:{s}")
             }
+            PropertyKey::Port(key) => {
+                format!("port:{}", key.as_str())
+            }
             PropertyKey::Bare(s) => s.to_string(),
         }
     }
@@ -34,6 +124,10 @@ impl std::str::FromStr for PropertyKey {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(key) = s.strip_prefix("port:") {
+            return key.parse().map(PropertyKey::Port);
+        }
+
         let (variant, key): (fn(String) -> PropertyKey, &str) =
             if let Some(key) = s.strip_prefix("client:") {
                 (PropertyKey::Client, key)
@@ -58,7 +152,7 @@ pub trait PropertyResolver {
         &'a self,
         state: &'a state::State,
         key: &PropertyKey,
-    ) -> Option<&'a str>;
+    ) -> Option<Cow<'a, str>>;
 }
 
 impl PropertyResolver for state::Device {
@@ -67,11 +161,14 @@ impl PropertyResolver for state::Device {
         &'a self,
         _state: &'a state::State,
         key: &PropertyKey,
-    ) -> Option<&'a str> {
+    ) -> Option<Cow<'a, str>> {
         match key {
-            PropertyKey::Device(s) | PropertyKey::Bare(s) => self.props.raw(s),
+            PropertyKey::Device(s) | PropertyKey::Bare(s) => {
+                self.props.raw(s).map(Cow::Borrowed)
+            }
             PropertyKey::Node(_) => None,
             PropertyKey::Client(_) => None,
+            PropertyKey::Port(_) => None,
         }
     }
 }
@@ -83,9 +180,11 @@ impl PropertyResolver for state::Node {
         &'a self,
         state: &'a state::State,
         key: &PropertyKey,
-    ) -> Option<&'a str> {
+    ) -> Option<Cow<'a, str>> {
         match key {
-            PropertyKey::Node(s) | PropertyKey::Bare(s) => self.props.raw(s),
+            PropertyKey::Node(s) | PropertyKey::Bare(s) => {
+                self.props.raw(s).map(Cow::Borrowed)
+            }
             PropertyKey::Device(_) => {
                 let device = state.devices.get(self.props.device_id()?)?;
                 device.resolve_key(state, key)
@@ -94,6 +193,9 @@ impl PropertyResolver for state::Node {
                 let client = state.clients.get(self.props.client_id()?)?;
                 client.resolve_key(state, key)
             }
+            PropertyKey::Port(port_key) => {
+                port_key.resolve(self).map(Cow::Owned)
+            }
         }
     }
 }
@@ -104,11 +206,14 @@ impl PropertyResolver for state::Client {
         &'a self,
         _state: &'a state::State,
         key: &PropertyKey,
-    ) -> Option<&'a str> {
+    ) -> Option<Cow<'a, str>> {
         match key {
-            PropertyKey::Client(s) | PropertyKey::Bare(s) => self.props.raw(s),
+            PropertyKey::Client(s) | PropertyKey::Bare(s) => {
+                self.props.raw(s).map(Cow::Borrowed)
+            }
             PropertyKey::Node(_) => None,
             PropertyKey::Device(_) => None,
+            PropertyKey::Port(_) => None,
         }
     }
 }
@@ -150,6 +255,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_port() {
+        assert_eq!(
+            PropertyKey::from_str("port:positions").unwrap(),
+            PropertyKey::Port(PortKey::Positions)
+        );
+        assert_eq!(
+            PropertyKey::from_str("port:channels").unwrap().to_string(),
+            "port:channels"
+        );
+    }
+
+    #[test]
+    fn unknown_port_is_error() {
+        assert!(PropertyKey::from_str("port:node.name").is_err());
+        assert!(PropertyKey::from_str("port:").is_err());
+    }
+
+    #[test]
+    fn channel_position_names() {
+        assert_eq!(channel_position_name(3), "FL");
+        assert_eq!(channel_position_name(37), "BRC");
+        assert_eq!(channel_position_name(0x1002), "AUX2");
+        assert_eq!(channel_position_name(0x3000), "UNK");
+    }
+
     #[test]
     fn empty_bare_is_error() {
         assert!(PropertyKey::from_str("").is_err());
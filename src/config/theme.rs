@@ -13,30 +13,44 @@ pub struct ThemeOverlay {
     default_device: Option<StyleDef>,
     default_stream: Option<StyleDef>,
     selector: Option<StyleDef>,
+    node_selected_bg: Option<StyleDef>,
     tab: Option<StyleDef>,
     tab_selected: Option<StyleDef>,
     tab_marker: Option<StyleDef>,
     list_more: Option<StyleDef>,
     node_title: Option<StyleDef>,
+    node_title_roles: Option<HashMap<String, StyleDef>>,
     node_target: Option<StyleDef>,
+    node_format: Option<StyleDef>,
     volume: Option<StyleDef>,
+    volume_boosted: Option<StyleDef>,
     volume_empty: Option<StyleDef>,
     volume_filled: Option<StyleDef>,
+    volume_control: Option<StyleDef>,
+    node_virtual: Option<StyleDef>,
+    node_network: Option<StyleDef>,
     meter_inactive: Option<StyleDef>,
     meter_active: Option<StyleDef>,
     meter_overload: Option<StyleDef>,
     meter_center_inactive: Option<StyleDef>,
     meter_center_active: Option<StyleDef>,
+    meter_hold: Option<StyleDef>,
+    meter_history: Option<StyleDef>,
+    meter_label: Option<StyleDef>,
     config_device: Option<StyleDef>,
     config_profile: Option<StyleDef>,
+    config_volume_disabled: Option<StyleDef>,
     dropdown_icon: Option<StyleDef>,
     dropdown_border: Option<StyleDef>,
     dropdown_item: Option<StyleDef>,
     dropdown_selected: Option<StyleDef>,
+    dropdown_unavailable: Option<StyleDef>,
     dropdown_more: Option<StyleDef>,
     help_border: Option<StyleDef>,
     help_item: Option<StyleDef>,
     help_more: Option<StyleDef>,
+    error: Option<StyleDef>,
+    status_bar: Option<StyleDef>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -92,30 +106,51 @@ impl TryFrom<ThemeOverlay> for Theme {
         set!(default_device);
         set!(default_stream);
         set!(selector);
+        set!(node_selected_bg);
         set!(tab);
         set!(tab_selected);
         set!(tab_marker);
         set!(list_more);
         set!(node_title);
         set!(node_target);
+        set!(node_format);
         set!(volume);
+        set!(volume_boosted);
         set!(volume_empty);
         set!(volume_filled);
+        set!(volume_control);
+        set!(node_virtual);
+        set!(node_network);
         set!(meter_inactive);
         set!(meter_active);
         set!(meter_overload);
         set!(meter_center_inactive);
         set!(meter_center_active);
+        set!(meter_hold);
+        set!(meter_history);
+        set!(meter_label);
         set!(config_device);
         set!(config_profile);
+        set!(config_volume_disabled);
         set!(dropdown_icon);
         set!(dropdown_border);
         set!(dropdown_item);
         set!(dropdown_selected);
+        set!(dropdown_unavailable);
         set!(dropdown_more);
         set!(help_border);
         set!(help_item);
         set!(help_more);
+        set!(error);
+        set!(status_bar);
+
+        if let Some(node_title_roles) = overlay.node_title_roles {
+            theme.node_title_roles.extend(
+                node_title_roles
+                    .into_iter()
+                    .map(|(role, style)| (role, style.into())),
+            );
+        }
 
         Ok(theme)
     }
@@ -127,32 +162,48 @@ impl Default for Theme {
             default_device: Style::default(),
             default_stream: Style::default(),
             selector: Style::default().fg(Color::LightCyan),
+            node_selected_bg: Style::default(),
             tab: Style::default(),
             tab_selected: Style::default().fg(Color::LightCyan),
             tab_marker: Style::default().fg(Color::LightCyan),
             list_more: Style::default().fg(Color::DarkGray),
             node_title: Style::default(),
+            node_title_roles: HashMap::new(),
             node_target: Style::default(),
+            node_format: Style::default().fg(Color::DarkGray),
             volume: Style::default(),
+            volume_boosted: Style::default().fg(Color::Yellow),
             volume_empty: Style::default().fg(Color::DarkGray),
             volume_filled: Style::default().fg(Color::LightBlue),
+            volume_control: Style::default().fg(Color::DarkGray),
+            node_virtual: Style::default().fg(Color::Magenta),
+            node_network: Style::default().fg(Color::Cyan),
             meter_inactive: Style::default().fg(Color::DarkGray),
             meter_active: Style::default().fg(Color::LightGreen),
             meter_overload: Style::default().fg(Color::Red),
             meter_center_inactive: Style::default().fg(Color::DarkGray),
             meter_center_active: Style::default().fg(Color::LightGreen),
+            meter_hold: Style::default().fg(Color::Yellow),
+            meter_history: Style::default().fg(Color::LightGreen),
+            meter_label: Style::default().fg(Color::DarkGray),
             config_device: Style::default(),
             config_profile: Style::default(),
+            config_volume_disabled: Style::default().fg(Color::DarkGray),
             dropdown_icon: Style::default(),
             dropdown_border: Style::default(),
             dropdown_item: Style::default(),
             dropdown_selected: Style::default()
                 .fg(Color::LightCyan)
                 .add_modifier(Modifier::REVERSED),
+            dropdown_unavailable: Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::CROSSED_OUT),
             dropdown_more: Style::default().fg(Color::DarkGray),
             help_border: Style::default(),
             help_item: Style::default(),
             help_more: Style::default().fg(Color::DarkGray),
+            error: Style::default().fg(Color::Red),
+            status_bar: Style::default().fg(Color::DarkGray),
         }
     }
 }
@@ -171,31 +222,47 @@ impl Theme {
             default_device: Style::default(),
             default_stream: Style::default(),
             selector: Style::default().add_modifier(Modifier::BOLD),
+            node_selected_bg: Style::default(),
             tab: Style::default(),
             tab_selected: Style::default().add_modifier(Modifier::BOLD),
             tab_marker: Style::default().add_modifier(Modifier::BOLD),
             list_more: Style::default(),
             node_title: Style::default(),
+            node_title_roles: HashMap::new(),
             node_target: Style::default(),
+            node_format: Style::default().add_modifier(Modifier::DIM),
             volume: Style::default(),
+            volume_boosted: Style::default().add_modifier(Modifier::BOLD),
             volume_empty: Style::default().add_modifier(Modifier::DIM),
             volume_filled: Style::default().add_modifier(Modifier::BOLD),
+            volume_control: Style::default().add_modifier(Modifier::DIM),
+            node_virtual: Style::default().add_modifier(Modifier::DIM),
+            node_network: Style::default().add_modifier(Modifier::DIM),
             meter_inactive: Style::default().add_modifier(Modifier::DIM),
             meter_active: Style::default().add_modifier(Modifier::BOLD),
             meter_overload: Style::default().add_modifier(Modifier::BOLD),
             meter_center_inactive: Style::default().add_modifier(Modifier::DIM),
             meter_center_active: Style::default().add_modifier(Modifier::BOLD),
+            meter_hold: Style::default().add_modifier(Modifier::REVERSED),
+            meter_history: Style::default(),
+            meter_label: Style::default().add_modifier(Modifier::DIM),
             config_device: Style::default(),
             config_profile: Style::default(),
+            config_volume_disabled: Style::default()
+                .add_modifier(Modifier::DIM),
             dropdown_icon: Style::default(),
             dropdown_border: Style::default(),
             dropdown_item: Style::default(),
             dropdown_selected: Style::default()
                 .add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            dropdown_unavailable: Style::default()
+                .add_modifier(Modifier::DIM | Modifier::CROSSED_OUT),
             dropdown_more: Style::default(),
             help_border: Style::default(),
             help_item: Style::default(),
             help_more: Style::default(),
+            error: Style::default().add_modifier(Modifier::BOLD),
+            status_bar: Style::default().add_modifier(Modifier::DIM),
         }
     }
 
@@ -204,30 +271,142 @@ impl Theme {
             default_device: Style::default(),
             default_stream: Style::default(),
             selector: Style::default(),
+            node_selected_bg: Style::default(),
             tab: Style::default(),
             tab_selected: Style::default(),
             tab_marker: Style::default(),
             list_more: Style::default(),
             node_title: Style::default(),
+            node_title_roles: HashMap::new(),
             node_target: Style::default(),
+            node_format: Style::default(),
             volume: Style::default(),
+            volume_boosted: Style::default(),
             volume_empty: Style::default(),
             volume_filled: Style::default(),
+            volume_control: Style::default(),
+            node_virtual: Style::default(),
+            node_network: Style::default(),
             meter_inactive: Style::default(),
             meter_active: Style::default(),
             meter_overload: Style::default(),
             meter_center_inactive: Style::default(),
             meter_center_active: Style::default(),
+            meter_hold: Style::default(),
+            meter_history: Style::default(),
+            meter_label: Style::default(),
             config_device: Style::default(),
             config_profile: Style::default(),
+            config_volume_disabled: Style::default(),
             dropdown_icon: Style::default(),
             dropdown_border: Style::default(),
             dropdown_item: Style::default(),
             dropdown_selected: Style::default(),
+            dropdown_unavailable: Style::default(),
             dropdown_more: Style::default(),
             help_border: Style::default(),
             help_item: Style::default(),
             help_more: Style::default(),
+            error: Style::default(),
+            status_bar: Style::default(),
+        }
+    }
+
+    /// Removes foreground and background colors from every style, keeping
+    /// modifiers like bold.
+    pub fn strip_colors(&mut self) {
+        // Destructured so that new styles can't be missed
+        let Theme {
+            default_device,
+            default_stream,
+            selector,
+            node_selected_bg,
+            tab,
+            tab_selected,
+            tab_marker,
+            list_more,
+            node_title,
+            node_target,
+            node_format,
+            volume,
+            volume_boosted,
+            volume_empty,
+            volume_filled,
+            volume_control,
+            node_virtual,
+            node_network,
+            meter_inactive,
+            meter_active,
+            meter_overload,
+            meter_center_inactive,
+            meter_center_active,
+            meter_hold,
+            meter_history,
+            meter_label,
+            config_device,
+            config_profile,
+            config_volume_disabled,
+            dropdown_icon,
+            dropdown_border,
+            dropdown_item,
+            dropdown_selected,
+            dropdown_unavailable,
+            dropdown_more,
+            help_border,
+            help_item,
+            help_more,
+            error,
+            status_bar,
+            node_title_roles,
+        } = self;
+        for style in [
+            default_device,
+            default_stream,
+            selector,
+            node_selected_bg,
+            tab,
+            tab_selected,
+            tab_marker,
+            list_more,
+            node_title,
+            node_target,
+            node_format,
+            volume,
+            volume_boosted,
+            volume_empty,
+            volume_filled,
+            volume_control,
+            node_virtual,
+            node_network,
+            meter_inactive,
+            meter_active,
+            meter_overload,
+            meter_center_inactive,
+            meter_center_active,
+            meter_hold,
+            meter_history,
+            meter_label,
+            config_device,
+            config_profile,
+            config_volume_disabled,
+            dropdown_icon,
+            dropdown_border,
+            dropdown_item,
+            dropdown_selected,
+            dropdown_unavailable,
+            dropdown_more,
+            help_border,
+            help_item,
+            help_more,
+            error,
+            status_bar,
+        ]
+        .into_iter()
+        .chain(node_title_roles.values_mut())
+        {
+            *style = Style::default()
+                .add_modifier(style.add_modifier)
+                .remove_modifier(style.sub_modifier);
         }
     }
 
@@ -309,4 +488,34 @@ mod tests {
             assert_eq!(theme.selector, builtin.selector);
         }
     }
+
+    #[test]
+    fn node_title_roles() {
+        let config = r#"
+        node_title_roles = { Music = { fg = "Magenta" } }
+        "#;
+
+        let overlay = toml::from_str::<ThemeOverlay>(config).unwrap();
+        let theme = Theme::try_from(overlay).unwrap();
+        assert_eq!(
+            theme.node_title_roles.get("Music"),
+            Some(&Style::default().fg(Color::Magenta))
+        );
+        assert_eq!(theme.node_title_roles.get("Game"), None);
+    }
+
+    #[test]
+    fn strip_colors_keeps_modifiers() {
+        let mut theme = Theme::default();
+        theme
+            .node_title_roles
+            .insert(String::from("Music"), Style::default().fg(Color::Magenta));
+        theme.strip_colors();
+        assert_eq!(theme.selector, Style::default());
+        assert_eq!(
+            theme.dropdown_selected,
+            Style::default().add_modifier(Modifier::REVERSED)
+        );
+        assert_eq!(theme.node_title_roles["Music"], Style::default());
+    }
 }
@@ -0,0 +1,187 @@
+//! A Ratatui widget for a read-only popup listing the PipeWire properties of
+//! a node or device.
+
+use crossterm::event::{MouseButton, MouseEventKind};
+use ratatui::{
+    prelude::{Alignment, Buffer, Constraint, Rect, Widget},
+    text::{Line, Span},
+    widgets::{Block, Borders, Row, StatefulWidget, Table},
+};
+use smallvec::smallvec;
+
+use crate::app::{Action, MouseArea};
+use crate::config::Config;
+use crate::wirehose::PropertyStore;
+
+/// A snapshot of an object's properties taken when the popup was opened.
+#[derive(Debug)]
+pub struct Details {
+    /// Title of the object
+    pub title: String,
+    /// Property keys and raw values, sorted by key
+    pub rows: Vec<(String, String)>,
+    /// Index of the first visible row
+    pub position: u16,
+}
+
+impl Details {
+    pub fn new(title: String, props: &PropertyStore) -> Self {
+        let rows = props
+            .entries()
+            .into_iter()
+            .map(|(key, value)| (String::from(key), String::from(value)))
+            .collect();
+        Self {
+            title,
+            rows,
+            position: 0,
+        }
+    }
+
+    /// Returns the width needed to show the widest key and value.
+    pub fn width(&self) -> usize {
+        let key_width = self.key_width();
+        let value_width = self
+            .rows
+            .iter()
+            .map(|(_, value)| value.chars().count())
+            .max()
+            .unwrap_or_default();
+        // Borders and column spacing
+        key_width + DetailsWidget::COLUMN_PADDING as usize + value_width + 2
+    }
+
+    fn key_width(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+pub struct DetailsWidget<'a> {
+    pub config: &'a Config,
+}
+
+pub struct DetailsWidgetState<'a> {
+    pub mouse_areas: &'a mut Vec<MouseArea>,
+    pub details: &'a mut Details,
+}
+
+impl DetailsWidget<'_> {
+    const COLUMN_PADDING: u16 = 2;
+}
+
+impl<'a> StatefulWidget for DetailsWidget<'a> {
+    type State = DetailsWidgetState<'a>;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let details = &mut *state.details;
+
+        // App initialized mouse_areas so clicking anywhere closes this widget.
+        // Make it safe to click within the widget.
+        state.mouse_areas.push((
+            area,
+            smallvec![MouseEventKind::Down(MouseButton::Left)],
+            smallvec![Action::Nothing],
+        ));
+
+        let borders = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.config.theme.dropdown_border)
+            .border_type(self.config.char_set.dropdown_border)
+            .title(Line::from(details.title.as_str()));
+
+        let list_area = borders.inner(area);
+        borders.render(area, buf);
+
+        state.mouse_areas.push((
+            list_area,
+            smallvec![MouseEventKind::ScrollUp],
+            smallvec![Action::MoveUp],
+        ));
+        state.mouse_areas.push((
+            list_area,
+            smallvec![MouseEventKind::ScrollDown],
+            smallvec![Action::MoveDown],
+        ));
+
+        // Fix position if we are scrolled beyond the bottom of the list
+        let rows_total = details.rows.len();
+        let rows_visible = rows_total.saturating_sub(details.position.into());
+        if rows_visible < list_area.height.into() {
+            details.position = rows_total
+                .saturating_sub(list_area.height.into())
+                .try_into()
+                .unwrap_or(u16::MAX);
+        }
+
+        // Add clickable indicators to the borders if there are more rows
+        let more_above = details.position > 0;
+        let more_below =
+            usize::from(details.position.saturating_add(list_area.height))
+                < rows_total;
+        let bottom = area.y.saturating_add(area.height.saturating_sub(1));
+        for (more, y, action) in [
+            (more_above, area.y, Action::MoveUp),
+            (more_below, bottom, Action::MoveDown),
+        ] {
+            if !more {
+                continue;
+            }
+            let more_area = Rect::new(area.x, y, area.width, 1);
+            Line::from(Span::styled(
+                &self.config.char_set.dropdown_more,
+                self.config.theme.dropdown_more,
+            ))
+            .alignment(Alignment::Center)
+            .render(more_area, buf);
+
+            state.mouse_areas.push((
+                more_area,
+                smallvec![MouseEventKind::Down(MouseButton::Left)],
+                smallvec![action],
+            ));
+        }
+
+        let rows: Vec<Row> = details
+            .rows
+            .iter()
+            .skip(details.position.into())
+            .map(|(key, value)| Row::new([key.as_str(), value.as_str()]))
+            .collect();
+        let key_width = details.key_width().try_into().unwrap_or(u16::MAX);
+        let table = Table::new(
+            rows,
+            [Constraint::Length(key_width), Constraint::Fill(1)],
+        )
+        .style(self.config.theme.dropdown_item)
+        .column_spacing(Self::COLUMN_PADDING);
+        Widget::render(table, list_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_sorted_by_key() {
+        let mut props = PropertyStore::default();
+        props.set_node_name(String::from("alsa_output"));
+        props.set_media_class(String::from("Audio/Sink"));
+        props.set_object_serial(42);
+
+        let details = Details::new(String::from("Speakers"), &props);
+        assert_eq!(
+            details.rows,
+            vec![
+                (String::from("media.class"), String::from("Audio/Sink")),
+                (String::from("node.name"), String::from("alsa_output")),
+                (String::from("object.serial"), String::from("42")),
+            ]
+        );
+        assert_eq!(details.width(), 13 + 2 + 11 + 2);
+    }
+}
@@ -1,6 +1,6 @@
 //! Type representing whether a device is sink or source.
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DeviceKind {
     Sink,
     Source,
@@ -2,7 +2,7 @@
 
 use ratatui::{
     layout::Flex,
-    prelude::{Buffer, Constraint, Direction, Layout, Rect},
+    prelude::{Alignment, Buffer, Constraint, Direction, Layout, Rect},
     text::{Line, Span},
     widgets::{StatefulWidget, Widget},
 };
@@ -12,6 +12,8 @@ use smallvec::smallvec;
 
 use crate::app::{Action, MouseArea};
 use crate::config::Config;
+use crate::dropdown_widget;
+use crate::node_widget;
 use crate::object_list::ObjectList;
 use crate::view;
 
@@ -53,22 +55,36 @@ impl<'a> DeviceWidget<'a> {
         // Number of items to show at once
         let max_visible_items = 5;
 
-        let max_target_length = object_list
-            .targets
-            .iter()
-            .map(|(_, title)| title.len())
-            .max()
-            .unwrap_or(0);
+        let items = object_list.dropdown_items();
+        let max_target_length =
+            items.iter().map(|title| title.len()).max().unwrap_or(0);
 
-        // Position the dropdown so that the first item is over the displayed item
+        // Position the dropdown so that the first item is over the displayed
+        // item, which is on the third line
         let x = list_area.left().saturating_add(4);
-        let y = object_area.top().saturating_add(1);
+        let row = object_area.top().saturating_add(2);
         // Add 2 for vertical borders and 2 for highlight symbol
         let width = max_target_length.saturating_add(4) as u16;
-        let height = std::cmp::min(max_visible_items, object_list.targets.len())
+        let height = std::cmp::min(max_visible_items, items.len())
             .saturating_add(2) as u16; // Add 2 for horizontal borders
 
-        Rect::new(x, y, width, height)
+        dropdown_widget::placement(x, row, width, height, list_area)
+    }
+
+    /// Returns the label for the volume of the device's active routes and
+    /// whether it can be changed.
+    fn volume_label(&self) -> (String, bool) {
+        match self.device.mean_volume() {
+            None => (String::from("no route"), false),
+            Some(_) if self.device.is_muted() => (String::from("muted"), true),
+            Some(volume) => (
+                node_widget::format_volume(
+                    self.config.volume_unit,
+                    volume.cbrt(),
+                ),
+                true,
+            ),
+        }
     }
 }
 
@@ -132,11 +148,54 @@ impl StatefulWidget for DeviceWidget<'_> {
         let title_area = layout[0];
         let target_area = layout[1];
 
-        Line::from(vec![
+        let (label, enabled) = self.volume_label();
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),                     // title_area
+                Constraint::Length(label.len() as u16), // volume_area
+                Constraint::Length(1),                  // _padding
+            ])
+            .spacing(1)
+            .split(title_area);
+        let title_area = layout[0];
+        let volume_area = layout[1];
+
+        let mut title_line = Line::from(vec![
             Span::from("   "),
             Span::styled(&self.device.title, self.config.theme.config_device),
-        ])
-        .render(title_area, buf);
+        ]);
+        if self.config.show_ids {
+            title_line.push_span(Span::from(" "));
+            title_line.push_span(Span::styled(
+                node_widget::format_ids(
+                    self.device.object_id,
+                    self.device.object_serial,
+                ),
+                self.config.theme.node_format,
+            ));
+        }
+        title_line.render(title_area, buf);
+
+        // Devices without an active route have no volume to change.
+        let style = if enabled {
+            self.config.theme.volume
+        } else {
+            self.config.theme.config_volume_disabled
+        };
+        Line::from(Span::styled(label, style))
+            .alignment(Alignment::Right)
+            .render(volume_area, buf);
+        if enabled {
+            mouse_areas.push((
+                volume_area,
+                smallvec![MouseEventKind::Down(MouseButton::Left)],
+                smallvec![
+                    Action::SelectObject(self.device.object_id),
+                    Action::ToggleMute
+                ],
+            ));
+        }
 
         Line::from(vec![
             Span::from("    "),
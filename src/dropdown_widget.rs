@@ -1,10 +1,12 @@
-//! A Ratatui widget for a dropdown menu of options pertaining to a node or device
-//! widget.
+//! Ratatui widgets for a dropdown menu of options pertaining to a node or
+//! device widget, and for dropdown lists in general.
 
 use ratatui::{
     prelude::{Alignment, Buffer, Rect, Widget},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, StatefulWidget},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, StatefulWidget,
+    },
 };
 
 use crossterm::event::{MouseButton, MouseEventKind};
@@ -14,6 +16,35 @@ use crate::app::{Action, MouseArea};
 use crate::config::Config;
 use crate::object_list::ObjectList;
 
+/// Places a dropdown of the given size at x so that its first item is on
+/// row. If that would run past the bottom of list_area, the dropdown opens
+/// upward with its last item on row instead. Either way it's kept within
+/// list_area, except for the borders, which may cover the lines above and
+/// below it.
+pub fn placement(
+    x: u16,
+    row: u16,
+    width: u16,
+    height: u16,
+    list_area: &Rect,
+) -> Rect {
+    let bounds = Rect::new(
+        list_area.x,
+        list_area.y.saturating_sub(1),
+        list_area.width,
+        list_area.height.saturating_add(2),
+    );
+    // Subtract 1 for the top border
+    let below = row.saturating_sub(1);
+    let y = if below.saturating_add(height) > bounds.bottom() {
+        // Add 1 for the row and 1 for the bottom border
+        row.saturating_add(2).saturating_sub(height)
+    } else {
+        below
+    };
+    Rect::new(x, y, width, height).clamp(bounds)
+}
+
 pub struct DropdownWidget<'a> {
     object_list: &'a mut ObjectList,
     dropdown_area: &'a Rect,
@@ -38,18 +69,83 @@ impl StatefulWidget for DropdownWidget<'_> {
     type State = Vec<MouseArea>;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let mouse_areas = state;
+        let titles = self.object_list.dropdown_items();
+        let items = match &self.object_list.confirm_target {
+            // The choices for confirming a profile change are always
+            // available.
+            Some((target, _)) => titles
+                .into_iter()
+                .zip([Action::SetTarget(*target), Action::CloseDropdown])
+                .map(|(title, action)| (title, true, action))
+                .collect(),
+            None => titles
+                .into_iter()
+                .zip(self.object_list.filtered_targets())
+                .map(|(title, &(target, _, available))| {
+                    (title, available, Action::SetTarget(target))
+                })
+                .collect(),
+        };
 
-        let targets: Vec<_> = self
-            .object_list
-            .targets
-            .iter()
-            .map(|(_, title)| title.clone())
-            .collect();
+        // Show what has been typed to filter the targets
+        DropdownList::new(
+            items,
+            &self.object_list.dropdown_filter,
+            *self.dropdown_area,
+            self.config,
+        )
+        .render(
+            area,
+            buf,
+            &mut DropdownListState {
+                mouse_areas: state,
+                list_state: &mut self.object_list.dropdown_state,
+            },
+        );
+    }
+}
+
+/// A bordered list of items to pick from, drawn over the area it belongs to.
+pub struct DropdownList<'a> {
+    /// Each item's title, whether it is available, and the action for
+    /// clicking it
+    items: Vec<(String, bool, Action)>,
+    /// Shown in the top border if not empty
+    title: &'a str,
+    dropdown_area: Rect,
+    config: &'a Config,
+}
+
+pub struct DropdownListState<'a> {
+    pub mouse_areas: &'a mut Vec<MouseArea>,
+    pub list_state: &'a mut ListState,
+}
+
+impl<'a> DropdownList<'a> {
+    pub fn new(
+        items: Vec<(String, bool, Action)>,
+        title: &'a str,
+        dropdown_area: Rect,
+        config: &'a Config,
+    ) -> Self {
+        Self {
+            items,
+            title,
+            dropdown_area,
+            config,
+        }
+    }
+}
+
+impl<'a> StatefulWidget for DropdownList<'a> {
+    type State = DropdownListState<'a>;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let mouse_areas = &mut *state.mouse_areas;
 
         let dropdown_area = self.dropdown_area.clamp(area);
 
-        // Click anywhere else in the object list to close the dropdown.
+        // Click anywhere else in the area to close the dropdown.
         mouse_areas.push((
             area,
             smallvec![MouseEventKind::Down(MouseButton::Left)],
@@ -67,25 +163,35 @@ impl StatefulWidget for DropdownWidget<'_> {
 
         let highlight_symbol =
             format!("{} ", self.config.char_set.dropdown_selector);
-        let list = List::new(targets)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(self.config.theme.dropdown_border)
-                    .border_type(self.config.char_set.dropdown_border),
-            )
+        let item_count = self.items.len();
+        let list_items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|(title, available, _)| {
+                let item = ListItem::new(title.as_str());
+                if *available {
+                    item
+                } else {
+                    item.style(self.config.theme.dropdown_unavailable)
+                }
+            })
+            .collect();
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.config.theme.dropdown_border)
+            .border_type(self.config.char_set.dropdown_border);
+        if !self.title.is_empty() {
+            block = block.title(Line::from(self.title));
+        }
+        let list = List::new(list_items)
+            .block(block)
             .style(self.config.theme.dropdown_item)
             .highlight_symbol(&highlight_symbol)
             .highlight_style(self.config.theme.dropdown_selected);
 
-        StatefulWidget::render(
-            &list,
-            dropdown_area,
-            buf,
-            &mut self.object_list.dropdown_state,
-        );
+        StatefulWidget::render(&list, dropdown_area, buf, state.list_state);
 
-        let first_index = self.object_list.dropdown_state.offset();
+        let first_index = state.list_state.offset();
 
         // Add a clickable indicator to the top border if there or more items
         // if scrolled up
@@ -117,7 +223,7 @@ impl StatefulWidget for DropdownWidget<'_> {
         let last_index = first_index.saturating_add(dropdown_area_inner_height);
         // Add a clickable indicator to the bottom border if there or more
         // items if scrolled down
-        if last_index < self.object_list.targets.len() {
+        if last_index < item_count {
             let y = dropdown_area
                 .y
                 .saturating_add(dropdown_area.height.saturating_sub(1));
@@ -146,20 +252,36 @@ impl StatefulWidget for DropdownWidget<'_> {
                 1,
             );
 
-            let target = self
-                .object_list
-                .targets
-                .iter()
-                .skip(first_index)
-                .nth(i as usize)
-                .map(|(target, _)| target);
-            if let Some(target) = target {
+            let index = first_index.saturating_add(i as usize);
+            if let Some((_, _, action)) = self.items.get(index) {
                 mouse_areas.push((
                     target_area,
                     smallvec![MouseEventKind::Down(MouseButton::Left)],
-                    smallvec![Action::SetTarget(*target)],
+                    smallvec![action.clone()],
                 ));
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placement_flips_at_bottom() {
+        let list_area = Rect::new(0, 1, 40, 20);
+
+        // Room below, so the first item is on the row.
+        let area = placement(10, 5, 20, 7, &list_area);
+        assert_eq!(area, Rect::new(10, 4, 20, 7));
+
+        // No room below, so the last item is on the row.
+        let area = placement(10, 18, 20, 7, &list_area);
+        assert_eq!(area, Rect::new(10, 13, 20, 7));
+
+        // Too tall either way, so it's kept within the list.
+        let area = placement(10, 3, 20, 30, &list_area);
+        assert_eq!(area, Rect::new(10, 0, 20, 22));
+    }
+}
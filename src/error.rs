@@ -0,0 +1,96 @@
+//! Categories of fatal errors, for reporting them to scripts.
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// What kind of problem made wiremix exit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorKind {
+    /// PipeWire couldn't be reached or reported an error
+    Connection,
+    /// The configuration file or command-line options are invalid
+    Config,
+    /// Anything else
+    Runtime,
+}
+
+impl ErrorKind {
+    /// Tags error with this kind. The result displays the same as error, so
+    /// tagging doesn't change how errors are normally shown.
+    pub fn tag(self, error: impl Into<anyhow::Error>) -> anyhow::Error {
+        anyhow::Error::new(Tagged {
+            kind: self,
+            error: error.into(),
+        })
+    }
+
+    /// Returns the kind error was tagged with, or Runtime if it wasn't.
+    pub fn of(error: &anyhow::Error) -> Self {
+        error
+            .downcast_ref::<Tagged>()
+            .map_or(ErrorKind::Runtime, |tagged| tagged.kind)
+    }
+}
+
+#[derive(Debug)]
+struct Tagged {
+    kind: ErrorKind,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for Tagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only the outermost message. The causes come from source().
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for Tagged {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.chain().nth(1)
+    }
+}
+
+/// A fatal error as printed by --json-errors
+#[derive(Debug, Serialize)]
+pub struct ErrorReport {
+    pub kind: ErrorKind,
+    /// The error and its causes
+    pub message: String,
+}
+
+impl From<&anyhow::Error> for ErrorReport {
+    fn from(error: &anyhow::Error) -> Self {
+        Self {
+            kind: ErrorKind::of(error),
+            message: format!("{error:#}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn tagged_errors_keep_their_messages() {
+        let error: anyhow::Result<()> =
+            Err(anyhow!("expected a table")).context("Invalid config");
+        let error = ErrorKind::Config.tag(error.unwrap_err());
+        assert_eq!(error.to_string(), "Invalid config");
+
+        let report = ErrorReport::from(&error);
+        assert_eq!(report.kind, ErrorKind::Config);
+        assert_eq!(report.message, "Invalid config: expected a table");
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            r#"{"kind":"config","message":"Invalid config: expected a table"}"#
+        );
+
+        let untagged = anyhow!("No node named 'Speakers'");
+        assert_eq!(ErrorKind::of(&untagged), ErrorKind::Runtime);
+    }
+}
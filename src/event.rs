@@ -1,14 +1,24 @@
 //! Input events for the application.
 //!
-//! These come from [`wirehose`](`crate::wirehose`) (PipeWire events) and from
-//! [`input`](`crate::input`) (terminal input events).
+//! These come from [`wirehose`](`crate::wirehose`) (PipeWire events), from
+//! [`input`](`crate::input`) (terminal input events), from
+//! [`signal`](`crate::signal`) (configuration reloads and hangups), and from
+//! [`osc`](`crate::osc`) (remote control).
 
+use crate::config::Config;
+use crate::osc;
 use crate::wirehose::Event as PipewireEvent;
 
 #[derive(Debug)]
 pub enum Event {
     Input(crossterm::event::Event),
     Pipewire(PipewireEvent),
+    /// A configuration re-read from disk, or the error from reading it
+    ReloadConfig(anyhow::Result<Box<Config>>),
+    /// A remote control command received over OSC
+    Osc(osc::Command),
+    /// The terminal went away, so there's nobody left to show anything to
+    Hangup,
 }
 
 impl From<crossterm::event::Event> for Event {
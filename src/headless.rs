@@ -0,0 +1,572 @@
+//! One-shot commands which run without the UI.
+//!
+//! [`run()`] waits for the initial PipeWire state, executes a
+//! [`Command`](`crate::opt::Command`) against it, and waits for PipeWire to
+//! apply the change. The meter command instead keeps printing the node's
+//! peaks until interrupted.
+//!
+//! [`dump_state()`] prints the initial state as JSON.
+
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+
+use crate::app;
+use crate::atomic_f32::AtomicF32;
+use crate::config::Config;
+use crate::error::ErrorKind;
+use crate::event::Event;
+use crate::meter;
+use crate::opt::Command;
+use crate::view::{self, View};
+use crate::wirehose::{
+    state::{CaptureEligibility, State},
+    CommandSender, Event as PipewireEvent, ObjectId, StateEvent,
+};
+
+/// How long to wait for PipeWire to report that a change was applied
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Number of glyphs in the meter printed by the meter command
+const METER_WIDTH: usize = 40;
+
+/// Executes command and waits for the change to be reflected in the PipeWire
+/// state.
+///
+/// Fails if the node isn't found or if the change isn't seen before timing
+/// out.
+pub fn run(
+    wirehose: &dyn CommandSender,
+    rx: mpsc::Receiver<Event>,
+    config: &Config,
+    command: &Command,
+) -> Result<()> {
+    let mut state = wait_until_ready(&rx)?;
+
+    let view = build_view(wirehose, &state, config);
+    let node_name = match command {
+        Command::SetVolume { node, .. }
+        | Command::Mute { node }
+        | Command::Unmute { node } => node,
+        Command::Meter { node } => {
+            let stdout = io::stdout();
+            let redraw = stdout.is_terminal();
+            return meter(
+                wirehose,
+                &rx,
+                config,
+                state,
+                node,
+                &mut stdout.lock(),
+                redraw,
+            );
+        }
+    };
+    let node_id = find_node(&view, node_name)
+        .ok_or_else(|| anyhow!("No node named '{node_name}'"))?;
+
+    // Check whether the node is in the expected state after the command
+    let is_applied: Box<dyn Fn(&view::Node) -> bool> = match command {
+        Command::SetVolume { percent, .. } => {
+            // The view raises volumes below the floor to it
+            let percent = percent.max(config.min_volume_percent).round();
+            if view.nodes[&node_id]
+                .volumes
+                .iter()
+                .all(|volume| (volume.cbrt() * 100.0).round() == percent)
+            {
+                // Nothing to change, so PipeWire won't report anything.
+                return Ok(());
+            }
+
+            let max = config
+                .enforce_max_volume
+                .then_some(config.max_volume_percent);
+            let adjustment = view::VolumeAdjustment::Absolute(percent / 100.0);
+            if !view.volume(node_id, adjustment, max) {
+                bail!("Can't set volume of '{node_name}' to {percent}%");
+            }
+
+            Box::new(move |node| {
+                node.volumes
+                    .iter()
+                    .all(|volume| (volume.cbrt() * 100.0).round() == percent)
+            })
+        }
+        Command::Mute { .. } | Command::Unmute { .. } => {
+            let mute = matches!(command, Command::Mute { .. });
+            if view.nodes[&node_id].mute == mute {
+                // Nothing to change, so PipeWire won't report anything.
+                return Ok(());
+            }
+
+            // View::mute toggles, which sets mute since it differs.
+            view.mute(node_id);
+            Box::new(move |node| node.mute == mute)
+        }
+        Command::Meter { .. } => unreachable!("meters return early"),
+    };
+
+    // Wait for the change to come back from PipeWire
+    let deadline = Instant::now() + TIMEOUT;
+    loop {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        let event = match rx.recv_timeout(timeout) {
+            Ok(event) => event,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                bail!("Timed out waiting for PipeWire to update '{node_name}'")
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let Event::Pipewire(PipewireEvent::State(event)) = event else {
+            continue;
+        };
+        state.update(event);
+
+        let view = build_view(wirehose, &state, config);
+        if view
+            .nodes
+            .get(&node_id)
+            .is_some_and(|node| is_applied(node))
+        {
+            return Ok(());
+        }
+    }
+}
+
+/// Captures the named node's peaks and writes a meter line to out each time
+/// they change, until PipeWire goes away or the process is interrupted. With
+/// redraw, each line replaces the previous one, as on a terminal.
+fn meter(
+    wirehose: &dyn CommandSender,
+    rx: &mpsc::Receiver<Event>,
+    config: &Config,
+    mut state: State,
+    node_name: &str,
+    out: &mut dyn Write,
+    redraw: bool,
+) -> Result<()> {
+    let view = build_view(wirehose, &state, config);
+    let node_id = find_node(&view, node_name)
+        .ok_or_else(|| anyhow!("No node named '{node_name}'"))?;
+    let title = view.nodes[&node_id].title.clone();
+    start_capture(wirehose, &state, node_id, config)?;
+
+    // Don't print faster than the UI would draw.
+    let frame = config.fps.map(|fps| Duration::from_secs_f32(1.0 / fps));
+    let mut last_print: Option<Instant> = None;
+
+    let mut print_peaks = || -> Result<()> {
+        for event in rx {
+            let Event::Pipewire(event) = event else {
+                continue;
+            };
+            match event {
+                PipewireEvent::State(StateEvent::PeaksDirty { pending }) => {
+                    if let (Some(frame), Some(last_print)) = (frame, last_print)
+                    {
+                        thread::sleep(
+                            frame.saturating_sub(last_print.elapsed()),
+                        );
+                    }
+                    last_print = Some(Instant::now());
+
+                    let peak = state
+                        .nodes
+                        .get(&node_id)
+                        .and_then(|node| node.peaks.as_ref())
+                        .map(|peaks| {
+                            peaks
+                                .iter()
+                                .map(AtomicF32::load)
+                                .fold(0.0, f32::max)
+                        });
+                    pending.store(false, Ordering::Relaxed);
+
+                    let line = meter_line(&title, peak, config);
+                    let result = if redraw {
+                        write!(out, "\r{line}").and_then(|()| out.flush())
+                    } else {
+                        writeln!(out, "{line}")
+                    };
+                    match result {
+                        // Whatever was reading the meter has gone away.
+                        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {
+                            return Ok(());
+                        }
+                        result => result?,
+                    }
+                }
+                PipewireEvent::State(event) => {
+                    // Ineligible nodes keep capturing, like pinned UI nodes.
+                    for capture_eligibility in state.update(event) {
+                        if let CaptureEligibility::NeedsRestart(object_id) =
+                            capture_eligibility
+                        {
+                            if object_id == node_id {
+                                start_capture(
+                                    wirehose, &state, node_id, config,
+                                )?;
+                            }
+                        }
+                    }
+                    if !state.nodes.contains_key(&node_id) {
+                        bail!("'{node_name}' was removed");
+                    }
+                }
+                PipewireEvent::Disconnected => {
+                    return Err(ErrorKind::Connection
+                        .tag(anyhow!("Disconnected from PipeWire")));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    };
+    let result = print_peaks();
+
+    // Finish the line being redrawn so that anything printed after it, like
+    // an error, starts on its own line.
+    if redraw && last_print.is_some() {
+        let _ = writeln!(out);
+    }
+    result
+}
+
+/// Starts capturing a node's peaks the same way the UI does.
+fn start_capture(
+    wirehose: &dyn CommandSender,
+    state: &State,
+    node_id: ObjectId,
+    config: &Config,
+) -> Result<()> {
+    app::start_node_capture(
+        wirehose,
+        state,
+        node_id,
+        &config.filters,
+        app::peak_processor(&config.meter),
+    )
+}
+
+/// Formats a node's title, a meter for its loudest channel, and that
+/// channel's level in dBFS.
+fn meter_line(title: &str, peak: Option<f32>, config: &Config) -> String {
+    let peak = peak.unwrap_or_default();
+    let db = view::volume_to_decibels(peak);
+    let db = if db.is_finite() {
+        format!("{db:.1}")
+    } else {
+        String::from("-inf")
+    };
+    let meter = meter::text(peak, METER_WIDTH, config);
+    format!("{title} {meter} {db:>5} dBFS")
+}
+
+/// A snapshot of the mixer state for serialization
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    nodes: Vec<&'a view::Node>,
+    devices: Vec<&'a view::Device>,
+    default_sink: Option<view::Target>,
+    default_source: Option<view::Target>,
+}
+
+impl<'a, 'b> From<&'a View<'b>> for Snapshot<'a> {
+    fn from(view: &'a View<'b>) -> Self {
+        Self {
+            nodes: view.full_nodes(view::NodeKind::All, None),
+            devices: view.full_devices(None),
+            default_sink: view.default_sink,
+            default_source: view.default_source,
+        }
+    }
+}
+
+/// Prints the nodes and devices to stdout as JSON once the initial PipeWire
+/// state has been received.
+pub fn dump_state(
+    wirehose: &dyn CommandSender,
+    rx: mpsc::Receiver<Event>,
+    config: &Config,
+) -> Result<()> {
+    let state = wait_until_ready(&rx)?;
+    let view = build_view(wirehose, &state, config);
+
+    println!("{}", serde_json::to_string_pretty(&Snapshot::from(&view))?);
+
+    Ok(())
+}
+
+/// Collects the PipeWire state until all initial data has been received.
+fn wait_until_ready(rx: &mpsc::Receiver<Event>) -> Result<State> {
+    let mut state = State::default();
+
+    loop {
+        match rx.recv()? {
+            Event::Pipewire(PipewireEvent::Ready) => return Ok(state),
+            // Before we're ready, errors are likely connection failures.
+            Event::Pipewire(PipewireEvent::Error(message)) => {
+                return Err(ErrorKind::Connection.tag(anyhow!(message)));
+            }
+            Event::Pipewire(PipewireEvent::Disconnected) => {
+                return Err(ErrorKind::Connection
+                    .tag(anyhow!("Disconnected from PipeWire")));
+            }
+            Event::Pipewire(PipewireEvent::State(event)) => {
+                state.update(event);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn build_view<'a>(
+    wirehose: &'a dyn CommandSender,
+    state: &State,
+    config: &Config,
+) -> View<'a> {
+    let mut view = View::from(
+        wirehose,
+        state,
+        &config.names,
+        &config.filters,
+        &config.sort,
+        &Default::default(),
+    );
+    view.min_volume_percent = config.min_volume_percent;
+    view.default_keys = config.default_keys.clone();
+    view
+}
+
+/// Finds the first node whose displayed title or node.name is name.
+pub(crate) fn find_node(view: &View, name: &str) -> Option<ObjectId> {
+    view.nodes_all.iter().copied().find(|node_id| {
+        view.nodes
+            .get(node_id)
+            .is_some_and(|node| node.title == name || node.name == name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+    use crate::mock::{self, MockCommand};
+    use crate::wirehose::PropertyStore;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    #[test]
+    fn find_node_and_snapshot() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let object_id = ObjectId::from_raw_id(0);
+        let mut props = PropertyStore::default();
+        props.set_media_class(String::from("Audio/Sink"));
+        props.set_node_name(String::from("alsa_output.speakers"));
+        props.set_node_description(String::from("Speakers"));
+        props.set_object_serial(0);
+        state.update(StateEvent::NodeProperties { object_id, props });
+        state.update(StateEvent::NodeVolumes {
+            object_id,
+            volumes: vec![1.0, 1.0],
+        });
+        state.update(StateEvent::NodeMute {
+            object_id,
+            mute: false,
+        });
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+        assert_eq!(find_node(&view, "Speakers"), Some(object_id));
+        assert_eq!(find_node(&view, "alsa_output.speakers"), Some(object_id));
+        assert_eq!(find_node(&view, "Headphones"), None);
+
+        let snapshot = serde_json::to_value(Snapshot::from(&view)).unwrap();
+        let node = &snapshot["nodes"][0];
+        assert_eq!(node["title"], "Speakers");
+        assert_eq!(node["mute"], false);
+        assert!(node.get("peaks").is_none());
+    }
+
+    /// Runs command against a speaker node that is initially muted or not,
+    /// with PipeWire then reporting mute as reported_mute.
+    fn run_mute(
+        command: Command,
+        mute: bool,
+        reported_mute: bool,
+    ) -> VecDeque<MockCommand> {
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+        let config = Config::from_toml_str("");
+
+        let object_id = ObjectId::from_raw_id(0);
+        let mut props = PropertyStore::default();
+        props.set_media_class(String::from("Audio/Sink"));
+        props.set_node_description(String::from("Speakers"));
+        props.set_object_serial(0);
+
+        let (tx, rx) = mpsc::channel();
+        for event in [
+            Event::Pipewire(PipewireEvent::State(StateEvent::NodeProperties {
+                object_id,
+                props,
+            })),
+            Event::Pipewire(PipewireEvent::State(StateEvent::NodeVolumes {
+                object_id,
+                volumes: vec![1.0, 1.0],
+            })),
+            Event::Pipewire(PipewireEvent::State(StateEvent::NodeMute {
+                object_id,
+                mute,
+            })),
+            Event::Pipewire(PipewireEvent::Ready),
+            Event::Pipewire(PipewireEvent::State(StateEvent::NodeMute {
+                object_id,
+                mute: reported_mute,
+            })),
+        ] {
+            tx.send(event).unwrap();
+        }
+
+        run(&wirehose, rx, &config, &command).unwrap();
+        commands.take()
+    }
+
+    #[test]
+    fn mute_sets_mute() {
+        let object_id = ObjectId::from_raw_id(0);
+        let node = String::from("Speakers");
+
+        let commands =
+            run_mute(Command::Mute { node: node.clone() }, false, true);
+        assert_eq!(
+            Vec::from(commands),
+            vec![MockCommand::NodeMute(object_id, true)]
+        );
+
+        // Already muted, so nothing is sent.
+        let commands = run_mute(Command::Mute { node }, true, true);
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    fn unmute_clears_mute() {
+        let object_id = ObjectId::from_raw_id(0);
+        let node = String::from("Speakers");
+
+        let commands =
+            run_mute(Command::Unmute { node: node.clone() }, true, false);
+        assert_eq!(
+            Vec::from(commands),
+            vec![MockCommand::NodeMute(object_id, false)]
+        );
+
+        // Already unmuted, so nothing is sent.
+        let commands = run_mute(Command::Unmute { node }, false, false);
+        assert!(commands.is_empty());
+    }
+
+    /// Runs the meter command on a speaker node which reports one set of
+    /// peaks, returning the output and the commands sent.
+    fn run_meter(
+        config: &Config,
+        redraw: bool,
+    ) -> (Result<String>, Arc<AtomicBool>, VecDeque<MockCommand>) {
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+
+        let object_id = ObjectId::from_raw_id(0);
+        let mut props = PropertyStore::default();
+        props.set_media_class(String::from("Audio/Sink"));
+        props.set_node_name(String::from("alsa_output.speakers"));
+        props.set_node_description(String::from("Speakers"));
+        props.set_object_serial(0);
+        let mut state = State::default();
+        state.update(StateEvent::NodeProperties { object_id, props });
+
+        let (tx, rx) = mpsc::channel();
+        let peaks: Arc<[AtomicF32]> =
+            Arc::new([AtomicF32::from(0.25), AtomicF32::from(0.5)]);
+        let pending = Arc::new(AtomicBool::new(true));
+        for event in [
+            StateEvent::NodeStreamStarted {
+                object_id,
+                rate: 48000,
+                peaks,
+                clips: Arc::new([]),
+            },
+            StateEvent::PeaksDirty {
+                pending: Arc::clone(&pending),
+            },
+        ] {
+            tx.send(Event::Pipewire(PipewireEvent::State(event)))
+                .unwrap();
+        }
+        drop(tx);
+
+        let mut out = Vec::new();
+        let result =
+            meter(&wirehose, &rx, config, state, "Speakers", &mut out, redraw)
+                .map(|()| String::from_utf8(out).unwrap());
+        (result, pending, commands.take())
+    }
+
+    #[test]
+    fn meter_prints_peaks() {
+        let config = Config::from_toml_str("");
+        let line = format!(
+            "Speakers {}  -6.0 dBFS",
+            meter::text(0.5, METER_WIDTH, &config)
+        );
+
+        let (out, pending, commands) = run_meter(&config, false);
+        assert_eq!(out.unwrap(), format!("{line}\n"));
+        assert!(!pending.load(Ordering::Relaxed));
+        assert_eq!(
+            Vec::from(commands),
+            vec![MockCommand::NodeCaptureStart(ObjectId::from_raw_id(0))]
+        );
+
+        // The redrawn line is ended when the events stop.
+        let (out, _, _) = run_meter(&config, true);
+        assert_eq!(out.unwrap(), format!("\r{line}\n"));
+    }
+
+    #[test]
+    fn start_capture_skips_filtered_nodes() {
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+        let config = Config::from_toml_str(
+            r#"
+            [[filters]]
+            matches = [{ "node.name" = "alsa_output.speakers" }]
+            "#,
+        );
+
+        let object_id = ObjectId::from_raw_id(0);
+        let mut props = PropertyStore::default();
+        props.set_node_name(String::from("alsa_output.speakers"));
+        props.set_object_serial(0);
+        let mut state = State::default();
+        state.update(StateEvent::NodeProperties { object_id, props });
+
+        assert!(start_capture(&wirehose, &state, object_id, &config).is_err());
+        assert!(commands.borrow().is_empty());
+    }
+}
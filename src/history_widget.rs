@@ -0,0 +1,50 @@
+//! A Ratatui widget for a sparkline of a node's recent peaks.
+
+use ratatui::{
+    prelude::{Buffer, Rect},
+    symbols,
+    widgets::{RenderDirection, Sparkline, Widget},
+};
+
+use crate::config::Config;
+use crate::meter;
+use crate::view;
+
+/// Resolution of the sparkline values
+const LEVELS: f32 = 1000.0;
+
+pub struct HistoryWidget<'a> {
+    config: &'a Config,
+    node: &'a view::Node,
+}
+
+impl<'a> HistoryWidget<'a> {
+    pub fn new(config: &'a Config, node: &'a view::Node) -> Self {
+        Self { config, node }
+    }
+}
+
+impl Widget for HistoryWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Newest on the right, scaled like the peak meters
+        let data: Vec<u64> = self
+            .node
+            .peak_history
+            .peaks()
+            .iter()
+            .rev()
+            .map(|&peak| {
+                let level = meter::level(peak, &self.config.meter);
+                (level * LEVELS).round() as u64
+            })
+            .collect();
+
+        Sparkline::default()
+            .data(&data)
+            .max(LEVELS as u64)
+            .bar_set(symbols::bar::NINE_LEVELS)
+            .direction(RenderDirection::RightToLeft)
+            .style(self.config.theme.meter_history)
+            .render(area, buf);
+    }
+}
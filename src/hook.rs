@@ -0,0 +1,108 @@
+//! User commands run in response to changes.
+//!
+//! Commands are run with `sh -c` after substituting placeholders. Substituted
+//! values are shell-quoted, so placeholders shouldn't be quoted again.
+
+use std::process::{Command, Stdio};
+use std::thread;
+
+use anyhow::{Context, Result};
+
+use crate::device_kind::DeviceKind;
+
+/// Runs command for a change of the default sink or source without waiting
+/// for it to finish.
+///
+/// `{name}` is replaced with the new default's node.name, `{title}` with its
+/// displayed name, and `{kind}` with "sink" or "source".
+pub fn default_changed(
+    command: &str,
+    name: &str,
+    title: &str,
+    kind: DeviceKind,
+) -> Result<()> {
+    let kind = match kind {
+        DeviceKind::Sink => "sink",
+        DeviceKind::Source => "source",
+    };
+    let command =
+        expand(command, &[("name", name), ("title", title), ("kind", kind)]);
+    spawn(&command)
+}
+
+/// Replaces each `{key}` in command with the quoted value. Anything else in
+/// braces is left alone.
+fn expand(command: &str, values: &[(&str, &str)]) -> String {
+    let mut expanded = String::new();
+    let mut rest = command;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let placeholder = values.iter().find(|(key, _)| {
+            rest.strip_prefix(*key)
+                .is_some_and(|after| after.starts_with('}'))
+        });
+        match placeholder {
+            Some((key, value)) => {
+                expanded.push_str(&quote(value));
+                rest = &rest[key.len() + 1..];
+            }
+            None => expanded.push('{'),
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Quotes s for the shell.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Spawns command detached from the terminal. A thread reaps it when it
+/// exits.
+fn spawn(command: &str) -> Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run '{command}'"))?;
+
+    thread::spawn(move || {
+        let _ = child.wait();
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_quotes_values() {
+        let command = expand(
+            "notify-send {kind} {title}",
+            &[("title", "Bob's Speakers"), ("kind", "sink")],
+        );
+        assert_eq!(command, r"notify-send 'sink' 'Bob'\''s Speakers'");
+    }
+
+    #[test]
+    fn expand_leaves_unknown_placeholders() {
+        let command = expand("echo {other} {name", &[("name", "x")]);
+        assert_eq!(command, "echo {other} {name");
+    }
+
+    #[test]
+    fn expand_ignores_placeholders_in_values() {
+        let command = expand(
+            "echo {name} {kind}",
+            &[("name", "{kind}"), ("kind", "sink")],
+        );
+        assert_eq!(command, "echo '{kind}' 'sink'");
+    }
+}
@@ -14,7 +14,8 @@ use crate::event::Event;
 
 /// Spawns a thread to listen for terminal input events.
 ///
-/// [`Event`](`crate::event::Event`)s are sent to tx.
+/// [`Event`](`crate::event::Event`)s are sent to tx. If the terminal can't be
+/// read anymore, [`Event::Hangup`] is sent and the thread exits.
 ///
 /// Returns a [`InputHandle`] to automatically clean up the thread.
 pub fn spawn(tx: Arc<mpsc::Sender<Event>>) -> InputHandle {
@@ -70,8 +71,11 @@ async fn input_loop(
                     Some(Ok(event)) => {
                         let _ = tx.send(Event::from(event));
                     }
-                    None => break,
-                    _ => {},
+                    // EOF or a read error, likely because the terminal hung up
+                    None | Some(Err(_)) => {
+                        let _ = tx.send(Event::Hangup);
+                        break;
+                    }
                 }
             }
         }
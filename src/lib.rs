@@ -1,24 +1,44 @@
 pub mod app;
 pub mod atomic_f32;
+pub mod card_widget;
+pub mod clipboard;
 pub mod config;
+pub mod details_widget;
 pub mod device_kind;
 pub mod device_widget;
 pub mod dropdown_widget;
+pub mod error;
 pub mod event;
+pub mod headless;
 pub mod help;
+pub mod history_widget;
+pub mod hook;
 pub mod input;
 pub mod meter;
+pub mod mixer;
+pub mod mute_memory;
 pub mod node_widget;
 pub mod object_list;
 pub mod opt;
+pub mod osc;
+pub mod peak_history;
+pub mod peak_hold;
+pub mod profile_defaults;
+pub mod remote_picker;
+pub mod saved_volumes;
+pub mod signal;
+pub mod target_history;
+pub mod truncate;
 pub mod view;
 pub mod wirehose;
 
 #[cfg(feature = "trace")]
 pub mod trace;
 
-#[cfg(test)]
-mod mock {
+/// A [`CommandSender`](`wirehose::CommandSender`) that records commands
+/// instead of sending them, for tests.
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock {
     use crate::wirehose::{CommandSender, ObjectId, PeakProcessor};
     use std::cell::RefCell;
     use std::collections::VecDeque;
@@ -28,6 +48,15 @@ mod mock {
     pub enum MockCommand {
         NodeCaptureStart(ObjectId),
         NodeCaptureStop(ObjectId),
+        NodeMute(ObjectId, bool),
+        NodeVolumes(ObjectId, Vec<f32>),
+        /// Device, card device, and mute
+        DeviceMute(ObjectId, i32, bool),
+        /// Device, card device, and volumes
+        DeviceVolumes(ObjectId, i32, Vec<f32>),
+        MetadataSetProperty(String, Option<String>),
+        SwitchRemote(Option<String>),
+        RereadParams,
     }
 
     #[derive(Default)]
@@ -67,15 +96,34 @@ mod mock {
                     .push_back(MockCommand::NodeCaptureStop(object_id));
             }
         }
-        fn node_mute(&self, _object_id: ObjectId, _mute: bool) {}
-        fn node_volumes(&self, _object_id: ObjectId, _volumes: Vec<f32>) {}
+        fn node_mute(&self, object_id: ObjectId, mute: bool) {
+            if let Some(commands) = self.commands {
+                commands
+                    .borrow_mut()
+                    .push_back(MockCommand::NodeMute(object_id, mute));
+            }
+        }
+        fn node_volumes(&self, object_id: ObjectId, volumes: Vec<f32>) {
+            if let Some(commands) = self.commands {
+                commands
+                    .borrow_mut()
+                    .push_back(MockCommand::NodeVolumes(object_id, volumes));
+            }
+        }
         fn device_mute(
             &self,
-            _object_id: ObjectId,
+            object_id: ObjectId,
             _route_index: i32,
-            _route_device: i32,
-            _mute: bool,
+            route_device: i32,
+            mute: bool,
         ) {
+            if let Some(commands) = self.commands {
+                commands.borrow_mut().push_back(MockCommand::DeviceMute(
+                    object_id,
+                    route_device,
+                    mute,
+                ));
+            }
         }
         fn device_set_profile(
             &self,
@@ -92,20 +140,44 @@ mod mock {
         }
         fn device_volumes(
             &self,
-            _object_id: ObjectId,
+            object_id: ObjectId,
             _route_index: i32,
-            _route_device: i32,
-            _volumes: Vec<f32>,
+            route_device: i32,
+            volumes: Vec<f32>,
         ) {
+            if let Some(commands) = self.commands {
+                commands.borrow_mut().push_back(MockCommand::DeviceVolumes(
+                    object_id,
+                    route_device,
+                    volumes,
+                ));
+            }
         }
         fn metadata_set_property(
             &self,
             _object_id: ObjectId,
             _subject: u32,
-            _key: String,
+            key: String,
             _type_: Option<String>,
-            _value: Option<String>,
+            value: Option<String>,
         ) {
+            if let Some(commands) = self.commands {
+                commands
+                    .borrow_mut()
+                    .push_back(MockCommand::MetadataSetProperty(key, value));
+            }
+        }
+        fn switch_remote(&self, remote: Option<String>) {
+            if let Some(commands) = self.commands {
+                commands
+                    .borrow_mut()
+                    .push_back(MockCommand::SwitchRemote(remote));
+            }
+        }
+        fn reread_params(&self) {
+            if let Some(commands) = self.commands {
+                commands.borrow_mut().push_back(MockCommand::RereadParams);
+            }
         }
     }
 }
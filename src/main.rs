@@ -1,4 +1,5 @@
 use std::io::stdout;
+use std::process::ExitCode;
 use std::sync::{mpsc, Arc};
 
 use anyhow::Result;
@@ -10,23 +11,48 @@ use crossterm::{
 
 use wiremix::app;
 use wiremix::config::Config;
+use wiremix::error::{ErrorKind, ErrorReport};
 use wiremix::event::Event;
+use wiremix::headless;
 use wiremix::input;
 use wiremix::opt::Opt;
+use wiremix::osc;
+use wiremix::signal;
 use wiremix::wirehose::Session;
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
+    // Parse command-line arguments
+    let opt = Opt::parse();
+
+    match run(&opt) {
+        Err(error) if opt.json_errors => {
+            eprintln!("{}", serde_json::to_string(&ErrorReport::from(&error))?);
+            Ok(ExitCode::FAILURE)
+        }
+        result => result.map(|()| ExitCode::SUCCESS),
+    }
+}
+
+fn run(opt: &Opt) -> Result<()> {
     // Event channel for sending PipeWire and input events to the UI
     let (event_tx, event_rx) = mpsc::channel();
     let event_tx = Arc::new(event_tx);
 
-    // Parse command-line arguments
-    let opt = Opt::parse();
-
     let config_default_path = Config::default_path();
     let config_path = opt.config.as_deref().or(config_default_path.as_deref());
 
-    let config = Config::try_new(config_path, &opt)?;
+    let config = Config::try_new(config_path, opt)
+        .map_err(|error| ErrorKind::Config.tag(error))?;
+
+    // Re-read the configuration on SIGHUP. This blocks SIGHUP, so it needs to
+    // happen before spawning any other threads.
+    if opt.command.is_none() && !opt.dump_state {
+        let config_path = config_path.map(|path| path.to_path_buf());
+        let opt = opt.clone();
+        signal::spawn(Arc::clone(&event_tx), move || {
+            Config::try_new(config_path.as_deref(), &opt)
+        })?;
+    }
 
     // Handler for events from PipeWire - just wrap them and put them on the
     // event channel.
@@ -35,9 +61,26 @@ fn main() -> Result<()> {
         move |event| event_tx.send(Event::Pipewire(event)).is_ok()
     };
     // Spawn the wirehose thread to monitor PipeWire
-    let client = Session::spawn(config.remote.clone(), event_handler)?;
+    let client = Session::spawn(config.remote.clone(), event_handler)
+        .map_err(|error| ErrorKind::Connection.tag(error))?;
+
+    if opt.dump_state {
+        return headless::dump_state(&client, event_rx, &config);
+    }
+
+    if let Some(command) = &opt.command {
+        // Headless mode for one-shot commands
+        return headless::run(&client, event_rx, &config, command);
+    }
+
     let _input_handle = input::spawn(Arc::clone(&event_tx));
 
+    // Remote control is opt-in since anything that can reach the socket can
+    // change the mixer.
+    if let Some(addr) = config.osc_listen {
+        osc::spawn(Arc::clone(&event_tx), addr)?;
+    }
+
     #[cfg(debug_assertions)]
     if opt.dump_events {
         // Event dumping mode for debugging the monitor code
@@ -1,32 +1,68 @@
 //! Peak level meter rendering.
 
 use ratatui::{
+    layout::Flex,
     prelude::{Alignment, Buffer, Constraint, Direction, Layout, Rect, Widget},
+    style::{Color, Style},
     text::{Line, Span},
 };
 
-use crate::config::Config;
+use crate::config::property_key::channel_position_name;
+use crate::config::{self, Config, MeterScale};
 
-fn render_peak(peak: f32, area: Rect) -> (usize, usize, usize) {
-    fn normalize(value: f32) -> f32 {
-        let amplitude = 10.0_f32.powf(value / 60.0);
-        let min = 10.0_f32.powf(-60.0 / 60.0);
-        let max = 10.0_f32.powf(6.0 / 60.0);
+/// Level at the top of the meter. Anything above 0 dBFS is drawn as overload.
+const MAX_DB: f32 = 6.0;
 
-        (amplitude - min) / (max - min)
-    }
+/// SPA channel positions in the order their meters are drawn
+const SPEAKER_ORDER: [u32; 9] = [
+    3,  // FL
+    4,  // FR
+    5,  // FC
+    6,  // LFE
+    12, // RL
+    13, // RR
+    7,  // SL
+    8,  // SR
+    11, // RC
+];
+
+fn normalize(value: f32) -> f32 {
+    let amplitude = 10.0_f32.powf(value / 60.0);
+    let min = 10.0_f32.powf(-60.0 / 60.0);
+    let max = 10.0_f32.powf(MAX_DB / 60.0);
 
-    // Convert to dB between -20 and +3
+    (amplitude - min) / (max - min)
+}
+
+/// Returns how far a peak reaches along a meter, from 0.0 to 1.0.
+pub fn level(peak: f32, meter: &config::Meter) -> f32 {
     let db = 20.0 * (peak + 1e-10).log10();
-    let vu_value = db.clamp(-60.0, 6.0);
+    match meter.scale {
+        MeterScale::Linear => normalize(db.clamp(-60.0, MAX_DB)),
+        // Equal steps in dB from -db_range up to the top
+        MeterScale::Log => {
+            let db = db.clamp(-meter.db_range, MAX_DB);
+            (db + meter.db_range) / (meter.db_range + MAX_DB)
+        }
+    }
+}
+
+/// Returns the number of lit characters for a peak.
+fn lit_count(peak: f32, total_chars: usize, meter: &config::Meter) -> usize {
+    let meter = level(peak, meter);
 
-    let meter = normalize(vu_value);
+    ((meter * total_chars as f32).round() as usize).min(total_chars)
+}
 
-    let total_chars = area.width as usize;
-    let lit = ((meter * total_chars as f32).round() as usize).min(total_chars);
+fn render_peak(
+    peak: f32,
+    total_chars: usize,
+    meter: &config::Meter,
+) -> (usize, usize, usize) {
+    let lit = lit_count(peak, total_chars, meter);
 
     // Values above 0.0 will be colored differently
-    let zero_char = (normalize(0.0) * total_chars as f32).round() as usize;
+    let zero_char = (level(1.0, meter) * total_chars as f32).round() as usize;
 
     // Assign colors
     let active_size = lit.min(zero_char);
@@ -38,10 +74,218 @@ fn render_peak(peak: f32, area: Rect) -> (usize, usize, usize) {
     (active_size, overload_size, inactive_size)
 }
 
+/// Returns a horizontal meter of total_chars glyphs as plain text, for
+/// printing outside the UI.
+pub fn text(peak: f32, total_chars: usize, config: &Config) -> String {
+    let (active_peak, overload_peak, inactive_peak) =
+        render_peak(peak, total_chars, &config.meter);
+    let char_set = &config.char_set;
+    [
+        char_set.meter_right_active.repeat(active_peak),
+        char_set.meter_right_overload.repeat(overload_peak),
+        char_set.meter_right_inactive.repeat(inactive_peak),
+    ]
+    .concat()
+}
+
+/// Returns the RGB value of a color, using the xterm palette for named and
+/// indexed colors.
+fn rgb(color: Color) -> Option<[f32; 3]> {
+    const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let [r, g, b] = match color {
+        Color::Reset => return None,
+        Color::Black | Color::Indexed(0) => [0, 0, 0],
+        Color::Red | Color::Indexed(1) => [205, 0, 0],
+        Color::Green | Color::Indexed(2) => [0, 205, 0],
+        Color::Yellow | Color::Indexed(3) => [205, 205, 0],
+        Color::Blue | Color::Indexed(4) => [0, 0, 238],
+        Color::Magenta | Color::Indexed(5) => [205, 0, 205],
+        Color::Cyan | Color::Indexed(6) => [0, 205, 205],
+        Color::Gray | Color::Indexed(7) => [229, 229, 229],
+        Color::DarkGray | Color::Indexed(8) => [127, 127, 127],
+        Color::LightRed | Color::Indexed(9) => [255, 0, 0],
+        Color::LightGreen | Color::Indexed(10) => [0, 255, 0],
+        Color::LightYellow | Color::Indexed(11) => [255, 255, 0],
+        Color::LightBlue | Color::Indexed(12) => [92, 92, 255],
+        Color::LightMagenta | Color::Indexed(13) => [255, 0, 255],
+        Color::LightCyan | Color::Indexed(14) => [0, 255, 255],
+        Color::White | Color::Indexed(15) => [255, 255, 255],
+        Color::Indexed(index @ 16..=231) => {
+            let index = usize::from(index - 16);
+            [CUBE[index / 36], CUBE[index / 6 % 6], CUBE[index % 6]]
+        }
+        Color::Indexed(index) => {
+            let gray = 8 + (index - 232) * 10;
+            [gray, gray, gray]
+        }
+        Color::Rgb(r, g, b) => [r, g, b],
+    };
+    Some([r, g, b].map(|channel| f32::from(channel) / 255.0))
+}
+
+/// Converts RGB to hue (in degrees), saturation, and value.
+fn hsv([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+    [hue, saturation, max]
+}
+
+fn from_hsv([hue, saturation, value]: [f32; 3]) -> Color {
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let [r, g, b] = match hue.rem_euclid(360.0) as u32 / 60 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+    let m = value - chroma;
+    let [r, g, b] =
+        [r, g, b].map(|channel| ((channel + m) * 255.0).round() as u8);
+    Color::Rgb(r, g, b)
+}
+
+/// Blends from one color to another by t (from 0.0 to 1.0). The hue takes
+/// the short way around, so green to red passes through yellow.
+fn blend(from: Color, to: Color, t: f32) -> Option<Color> {
+    let [from_hue, from_saturation, from_value] = hsv(rgb(from)?);
+    let [to_hue, to_saturation, to_value] = hsv(rgb(to)?);
+    let mut hue_delta = to_hue - from_hue;
+    if hue_delta > 180.0 {
+        hue_delta -= 360.0;
+    } else if hue_delta < -180.0 {
+        hue_delta += 360.0;
+    }
+    let t = t.clamp(0.0, 1.0);
+    Some(from_hsv([
+        from_hue + hue_delta * t,
+        from_saturation + (to_saturation - from_saturation) * t,
+        from_value + (to_value - from_value) * t,
+    ]))
+}
+
+/// Returns the style of the active cell at position (counting from 1) along
+/// a meter total_chars long. With meter.gradient, the color shifts from
+/// meter_active toward meter_overload as the position approaches 0 dBFS.
+fn active_style(position: usize, total_chars: usize, config: &Config) -> Style {
+    let theme = &config.theme;
+    if !config.meter.gradient {
+        return theme.meter_active;
+    }
+    let zero_char = (level(1.0, &config.meter) * total_chars as f32).round();
+    let t = position as f32 / zero_char.max(1.0);
+    theme
+        .meter_active
+        .fg
+        .zip(theme.meter_overload.fg)
+        .and_then(|(from, to)| blend(from, to, t))
+        .map_or(theme.meter_active, |color| theme.meter_active.fg(color))
+}
+
+/// Returns the number of glyphs that fit across a horizontal meter.
+fn glyph_count(area: Rect, config: &Config) -> usize {
+    (area.width / config.char_set.meter_glyph_width()) as usize
+}
+
+/// Returns the x coordinate of the glyph at a 1-based position along a
+/// horizontal meter. Left meters grow from right to left.
+fn glyph_x(area: Rect, position: usize, is_left: bool, config: &Config) -> u16 {
+    let glyph_width = config.char_set.meter_glyph_width();
+    let offset =
+        (position.saturating_sub(1) as u16).saturating_mul(glyph_width);
+    if is_left {
+        area.right()
+            .saturating_sub(offset.saturating_add(glyph_width))
+    } else {
+        area.x.saturating_add(offset)
+    }
+}
+
+/// Restyles the active cells of a horizontal meter if meter.gradient is
+/// enabled. Left meters grow from right to left.
+fn render_gradient(
+    area: Rect,
+    buf: &mut Buffer,
+    active: usize,
+    is_left: bool,
+    config: &Config,
+) {
+    if !config.meter.gradient {
+        return;
+    }
+
+    let total_chars = glyph_count(area, config);
+    let glyph_width = config.char_set.meter_glyph_width();
+    for position in 1..=active.min(total_chars) {
+        let x = glyph_x(area, position, is_left, config);
+        let style = active_style(position, total_chars, config);
+        for x in x..x.saturating_add(glyph_width) {
+            if let Some(cell) = buf.cell_mut((x, area.y)) {
+                cell.set_style(style);
+            }
+        }
+    }
+}
+
+/// Renders a peak-hold marker over a meter if it is beyond the lit part.
+/// Left meters grow from right to left.
+fn render_hold(
+    area: Rect,
+    buf: &mut Buffer,
+    peak: f32,
+    hold: f32,
+    is_left: bool,
+    config: &Config,
+) {
+    let total_chars = glyph_count(area, config);
+    let held = lit_count(hold, total_chars, &config.meter);
+    if held == 0 || held <= lit_count(peak, total_chars, &config.meter) {
+        return;
+    }
+
+    let x = glyph_x(area, held, is_left, config);
+    let glyph_width = config.char_set.meter_glyph_width();
+    let cell = Rect::new(x, area.y, glyph_width, 1);
+    Span::styled(&config.char_set.meter_hold, config.theme.meter_hold)
+        .render(cell, buf);
+}
+
+/// Renders a latched clip indicator at the far end of a meter.
+fn render_clip(area: Rect, buf: &mut Buffer, is_left: bool, config: &Config) {
+    let total_chars = glyph_count(area, config);
+    if total_chars == 0 {
+        return;
+    }
+
+    let glyph = if is_left {
+        &config.char_set.meter_left_overload
+    } else {
+        &config.char_set.meter_right_overload
+    };
+    let x = glyph_x(area, total_chars, is_left, config);
+    let glyph_width = config.char_set.meter_glyph_width();
+    Span::styled(glyph, config.theme.meter_overload)
+        .render(Rect::new(x, area.y, glyph_width, 1), buf);
+}
+
 pub fn render_stereo(
     meter_area: Rect,
     buf: &mut Buffer,
     peaks: Option<(f32, f32)>,
+    holds: Option<(f32, f32)>,
+    clips: (bool, bool),
     config: &Config,
 ) {
     let layout = Layout::default()
@@ -61,7 +305,7 @@ pub fn render_stereo(
 
     let area = meter_left;
     let (active_peak, overload_peak, inactive_peak) =
-        render_peak(left_peak, area);
+        render_peak(left_peak, glyph_count(area, config), &config.meter);
     Line::from(vec![
         Span::styled(
             config.char_set.meter_left_inactive.repeat(inactive_peak),
@@ -78,10 +322,17 @@ pub fn render_stereo(
     ])
     .alignment(Alignment::Right)
     .render(area, buf);
+    render_gradient(area, buf, active_peak, true, config);
+    if let Some((left_hold, _)) = holds {
+        render_hold(area, buf, left_peak, left_hold, true, config);
+    }
+    if clips.0 {
+        render_clip(area, buf, true, config);
+    }
 
     let area = meter_right;
     let (active_peak, overload_peak, inactive_peak) =
-        render_peak(right_peak, area);
+        render_peak(right_peak, glyph_count(area, config), &config.meter);
     Line::from(vec![
         Span::styled(
             config.char_set.meter_right_active.repeat(active_peak),
@@ -97,6 +348,13 @@ pub fn render_stereo(
         ),
     ])
     .render(area, buf);
+    render_gradient(area, buf, active_peak, false, config);
+    if let Some((_, right_hold)) = holds {
+        render_hold(area, buf, right_peak, right_hold, false, config);
+    }
+    if clips.1 {
+        render_clip(area, buf, false, config);
+    }
 
     let live_line = if peaks.is_some() {
         Line::from(Span::styled(
@@ -120,28 +378,17 @@ pub fn render_stereo(
     live_line.render(meter_live, buf);
 }
 
-pub fn render_mono(
-    meter_area: Rect,
+/// Renders a meter growing from left to right.
+fn render_bar(
+    area: Rect,
     buf: &mut Buffer,
-    peak: Option<f32>,
+    peak: f32,
+    hold: Option<f32>,
+    clipped: bool,
     config: &Config,
 ) {
-    let mono_peak = peak.unwrap_or_default();
-
-    let layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Length(1), // meter_live
-            Constraint::Fill(2),   // meter_mono
-        ])
-        .spacing(1)
-        .split(meter_area);
-    let meter_live = layout[0];
-    let meter_mono = layout[1];
-
-    let area = meter_mono;
     let (active_peak, overload_peak, inactive_peak) =
-        render_peak(mono_peak, area);
+        render_peak(peak, glyph_count(area, config), &config.meter);
     Line::from(vec![
         Span::styled(
             config.char_set.meter_right_active.repeat(active_peak),
@@ -157,8 +404,18 @@ pub fn render_mono(
         ),
     ])
     .render(area, buf);
+    render_gradient(area, buf, active_peak, false, config);
+    if let Some(hold) = hold {
+        render_hold(area, buf, peak, hold, false, config);
+    }
+    if clipped {
+        render_clip(area, buf, false, config);
+    }
+}
 
-    let live_line = if peak.is_some() {
+/// Returns the single-character live indicator.
+fn live_line(live: bool, config: &Config) -> Line<'_> {
+    if live {
         Line::from(Span::styled(
             &config.char_set.meter_center_right_active,
             config.theme.meter_center_active,
@@ -168,6 +425,329 @@ pub fn render_mono(
             &config.char_set.meter_center_right_inactive,
             config.theme.meter_center_inactive,
         ))
+    }
+}
+
+pub fn render_mono(
+    meter_area: Rect,
+    buf: &mut Buffer,
+    peak: Option<f32>,
+    hold: Option<f32>,
+    clipped: bool,
+    config: &Config,
+) {
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(1), // meter_live
+            Constraint::Fill(2),   // meter_mono
+        ])
+        .spacing(1)
+        .split(meter_area);
+    let meter_live = layout[0];
+    let meter_mono = layout[1];
+
+    render_bar(
+        meter_mono,
+        buf,
+        peak.unwrap_or_default(),
+        hold,
+        clipped,
+        config,
+    );
+    live_line(peak.is_some(), config).render(meter_live, buf);
+}
+
+/// Returns the channel indexes of a node in speaker order, each with a label.
+/// Channels at positions missing from SPEAKER_ORDER follow in index order.
+/// Without positions, channels keep their order. Channels are labeled by
+/// name, then by position, then by number.
+pub fn channel_order(
+    positions: Option<&[u32]>,
+    names: Option<&[String]>,
+    count: usize,
+) -> Vec<(usize, String)> {
+    let position = |index: usize| positions?.get(index).copied();
+    let rank = |index: usize| {
+        position(index)
+            .and_then(|position| {
+                SPEAKER_ORDER
+                    .iter()
+                    .position(|&ordered| ordered == position)
+            })
+            .unwrap_or(SPEAKER_ORDER.len())
     };
-    live_line.render(meter_live, buf);
+
+    let mut order: Vec<usize> = (0..count).collect();
+    // Stable, so unknown positions stay in index order
+    order.sort_by_key(|&index| rank(index));
+    order
+        .into_iter()
+        .map(|index| {
+            let name = names
+                .and_then(|names| names.get(index))
+                .filter(|name| !name.is_empty());
+            let label = match (name, position(index)) {
+                (Some(name), _) => name.clone(),
+                (None, Some(position)) => channel_position_name(position),
+                (None, None) => (index + 1).to_string(),
+            };
+            (index, label)
+        })
+        .collect()
+}
+
+/// Renders a labeled meter for each channel side by side. live indicates
+/// whether peaks are being received.
+pub fn render_channels(
+    meter_area: Rect,
+    buf: &mut Buffer,
+    columns: &[Column],
+    live: bool,
+    config: &Config,
+) {
+    let constraints =
+        std::iter::once(Constraint::Length(1)) // meter_live
+            .chain(columns.iter().map(|_| Constraint::Fill(1)));
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .spacing(1)
+        .split(meter_area);
+    let meter_live = layout[0];
+
+    for (column, &area) in columns.iter().zip(layout.iter().skip(1)) {
+        let label = column.label.unwrap_or_default();
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(label.len() as u16), // label_area
+                Constraint::Fill(1),                    // bar_area
+            ])
+            .spacing(1)
+            .split(area);
+        Span::styled(label, config.theme.meter_label).render(layout[0], buf);
+        render_bar(
+            layout[1],
+            buf,
+            column.peak,
+            column.hold,
+            column.clipped,
+            config,
+        );
+    }
+
+    live_line(live, config).render(meter_live, buf);
+}
+
+/// State of one channel of a multichannel meter.
+#[derive(Default, Clone, Copy)]
+pub struct Column<'a> {
+    pub peak: f32,
+    pub hold: Option<f32>,
+    pub clipped: bool,
+    pub label: Option<&'a str>,
+}
+
+/// Renders a single channel as a column growing from the bottom of area,
+/// with its label, if any, underneath.
+fn render_column(
+    area: Rect,
+    buf: &mut Buffer,
+    column: Column,
+    config: &Config,
+) {
+    let area = match column.label {
+        Some(label) => {
+            let label_area = Rect {
+                y: area.bottom().saturating_sub(1),
+                height: area.height.min(1),
+                ..area
+            };
+            Span::styled(label, config.theme.meter_label)
+                .render(label_area, buf);
+            Rect {
+                height: area.height.saturating_sub(1),
+                ..area
+            }
+        }
+        None => area,
+    };
+
+    let total_chars = area.height as usize;
+    let (active_peak, overload_peak, _) =
+        render_peak(column.peak, total_chars, &config.meter);
+    let lit = active_peak + overload_peak;
+    let held = column
+        .hold
+        .map(|hold| lit_count(hold, total_chars, &config.meter))
+        .filter(|&held| held > lit);
+
+    let char_set = &config.char_set;
+    let theme = &config.theme;
+    for level in 1..=total_chars {
+        let (symbol, style) = if column.clipped && level == total_chars {
+            (&char_set.meter_right_overload, theme.meter_overload)
+        } else if level <= active_peak {
+            (
+                &char_set.meter_right_active,
+                active_style(level, total_chars, config),
+            )
+        } else if level <= lit {
+            (&char_set.meter_right_overload, theme.meter_overload)
+        } else if held == Some(level) {
+            (&char_set.meter_hold, theme.meter_hold)
+        } else {
+            (&char_set.meter_right_inactive, theme.meter_inactive)
+        };
+        let y = area.bottom().saturating_sub(level as u16);
+        Span::styled(symbol.repeat(glyph_count(area, config)), style)
+            .render(Rect::new(area.x, y, area.width, 1), buf);
+    }
+}
+
+/// Renders a meter with a column for each channel. live indicates whether
+/// peaks are being received.
+pub fn render_vertical(
+    meter_area: Rect,
+    buf: &mut Buffer,
+    columns: &[Column],
+    live: bool,
+    config: &Config,
+) {
+    let constraints = std::iter::once(Constraint::Length(1)) // meter_live
+        .chain(columns.iter().map(|column| {
+            // Wide enough for the label
+            let width = column.label.map_or(0, |label| label.len());
+            Constraint::Length(width.max(2) as u16)
+        }));
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .spacing(1)
+        .flex(Flex::Start)
+        .split(meter_area);
+    let meter_live = layout[0];
+
+    for (&column, &area) in columns.iter().zip(layout.iter().skip(1)) {
+        render_column(area, buf, column, config);
+    }
+
+    // The live indicator sits at the base of the columns.
+    let bottom = meter_live.bottom().saturating_sub(1);
+    live_line(live, config).render(
+        Rect {
+            y: bottom,
+            height: 1,
+            ..meter_live
+        },
+        buf,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_scale() {
+        let meter = config::Meter {
+            scale: MeterScale::Log,
+            db_range: 54.0,
+            ..Default::default()
+        };
+        assert_eq!(level(0.0, &meter), 0.0);
+        assert_eq!(level(1.0, &meter), 0.9);
+        assert_eq!(level(2.0, &meter), 1.0);
+        // -24 dBFS is halfway up
+        assert_eq!(lit_count(10.0_f32.powf(-24.0 / 20.0), 10, &meter), 5);
+    }
+
+    #[test]
+    fn text_meter() {
+        let config = Config::from_toml_str("");
+        let char_set = &config.char_set;
+        assert_eq!(
+            text(0.0, 4, &config),
+            char_set.meter_right_inactive.repeat(4)
+        );
+        assert_eq!(
+            text(2.0, 10, &config),
+            [
+                char_set.meter_right_active.repeat(8),
+                char_set.meter_right_overload.repeat(2),
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn gradient_passes_through_yellow() {
+        assert_eq!(
+            blend(Color::LightGreen, Color::LightRed, 0.5),
+            Some(Color::Rgb(255, 255, 0))
+        );
+        assert_eq!(
+            blend(Color::Indexed(46), Color::Rgb(255, 0, 0), 0.0),
+            Some(Color::Rgb(0, 255, 0))
+        );
+        assert_eq!(blend(Color::Reset, Color::Red, 0.5), None);
+    }
+
+    #[test]
+    fn wide_glyphs_take_two_columns() {
+        let config = Config::from_toml_str(
+            r#"
+            [char_sets.default]
+            allow_wide_glyphs = true
+            meter_left_inactive = "⚫"
+            meter_left_active = "🟢"
+            meter_left_overload = "🔴"
+            meter_right_inactive = "⚫"
+            meter_right_active = "🟢"
+            meter_right_overload = "🔴"
+            meter_hold = "🟡"
+            "#,
+        );
+        let area = Rect::new(10, 0, 5, 1);
+        assert_eq!(glyph_count(area, &config), 2);
+        assert_eq!(glyph_x(area, 1, false, &config), 10);
+        assert_eq!(glyph_x(area, 2, false, &config), 12);
+        // Left meters are right-aligned, leaving the odd column at the start.
+        assert_eq!(glyph_x(area, 1, true, &config), 13);
+        assert_eq!(glyph_x(area, 2, true, &config), 11);
+    }
+
+    #[test]
+    fn speaker_order() {
+        let labels = |positions: Option<&[u32]>, count| {
+            channel_order(positions, None, count)
+                .into_iter()
+                .map(|(index, label)| format!("{index}:{label}"))
+                .collect::<Vec<_>>()
+        };
+        // 5.1 in ALSA order: FL FR RL RR FC LFE
+        assert_eq!(
+            labels(Some(&[3, 4, 12, 13, 5, 6]), 6),
+            ["0:FL", "1:FR", "4:FC", "5:LFE", "2:RL", "3:RR"]
+        );
+        // Unknown positions follow in index order.
+        assert_eq!(
+            labels(Some(&[0x1001, 4, 0x1000, 3]), 4),
+            ["3:FL", "1:FR", "0:AUX1", "2:AUX0"]
+        );
+        assert_eq!(labels(None, 3), ["0:1", "1:2", "2:3"]);
+
+        // Names take precedence, but don't change the order.
+        let names = ["Kick", "", "Snare"].map(String::from);
+        let order = channel_order(Some(&[4, 3]), Some(&names), 3);
+        assert_eq!(
+            order,
+            [
+                (1, String::from("FL")),
+                (0, String::from("Kick")),
+                (2, String::from("Snare")),
+            ]
+        );
+    }
 }
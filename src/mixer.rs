@@ -0,0 +1,276 @@
+//! A mixer for embedding wiremix's PipeWire handling without the UI.
+//!
+//! [`Mixer`] spawns the PipeWire monitoring thread, keeps a
+//! [`State`](`crate::wirehose::state::State`) up to date on a thread of its
+//! own, and offers [`Snapshot`]s of the nodes along with commands that
+//! find their nodes by name.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use wiremix::config::Config;
+//! use wiremix::mixer::Mixer;
+//! use wiremix::opt::Opt;
+//!
+//! let config = Config::try_new(None, &Opt::default())?;
+//! let mixer = Mixer::spawn(config)?;
+//! mixer.wait_until_ready(Duration::from_secs(2))?;
+//! for node in mixer.snapshot().nodes {
+//!     println!("{}: {:?}", node.title, node.volumes);
+//! }
+//! mixer.set_volume("Speakers", 50.0)?;
+//! # anyhow::Ok(())
+//! ```
+
+use std::sync::{mpsc, Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::device_kind::DeviceKind;
+use crate::headless;
+use crate::view::{self, View};
+use crate::wirehose::{
+    media_class, state::State, CommandSender, Event, ObjectId, Session,
+};
+
+/// A node as of the time of a [`Snapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NodeInfo {
+    /// node.name
+    pub name: String,
+    /// Displayed title
+    pub title: String,
+    pub media_class: String,
+    /// Channel volumes on the cubic scale PipeWire uses
+    pub volumes: Vec<f32>,
+    pub mute: bool,
+    pub is_default_sink: bool,
+    pub is_default_source: bool,
+}
+
+impl From<&view::Node> for NodeInfo {
+    fn from(node: &view::Node) -> Self {
+        Self {
+            name: node.name.clone(),
+            title: node.title.clone(),
+            media_class: node.media_class.clone(),
+            volumes: node.volumes.clone(),
+            mute: node.mute,
+            is_default_sink: node.is_default_sink,
+            is_default_source: node.is_default_source,
+        }
+    }
+}
+
+/// The nodes known to a [`Mixer`] at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Snapshot {
+    /// Nodes in the order the All tab lists them
+    pub nodes: Vec<NodeInfo>,
+}
+
+impl Snapshot {
+    /// Finds the first node whose title or node.name is name.
+    pub fn node(&self, name: &str) -> Option<&NodeInfo> {
+        self.nodes
+            .iter()
+            .find(|node| node.title == name || node.name == name)
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    state: State,
+    /// Has the initial state been received?
+    ready: bool,
+    /// Error reported before the initial state was received
+    error: Option<String>,
+}
+
+/// State shared with the event thread
+#[derive(Default)]
+struct Shared {
+    inner: Mutex<Inner>,
+    /// Notified after every event
+    changed: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // State updates don't panic halfway, so the state is still usable.
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A handle to a PipeWire mixer.
+///
+/// Commands are sent asynchronously, and their effects show up in later
+/// snapshots once PipeWire reports them. A `Mixer<Session>` is `Send` and
+/// `Sync`, so it can be shared between threads.
+pub struct Mixer<S: CommandSender = Session> {
+    wirehose: S,
+    config: Config,
+    shared: Arc<Shared>,
+}
+
+impl Mixer {
+    /// Connects to the PipeWire instance named by `config.remote`.
+    pub fn spawn(config: Config) -> Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let handler = move |event| tx.send(event).is_ok();
+        let session = Session::spawn(config.remote.clone(), handler)?;
+        Ok(Self::with_sender(session, rx, config))
+    }
+}
+
+impl<S: CommandSender> Mixer<S> {
+    /// Creates a mixer which sends commands to `wirehose` and follows the
+    /// events received on `rx`. The event thread exits once the sending side
+    /// of `rx` is dropped.
+    pub fn with_sender(
+        wirehose: S,
+        rx: mpsc::Receiver<Event>,
+        config: Config,
+    ) -> Self {
+        let shared = Arc::new(Shared::default());
+        thread::spawn({
+            let shared = Arc::clone(&shared);
+            move || receive(&shared, rx)
+        });
+        Self {
+            wirehose,
+            config,
+            shared,
+        }
+    }
+
+    /// Blocks until the initial PipeWire state has been received.
+    ///
+    /// Fails if PipeWire reports an error first or if timeout passes.
+    pub fn wait_until_ready(&self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        let mut inner = self.shared.lock();
+        while !inner.ready {
+            if let Some(error) = inner.error.take() {
+                bail!(error);
+            }
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            if timeout.is_zero() {
+                bail!("Timed out waiting for PipeWire");
+            }
+            inner = self
+                .shared
+                .changed
+                .wait_timeout(inner, timeout)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+        Ok(())
+    }
+
+    /// Returns the nodes as they are now.
+    pub fn snapshot(&self) -> Snapshot {
+        self.with_view(|view| Snapshot {
+            nodes: view
+                .nodes_all
+                .iter()
+                .filter_map(|node_id| view.nodes.get(node_id))
+                .map(NodeInfo::from)
+                .collect(),
+        })
+    }
+
+    /// Sets every channel of the named node to percent, respecting
+    /// `max_volume_percent` if it is enforced.
+    pub fn set_volume(&self, name: &str, percent: f32) -> Result<()> {
+        let max = self
+            .config
+            .enforce_max_volume
+            .then_some(self.config.max_volume_percent);
+        self.with_node(name, |view, node_id, _| {
+            let adjustment = view::VolumeAdjustment::Absolute(percent / 100.0);
+            if !view.volume(node_id, adjustment, max) {
+                bail!("Can't set volume of '{name}' to {percent}%");
+            }
+            Ok(())
+        })
+    }
+
+    /// Mutes or unmutes the named node.
+    pub fn mute(&self, name: &str, mute: bool) -> Result<()> {
+        self.with_node(name, |view, node_id, node| {
+            if node.mute != mute {
+                // View::mute() toggles
+                view.mute(node_id);
+            }
+            Ok(())
+        })
+    }
+
+    /// Makes the named sink or source the default.
+    pub fn set_default(&self, name: &str) -> Result<()> {
+        self.with_node(name, |view, node_id, node| {
+            let device_kind = if media_class::is_sink(&node.media_class) {
+                DeviceKind::Sink
+            } else if media_class::is_source(&node.media_class) {
+                DeviceKind::Source
+            } else {
+                bail!("'{name}' is not a sink or source");
+            };
+            view.set_default(node_id, device_kind);
+            Ok(())
+        })
+    }
+
+    fn with_view<T>(&self, f: impl FnOnce(&View) -> T) -> T {
+        let inner = self.shared.lock();
+        let view =
+            headless::build_view(&self.wirehose, &inner.state, &self.config);
+        f(&view)
+    }
+
+    fn with_node(
+        &self,
+        name: &str,
+        f: impl FnOnce(&View, ObjectId, &view::Node) -> Result<()>,
+    ) -> Result<()> {
+        self.with_view(|view| {
+            let node_id = headless::find_node(view, name)
+                .ok_or_else(|| anyhow!("No node named '{name}'"))?;
+            f(view, node_id, &view.nodes[&node_id])
+        })
+    }
+}
+
+/// Applies events to the shared state until the channel closes.
+fn receive(shared: &Shared, rx: mpsc::Receiver<Event>) {
+    for event in rx {
+        let mut inner = shared.lock();
+        match event {
+            Event::State(event) => {
+                // Peaks aren't captured, so capture eligibility is ignored.
+                inner.state.update(event);
+            }
+            Event::Ready => inner.ready = true,
+            Event::Error(message) => {
+                // Before we're ready, errors are likely connection failures.
+                if !inner.ready {
+                    inner.error = Some(message);
+                }
+            }
+            // Mixers don't switch remotes.
+            Event::RemoteSwitchFailed { .. } => {}
+            Event::Disconnected => {
+                // Everything is sent again after reconnecting.
+                inner.state = State::default();
+                inner.ready = false;
+            }
+        }
+        drop(inner);
+        shared.changed.notify_all();
+    }
+}
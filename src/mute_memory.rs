@@ -0,0 +1,155 @@
+//! Per-node volumes to restore when unmuting.
+
+use std::collections::HashMap;
+
+use crate::view::View;
+use crate::wirehose::ObjectId;
+
+#[derive(Debug)]
+struct Entry {
+    /// Volumes from before the node was muted
+    volumes: Vec<f32>,
+    /// Has the mute taken effect yet?
+    seen_muted: bool,
+}
+
+/// Remembers the volumes of nodes muted through wiremix.
+///
+/// Some devices report a volume of 0 while muted, so unmuting them would
+/// otherwise leave them silent.
+#[derive(Default, Debug)]
+pub struct MuteMemory {
+    entries: HashMap<ObjectId, Entry>,
+}
+
+impl MuteMemory {
+    /// Remember the volumes of a node that is about to be muted.
+    pub fn record(&mut self, node_id: ObjectId, volumes: Vec<f32>) {
+        self.entries.insert(
+            node_id,
+            Entry {
+                volumes,
+                seen_muted: false,
+            },
+        );
+    }
+
+    /// Forget nodes which have gone away, have been unmuted by something
+    /// else, or have had their volume changed while muted.
+    pub fn update(&mut self, view: &View) {
+        self.entries.retain(|node_id, entry| {
+            let Some(node) = view.nodes.get(node_id) else {
+                return false;
+            };
+            if !node.mute {
+                // Still waiting for the mute to take effect?
+                return !entry.seen_muted;
+            }
+            entry.seen_muted = true;
+
+            is_unchanged(&entry.volumes, &node.volumes)
+        });
+    }
+
+    /// Returns the volumes to restore for a node that is about to be unmuted.
+    pub fn take(&mut self, node_id: ObjectId) -> Option<Vec<f32>> {
+        self.entries.remove(&node_id).map(|entry| entry.volumes)
+    }
+}
+
+/// Checks if the volumes of a muted node are what they were before muting,
+/// allowing for devices that report 0 while muted.
+fn is_unchanged(before: &[f32], current: &[f32]) -> bool {
+    before == current || current.iter().all(|&volume| volume == 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+    use crate::mock;
+    use crate::wirehose::{state::State, PropertyStore, StateEvent};
+
+    fn view_with<'a>(
+        wirehose: &'a mock::WirehoseHandle<'a>,
+        state: &mut State,
+        node_id: ObjectId,
+        volumes: Vec<f32>,
+        mute: bool,
+    ) -> View<'a> {
+        let mut props = PropertyStore::default();
+        props.set_media_class(String::from("Audio/Sink"));
+        props.set_node_name(String::from("sink"));
+        props.set_object_serial(0);
+        state.update(StateEvent::NodeProperties {
+            object_id: node_id,
+            props,
+        });
+        state.update(StateEvent::NodeVolumes {
+            object_id: node_id,
+            volumes,
+        });
+        state.update(StateEvent::NodeMute {
+            object_id: node_id,
+            mute,
+        });
+        View::from(
+            wirehose,
+            state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        )
+    }
+
+    #[test]
+    fn update_keeps_until_changed() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut state = State::default();
+        let node_id = ObjectId::from_raw_id(0);
+        let mut memory = MuteMemory::default();
+        memory.record(node_id, vec![0.5, 0.5]);
+
+        // The mute hasn't taken effect yet
+        let view =
+            view_with(&wirehose, &mut state, node_id, vec![0.5, 0.5], false);
+        memory.update(&view);
+        assert!(memory.entries.contains_key(&node_id));
+
+        let view =
+            view_with(&wirehose, &mut state, node_id, vec![0.0, 0.0], true);
+        memory.update(&view);
+        assert!(memory.entries.contains_key(&node_id));
+
+        // Changed while muted
+        let view =
+            view_with(&wirehose, &mut state, node_id, vec![0.2, 0.2], true);
+        memory.update(&view);
+        assert_eq!(memory.take(node_id), None);
+    }
+
+    #[test]
+    fn restores_when_untouched() {
+        assert!(is_unchanged(&[0.5, 0.5], &[0.5, 0.5]));
+    }
+
+    #[test]
+    fn restores_when_zeroed_by_device() {
+        assert!(is_unchanged(&[0.5, 0.5], &[0.0, 0.0]));
+    }
+
+    #[test]
+    fn forgets_when_changed() {
+        assert!(!is_unchanged(&[0.5, 0.5], &[0.2, 0.2]));
+    }
+
+    #[test]
+    fn take_forgets() {
+        let mut memory = MuteMemory::default();
+        let node_id = ObjectId::from_raw_id(0);
+        memory.record(node_id, vec![0.5, 0.5]);
+        assert_eq!(memory.take(node_id), Some(vec![0.5, 0.5]));
+        assert_eq!(memory.take(node_id), None);
+    }
+}
@@ -1,10 +1,13 @@
 //! A Ratatui widget representing a single PipeWire node in an object list.
 
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use ratatui::{
     layout::Flex,
     prelude::{Alignment, Buffer, Constraint, Direction, Layout, Rect},
+    style::Style,
     text::{Line, Span},
     widgets::{StatefulWidget, Widget},
 };
@@ -13,11 +16,17 @@ use crossterm::event::{MouseButton, MouseEventKind};
 use smallvec::smallvec;
 
 use crate::app::{Action, MouseArea};
-use crate::config::{Config, Peaks};
+use crate::config::{
+    Config, MeterOrientation, MeterScroll, NodeLayout, Peaks, ShowLinks,
+    VolumeUnit,
+};
 use crate::device_kind::DeviceKind;
+use crate::dropdown_widget;
 use crate::meter;
 use crate::object_list::ObjectList;
+use crate::truncate;
 use crate::view;
+use crate::wirehose::ObjectId;
 
 fn is_default(node: &view::Node, device_kind: Option<DeviceKind>) -> bool {
     match device_kind {
@@ -27,31 +36,83 @@ fn is_default(node: &view::Node, device_kind: Option<DeviceKind>) -> bool {
     }
 }
 
+/// Formats an object's ID and serial for show_ids.
+pub fn format_ids(object_id: ObjectId, object_serial: u64) -> String {
+    format!("(id {}, serial {object_serial})", u32::from(object_id))
+}
+
+/// Formats a cube-root-scaled volume for a volume label.
+pub fn format_volume(unit: VolumeUnit, volume: f32) -> String {
+    match unit {
+        VolumeUnit::Percent => {
+            let percent = (volume * 100.0).round() as u32;
+            format!("{percent}%")
+        }
+        VolumeUnit::Db => {
+            let db = view::volume_to_decibels(volume.powi(3));
+            if db.is_infinite() {
+                String::from("-inf dB")
+            } else if db.abs() >= 100.0 {
+                // Drop the decimal so that it fits in the label
+                format!("{db:.0} dB")
+            } else {
+                format!("{db:.1} dB")
+            }
+        }
+    }
+}
+
 pub struct NodeWidget<'a> {
     config: &'a Config,
+    /// Peak meter mode of the tab showing the node
+    peaks: Peaks,
     device_kind: Option<DeviceKind>,
     node: &'a view::Node,
     selected: bool,
+    expanded: bool,
 }
 
 impl<'a> NodeWidget<'a> {
     pub fn new(
         config: &'a Config,
+        peaks: Peaks,
         device_kind: Option<DeviceKind>,
         node: &'a view::Node,
         selected: bool,
+        expanded: bool,
     ) -> Self {
         Self {
             config,
+            peaks,
             device_kind,
             node,
             selected,
+            expanded,
+        }
+    }
+
+    /// Height of the area holding the volume bar and peak meter.
+    fn bar_height(orientation: MeterOrientation) -> u16 {
+        match orientation {
+            MeterOrientation::Horizontal => 1,
+            MeterOrientation::Vertical => 4,
         }
     }
 
     /// Height of a full node display.
-    pub fn height() -> u16 {
-        3
+    pub fn height(orientation: MeterOrientation) -> u16 {
+        2 + Self::bar_height(orientation)
+    }
+
+    /// Height of a compact node display.
+    pub fn compact_height() -> u16 {
+        1
+    }
+
+    /// Height of a node display of the given unexpanded height expanded to
+    /// show a volume bar for each of its channels.
+    pub fn expanded_height(height: u16, channels: usize) -> u16 {
+        height.saturating_add(channels as u16)
     }
 
     /// Spacing between nodes
@@ -59,6 +120,11 @@ impl<'a> NodeWidget<'a> {
         2
     }
 
+    /// Spacing between compact nodes
+    pub fn compact_spacing() -> u16 {
+        0
+    }
+
     /// Area for the target dropdown
     pub fn dropdown_area(
         object_list: &ObjectList,
@@ -71,7 +137,7 @@ impl<'a> NodeWidget<'a> {
         let max_target_length = object_list
             .targets
             .iter()
-            .map(|(_, title)| title.len())
+            .map(|(_, title, _)| title.len())
             .max()
             .unwrap_or(0);
 
@@ -80,12 +146,92 @@ impl<'a> NodeWidget<'a> {
         let height = std::cmp::min(max_visible_items, object_list.targets.len())
             .saturating_add(2) as u16; // Plus 2 for horizontal borders
 
-        // Align to the right of the list area
+        // Align to the right of the list area, with the first item over the
+        // target on the title line
         let x = list_area.right().saturating_sub(width);
-        // Subtract 1 for the top border
-        let y = object_area.top().saturating_sub(1);
+        dropdown_widget::placement(
+            x,
+            object_area.top(),
+            width,
+            height,
+            list_area,
+        )
+    }
+
+    /// Splits a bar line into areas for the volume bar and (if enabled) the
+    /// peak meter.
+    fn bar_layout(&self, bar_area: Rect) -> (Rect, Option<Rect>) {
+        if self.peaks == Peaks::Off {
+            let layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![
+                    Constraint::Length(2), // _padding
+                    Constraint::Fill(9),   // volume_area
+                    Constraint::Fill(1),   // _padding
+                ])
+                .split(bar_area);
+            // index 0 is _padding
+            (layout[1], None)
+        } else {
+            let layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![
+                    Constraint::Length(2), // _padding
+                    Constraint::Fill(4),   // volume_area
+                    Constraint::Fill(1),   // _padding
+                    Constraint::Fill(4),   // meter_area
+                    Constraint::Fill(1),   // _padding
+                ])
+                .split(bar_area);
+            // index 0 is _padding, index 2 is _padding
+            (layout[1], Some(layout[3]))
+        }
+    }
+}
 
-        Rect::new(x, y, width, height)
+impl NodeWidget<'_> {
+    /// Renders the title, the volume bar, and a mono peak meter side by side
+    /// on one line.
+    fn render_compact(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        mouse_areas: &mut Vec<MouseArea>,
+    ) {
+        let mut constraints = vec![
+            Constraint::Fill(3), // title_area
+            Constraint::Fill(2), // volume_area
+        ];
+        if self.peaks != Peaks::Off {
+            constraints.push(Constraint::Fill(2)); // meter_area
+        }
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
+            .spacing(1)
+            .split(area);
+
+        HeaderWidget::new(
+            self.config,
+            self.device_kind,
+            self.node,
+            self.selected,
+        )
+        .title_line()
+        .render(layout[0], buf);
+        VolumeWidget::new(self.config, self.node, None).render(
+            layout[1],
+            buf,
+            mouse_areas,
+        );
+        if let Some(&meter_area) = layout.get(2) {
+            // There's only room for one line, so the meter is horizontal.
+            MeterWidget {
+                orientation: MeterOrientation::Horizontal,
+                ..MeterWidget::new(self.config, Peaks::Mono, self.node)
+            }
+            .render(meter_area, buf);
+        }
     }
 }
 
@@ -139,62 +285,223 @@ impl StatefulWidget for NodeWidget<'_> {
         let selector_area = layout[0];
         let node_area = layout[1];
 
+        if self.selected {
+            buf.set_style(area, self.config.theme.node_selected_bg);
+        }
+
         SelectorWidget::new(self.config, self.selected)
             .render(selector_area, buf);
 
-        // Split the main node area into a header line and a line for the
-        // volume bar and peak meter.
+        // Split off a line for each channel's volume bar below the main node
+        // area.
+        let orientation = self.config.meter_orientation;
+        let height = match self.config.node_layout {
+            NodeLayout::Full => Self::height(orientation),
+            NodeLayout::Compact => Self::compact_height(),
+        };
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(1), // header_area
-                Constraint::Length(1), // bar_area
+                Constraint::Length(height), // node_area
+                Constraint::Min(0),         // channels_area
             ])
-            .spacing(1)
-            .flex(Flex::Legacy)
             .split(node_area);
-        let header_area = layout[0];
-        let bar_area = layout[1];
-
-        HeaderWidget::new(self.config, self.device_kind, self.node).render(
-            header_area,
-            buf,
-            mouse_areas,
-        );
+        let node_area = layout[0];
+        let channels_area = layout[1];
 
-        // Render volume bar and (if enabled) peak meter
-        let volume = VolumeWidget::new(self.config, self.node);
-        if self.config.peaks == Peaks::Off {
+        if self.config.node_layout == NodeLayout::Compact {
+            self.render_compact(node_area, buf, mouse_areas);
+        } else {
+            // Split the main node area into a header line and an area for
+            // the volume bar and peak meter. Vertical meters make the latter
+            // taller.
+            let bar_height = Self::bar_height(orientation);
             let layout = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(vec![
-                    Constraint::Length(2), // _padding
-                    Constraint::Fill(9),   // volume_area
-                    Constraint::Fill(1),   // _padding
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(1),          // header_area
+                    Constraint::Length(bar_height), // bar_area
                 ])
-                .split(bar_area);
-            // index 0 is _padding
-            let volume_area = layout[1];
+                .spacing(1)
+                .flex(Flex::Legacy)
+                .split(node_area);
+            // The header can use the spacing line for a wrapped target.
+            let header_area = Rect {
+                height: layout[1]
+                    .y
+                    .saturating_sub(layout[0].y)
+                    .max(layout[0].height),
+                ..layout[0]
+            };
+            let bar_area = layout[1];
 
-            volume.render(volume_area, buf, mouse_areas);
-        } else {
-            let layout = Layout::default()
-                .direction(Direction::Horizontal)
+            HeaderWidget::new(
+                self.config,
+                self.device_kind,
+                self.node,
+                self.selected,
+            )
+            .render(header_area, buf, mouse_areas);
+
+            // Render volume bar and (if enabled) peak meter
+            let (volume_area, meter_area) = self.bar_layout(bar_area);
+            // The volume bar stays on the first line, beside the top of a
+            // vertical meter.
+            let volume_area = Rect {
+                height: volume_area.height.min(1),
+                ..volume_area
+            };
+            VolumeWidget::new(self.config, self.node, None).render(
+                volume_area,
+                buf,
+                mouse_areas,
+            );
+            if let Some(meter_area) = meter_area {
+                // Sideways scrolling over the meter overrides the node's volume
+                // scrolling unless configured to adjust the volume too.
+                let scroll_actions = match self.config.meter.scroll {
+                    MeterScroll::Volume => None,
+                    MeterScroll::None => Some(smallvec![]),
+                    MeterScroll::Reset => Some(smallvec![
+                        Action::SelectObject(self.node.object_id),
+                        Action::ResetPeakHold,
+                    ]),
+                };
+                if let Some(actions) = scroll_actions {
+                    mouse_areas.push((
+                        meter_area,
+                        smallvec![
+                            MouseEventKind::ScrollLeft,
+                            MouseEventKind::ScrollRight
+                        ],
+                        actions,
+                    ));
+                }
+                MeterWidget::new(self.config, self.peaks, self.node)
+                    .render(meter_area, buf);
+            }
+        }
+
+        if self.expanded && self.node.group.is_none() {
+            // Render a volume bar for each channel, aligned with the main one
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
                 .constraints(vec![
-                    Constraint::Length(2), // _padding
-                    Constraint::Fill(4),   // volume_area
-                    Constraint::Fill(1),   // _padding
-                    Constraint::Fill(4),   // meter_area
-                    Constraint::Fill(1),   // _padding
+                    Constraint::Length(1);
+                    self.node.volumes.len()
                 ])
-                .split(bar_area);
-            // index 0 is _padding
-            let volume_area = layout[1];
-            // index 2 is _padding
-            let meter_area = layout[3];
+                .split(channels_area);
+            for (index, &row) in rows.iter().enumerate() {
+                let (volume_area, _) = self.bar_layout(row);
+                VolumeWidget::new(self.config, self.node, Some(index)).render(
+                    volume_area,
+                    buf,
+                    mouse_areas,
+                );
+            }
+        }
+    }
+}
+
+/// A node shown on its own, with a volume bar and peak meter for each
+/// channel.
+pub struct FocusWidget<'a> {
+    config: &'a Config,
+    peaks: Peaks,
+    device_kind: Option<DeviceKind>,
+    node: &'a view::Node,
+    /// Index of the channel being adjusted
+    channel: usize,
+}
+
+impl<'a> FocusWidget<'a> {
+    pub fn new(
+        config: &'a Config,
+        peaks: Peaks,
+        device_kind: Option<DeviceKind>,
+        node: &'a view::Node,
+        channel: usize,
+    ) -> Self {
+        Self {
+            config,
+            peaks,
+            device_kind,
+            node,
+            channel,
+        }
+    }
+}
+
+impl StatefulWidget for FocusWidget<'_> {
+    type State = Vec<MouseArea>;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let mouse_areas = state;
+
+        let order = meter::channel_order(
+            self.node.positions.as_deref(),
+            self.node.channel_names.as_deref(),
+            self.node.volumes.len(),
+        );
+        let label_width = order
+            .iter()
+            .map(|(_, label)| label.chars().count())
+            .max()
+            .unwrap_or_default() as u16;
+
+        // The title, then a line for each channel with a blank line between
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                std::iter::once(Constraint::Length(1)) // title_area
+                    .chain(order.iter().map(|_| Constraint::Length(1))),
+            )
+            .spacing(1)
+            .split(area);
+
+        let title_area = Rect {
+            x: layout[0].x.saturating_add(2),
+            width: layout[0].width.saturating_sub(2),
+            ..layout[0]
+        };
+        HeaderWidget::new(self.config, self.device_kind, self.node, true)
+            .title_line()
+            .render(title_area, buf);
+
+        for ((index, label), &row) in order.iter().zip(layout.iter().skip(1)) {
+            let mut constraints = vec![
+                Constraint::Length(1),           // selector_area
+                Constraint::Length(1),           // _padding
+                Constraint::Length(label_width), // label_area
+                Constraint::Length(1),           // _padding
+                Constraint::Fill(4),             // volume_area
+            ];
+            if self.peaks != Peaks::Off {
+                constraints.push(Constraint::Fill(1)); // _padding
+                constraints.push(Constraint::Fill(4)); // meter_area
+            }
+            constraints.push(Constraint::Fill(1)); // _padding
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(constraints)
+                .split(row);
 
-            volume.render(volume_area, buf, mouse_areas);
-            MeterWidget::new(self.config, self.node).render(meter_area, buf);
+            SelectorWidget::new(self.config, *index == self.channel)
+                .render(columns[0], buf);
+            Line::from(label.as_str()).render(columns[2], buf);
+            VolumeWidget::new(self.config, self.node, Some(*index)).render(
+                columns[4],
+                buf,
+                mouse_areas,
+            );
+            if self.peaks != Peaks::Off {
+                MeterWidget {
+                    orientation: MeterOrientation::Horizontal,
+                    channel: Some(*index),
+                    ..MeterWidget::new(self.config, self.peaks, self.node)
+                }
+                .render(columns[6], buf);
+            }
         }
     }
 }
@@ -212,7 +519,14 @@ impl<'a> SelectorWidget<'a> {
 
 impl Widget for SelectorWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        if self.selected {
+        if self.selected && area.height == 1 {
+            // A compact node only has room for the middle.
+            Span::styled(
+                &self.config.char_set.selector_middle,
+                self.config.theme.selector,
+            )
+            .render(area, buf);
+        } else if self.selected {
             // Render and indication that this is the selected node.
             let rows = Layout::default()
                 .direction(Direction::Vertical)
@@ -240,6 +554,7 @@ struct HeaderWidget<'a> {
     config: &'a Config,
     device_kind: Option<DeviceKind>,
     node: &'a view::Node,
+    selected: bool,
 }
 
 impl<'a> HeaderWidget<'a> {
@@ -247,37 +562,95 @@ impl<'a> HeaderWidget<'a> {
         config: &'a Config,
         device_kind: Option<DeviceKind>,
         node: &'a view::Node,
+        selected: bool,
     ) -> Self {
         Self {
             config,
             device_kind,
             node,
+            selected,
         }
     }
 
-    fn target_line(&self) -> Line<'_> {
+    /// Returns the target title shortened to fit in max_width columns. The
+    /// default target indicator is kept when shortening.
+    fn target_line(&self, max_width: u16) -> Line<'_> {
+        let max_width = usize::from(max_width);
         match self.node.target {
             Some(view::Target::Default) => {
                 // Add the default target indicator
+                let indicator = Span::styled(
+                    &self.config.char_set.default_stream,
+                    self.config.theme.default_stream,
+                );
+                let title_width =
+                    max_width.saturating_sub(indicator.width() + 1);
                 Line::from(vec![
-                    Span::styled(
-                        &self.config.char_set.default_stream,
-                        self.config.theme.default_stream,
-                    ),
+                    indicator,
                     Span::from(" "),
                     Span::styled(
-                        &self.node.target_title,
+                        truncate::with_ellipses(
+                            &self.node.target_title,
+                            title_width,
+                        ),
                         self.config.theme.node_target,
                     ),
                 ])
             }
             _ => Line::from(Span::styled(
-                &self.node.target_title,
+                truncate::with_ellipses(&self.node.target_title, max_width),
                 self.config.theme.node_target,
             )),
         }
     }
 
+    /// Returns the style for the node's role, if the theme has one, or the
+    /// plain node_title style.
+    fn title_style(&self) -> Style {
+        let theme = &self.config.theme;
+        self.node
+            .role
+            .as_ref()
+            .and_then(|role| theme.node_title_roles.get(role))
+            .copied()
+            .unwrap_or(theme.node_title)
+    }
+
+    /// Formats the current peak of each channel in dBFS, or returns None if
+    /// the node's peaks aren't being captured. These are the peaks the meters
+    /// show, so they follow the meter's attack and decay.
+    fn peak_values(&self) -> Option<String> {
+        let peaks = self.node.peaks.as_ref().filter(|_| self.node.capturing)?;
+        let peaks: Vec<f32> = peaks.iter().map(|peak| peak.load()).collect();
+        Some(format_dbfs(&peaks))
+    }
+
+    /// Describes the nodes linked to and from this one according to
+    /// show_links, or returns None if there's nothing to show.
+    fn links(&self) -> Option<String> {
+        let inputs = &self.node.inputs;
+        let outputs = &self.node.outputs;
+        if inputs.is_empty() && outputs.is_empty() {
+            return None;
+        }
+        match self.config.show_links {
+            ShowLinks::Off => None,
+            ShowLinks::Count => {
+                Some(format!("{} in, {} out", inputs.len(), outputs.len()))
+            }
+            ShowLinks::Names => {
+                let mut links = Vec::new();
+                if !inputs.is_empty() {
+                    links.push(format!("from {}", inputs.join(", ")));
+                }
+                if !outputs.is_empty() {
+                    links.push(format!("to {}", outputs.join(", ")));
+                }
+                Some(links.join("; "))
+            }
+        }
+    }
+
     fn title_line(&self) -> Line<'_> {
         let default_span = if is_default(self.node, self.device_kind) {
             Span::styled(
@@ -287,11 +660,109 @@ impl<'a> HeaderWidget<'a> {
         } else {
             Span::from(" ")
         };
-        Line::from(vec![
+        let mut line = Line::from(vec![
             default_span,
             Span::from(" "),
-            Span::styled(&self.node.title, self.config.theme.node_title),
-        ])
+            Span::styled(&self.node.title, self.title_style()),
+        ]);
+        // Devices change volume on the hardware, everything else in software.
+        let volume_control = if self.node.device_info.is_some() {
+            &self.config.char_set.volume_hardware
+        } else {
+            &self.config.char_set.volume_software
+        };
+        if !volume_control.is_empty() {
+            line.push_span(Span::from(" "));
+            line.push_span(Span::styled(
+                volume_control,
+                self.config.theme.volume_control,
+            ));
+        }
+        // Tell virtual and network nodes apart from hardware.
+        let markers = [
+            (
+                self.node.is_virtual,
+                &self.config.char_set.node_virtual,
+                self.config.theme.node_virtual,
+            ),
+            (
+                self.node.is_network,
+                &self.config.char_set.node_network,
+                self.config.theme.node_network,
+            ),
+        ];
+        for (_, marker, style) in markers
+            .into_iter()
+            .filter(|(shown, marker, _)| *shown && !marker.is_empty())
+        {
+            line.push_span(Span::from(" "));
+            line.push_span(Span::styled(marker, style));
+        }
+        if let Some(format) = self
+            .node
+            .format
+            .as_ref()
+            .filter(|_| self.selected && self.config.show_format)
+        {
+            line.push_span(Span::from(" "));
+            line.push_span(Span::styled(
+                format!("({format})"),
+                self.config.theme.node_format,
+            ));
+        }
+        if let Some(values) = self
+            .peak_values()
+            .filter(|_| self.selected && self.config.meter.values)
+        {
+            line.push_span(Span::from(" "));
+            line.push_span(Span::styled(
+                format!("({values})"),
+                self.config.theme.node_format,
+            ));
+        }
+        if let Some(latency_ms) =
+            self.node.latency_ms.filter(|_| self.config.show_latency)
+        {
+            line.push_span(Span::from(" "));
+            line.push_span(Span::styled(
+                format!("({latency_ms:.1} ms)"),
+                self.config.theme.node_format,
+            ));
+        }
+        if let Some(process) = self
+            .node
+            .process
+            .as_ref()
+            .filter(|_| self.config.show_process)
+        {
+            line.push_span(Span::from(" "));
+            line.push_span(Span::styled(
+                format!("({process})"),
+                self.config.theme.node_format,
+            ));
+        }
+        if self.config.show_ids {
+            line.push_span(Span::from(" "));
+            line.push_span(Span::styled(
+                format_ids(self.node.object_id, self.node.object_serial),
+                self.config.theme.node_format,
+            ));
+        }
+        if let Some(links) = self.links().filter(|_| self.selected) {
+            line.push_span(Span::from(" "));
+            line.push_span(Span::styled(
+                format!("({links})"),
+                self.config.theme.node_format,
+            ));
+        }
+        if self.node.locked {
+            line.push_span(Span::from(" "));
+            line.push_span(Span::styled(
+                "(locked)",
+                self.config.theme.node_format,
+            ));
+        }
+        line
     }
 }
 
@@ -301,8 +772,41 @@ impl StatefulWidget for HeaderWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let mouse_areas = state;
 
-        let target_line = self.target_line();
-        let target_width = target_line.width().try_into().unwrap_or(u16::MAX);
+        // The first line is for the title, and the line below it is spacing
+        // unless the target is wrapped onto it.
+        let [area, below_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)])
+                .areas(area);
+
+        // Keep the target to half of the header so that a long target title
+        // doesn't crowd out the node title.
+        let target_width = self
+            .target_line(area.width.saturating_sub(3) / 2)
+            .width()
+            .try_into()
+            .unwrap_or(u16::MAX);
+
+        // Leave min_title_width for the title if the target would squeeze it,
+        // by either shortening the target or moving it below.
+        let min_title_width = self.config.min_title_width;
+        // Subtract 2 for margins and 1 for spacing
+        let title_width =
+            area.width.saturating_sub(3).saturating_sub(target_width);
+        let squeezed = title_width < min_title_width;
+        let wrapped = squeezed && self.config.wrap_target;
+        let target_width = if wrapped {
+            0
+        } else if squeezed {
+            area.width.saturating_sub(3).saturating_sub(min_title_width)
+        } else {
+            target_width
+        };
+        // The wrapped target has the line below to itself, less margins.
+        let target_line = self.target_line(if wrapped {
+            area.width.saturating_sub(2)
+        } else {
+            target_width
+        });
 
         // See if we can fit the whole title on the screen. We'll scrap this
         // layout if it doesn't fit.
@@ -337,8 +841,15 @@ impl StatefulWidget for HeaderWidget<'_> {
             let ellipses_area = layout[1];
             target_area = layout[3];
 
-            Span::styled("...", self.config.theme.node_title)
-                .render(ellipses_area, buf);
+            Span::styled("...", self.title_style()).render(ellipses_area, buf);
+        }
+        if wrapped {
+            let layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(0)])
+                .horizontal_margin(1)
+                .split(below_area);
+            target_area = layout[0];
         }
         let (title_area, target_area) = (title_area, target_area);
 
@@ -362,11 +873,56 @@ impl StatefulWidget for HeaderWidget<'_> {
 struct VolumeWidget<'a> {
     config: &'a Config,
     node: &'a view::Node,
+    /// Display a single channel instead of the mean of all channels
+    channel: Option<usize>,
 }
 
 impl<'a> VolumeWidget<'a> {
-    fn new(config: &'a Config, node: &'a view::Node) -> Self {
-        Self { config, node }
+    fn new(
+        config: &'a Config,
+        node: &'a view::Node,
+        channel: Option<usize>,
+    ) -> Self {
+        Self {
+            config,
+            node,
+            channel,
+        }
+    }
+
+    fn volume(&self) -> Option<f32> {
+        let volumes = &self.node.volumes;
+        let volume = match (self.channel, &self.node.group) {
+            (Some(index), _) => *volumes.get(index)?,
+            (None, Some(group)) => group.volume,
+            (None, None) if !volumes.is_empty() => {
+                volumes.iter().sum::<f32>() / volumes.len() as f32
+            }
+            (None, None) => return None,
+        };
+        Some(volume.cbrt())
+    }
+
+    /// The narrowest volume widget with the label drawn over the bar
+    const MIN_OVERLAY_WIDTH: u16 = 24;
+
+    fn label_width(&self) -> u16 {
+        match self.config.volume_unit {
+            VolumeUnit::Percent => 5,
+            VolumeUnit::Db => 8,
+        }
+    }
+
+    /// Formats a cube-root-scaled volume for the label.
+    fn format_volume(&self, volume: f32) -> String {
+        format_volume(self.config.volume_unit, volume)
+    }
+
+    fn set_volume_action(&self, volume: f32) -> Action {
+        match self.channel {
+            Some(index) => Action::SetChannelVolume(index, volume),
+            None => Action::SetAbsoluteVolume(volume),
+        }
     }
 }
 
@@ -378,32 +934,32 @@ impl StatefulWidget for VolumeWidget<'_> {
 
         let max_volume = self.config.max_volume_percent / 100.0;
 
-        let layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Length(5), // volume_label
-                Constraint::Min(0),    // volume_bar
-            ])
-            .spacing(1)
-            .split(area);
-        let volume_label = layout[0];
-        let volume_bar = layout[1];
-
-        let volumes = &self.node.volumes;
-        if !volumes.is_empty() {
-            let mean = volumes.iter().sum::<f32>() / volumes.len() as f32;
-            let volume = mean.cbrt();
-            let percent = (volume * 100.0).round() as u32;
-
-            Line::from(Span::styled(
-                format!("{percent}%"),
-                self.config.theme.volume,
-            ))
-            .alignment(Alignment::Right)
-            .render(volume_label, buf);
+        let overlay =
+            self.config.volume_overlay && area.width >= Self::MIN_OVERLAY_WIDTH;
+        let (volume_label, volume_bar) = if overlay {
+            let [volume_label] =
+                Layout::horizontal([Constraint::Length(self.label_width())])
+                    .flex(Flex::Center)
+                    .areas(area);
+            (volume_label, area)
+        } else {
+            let layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Length(self.label_width()), // volume_label
+                    Constraint::Min(0),                     // volume_bar
+                ])
+                .spacing(1)
+                .split(area);
+            (layout[0], layout[1])
+        };
 
+        if let Some(volume) = self.volume() {
+            let steps = (volume_bar.width
+                / self.config.char_set.volume_glyph_width())
+                as usize;
             let count = ((volume.clamp(0.0, max_volume) / max_volume)
-                * volume_bar.width as f32)
+                * steps as f32)
                 .round() as usize;
 
             let filled = self.config.char_set.volume_filled.repeat(count);
@@ -411,25 +967,54 @@ impl StatefulWidget for VolumeWidget<'_> {
                 .config
                 .char_set
                 .volume_empty
-                .repeat((volume_bar.width as usize).saturating_sub(count));
+                .repeat(steps.saturating_sub(count));
             Line::from(vec![
                 Span::styled(filled, self.config.theme.volume_filled),
                 Span::styled(blank, self.config.theme.volume_empty),
             ])
             .render(volume_bar, buf);
+
+            // Drawn after the bar so that an overlaid label is on top
+            let alignment = if overlay {
+                Alignment::Center
+            } else {
+                Alignment::Right
+            };
+            // Anything above 100% may clip
+            let style = if (volume * 100.0).round() > 100.0 {
+                self.config.theme.volume_boosted
+            } else {
+                self.config.theme.volume
+            };
+            Line::from(Span::styled(self.format_volume(volume), style))
+                .alignment(alignment)
+                .render(volume_label, buf);
         }
-        if self.node.mute {
-            Line::from("muted").render(volume_label, buf);
+        if self.channel.is_none() {
+            let mute = match &self.node.group {
+                Some(group) => group.mute,
+                None => self.node.mute,
+            };
+            if mute && overlay {
+                // Cover the whole overlaid volume
+                let width = usize::from(volume_label.width);
+                Line::from(format!("{:^width$}", "muted"))
+                    .render(volume_label, buf);
+            } else if mute {
+                Line::from("muted").render(volume_label, buf);
+            }
+        }
+        // An overlaid label is part of the bar, so clicking it sets the volume
+        if self.channel.is_none() && !overlay {
+            mouse_areas.push((
+                volume_label,
+                smallvec![MouseEventKind::Down(MouseButton::Left)],
+                smallvec![
+                    Action::SelectObject(self.node.object_id),
+                    Action::ToggleMute
+                ],
+            ));
         }
-
-        mouse_areas.push((
-            volume_label,
-            smallvec![MouseEventKind::Down(MouseButton::Left)],
-            smallvec![
-                Action::SelectObject(self.node.object_id),
-                Action::ToggleMute
-            ],
-        ));
 
         // Add mouse areas for setting volume
         for i in 0..=volume_bar.width {
@@ -458,7 +1043,7 @@ impl StatefulWidget for VolumeWidget<'_> {
                 ],
                 smallvec![
                     Action::SelectObject(self.node.object_id),
-                    Action::SetAbsoluteVolume(sticky_volume),
+                    self.set_volume_action(sticky_volume),
                 ],
             ));
         }
@@ -467,32 +1052,257 @@ impl StatefulWidget for VolumeWidget<'_> {
 
 struct MeterWidget<'a> {
     config: &'a Config,
+    peaks: Peaks,
+    orientation: MeterOrientation,
     node: &'a view::Node,
+    /// Meter only this channel
+    channel: Option<usize>,
 }
 
 impl<'a> MeterWidget<'a> {
-    fn new(config: &'a Config, node: &'a view::Node) -> Self {
-        Self { config, node }
+    fn new(config: &'a Config, peaks: Peaks, node: &'a view::Node) -> Self {
+        Self {
+            config,
+            peaks,
+            orientation: config.meter_orientation,
+            node,
+            channel: None,
+        }
     }
 }
 
-impl Widget for MeterWidget<'_> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        match self.node.peaks.as_deref() {
-            Some([left, right]) if self.config.peaks != Peaks::Mono => {
+impl MeterWidget<'_> {
+    /// Loads the current peak of each channel. Groups show the loudest of
+    /// their members on each channel. Nodes that aren't being captured have
+    /// no peaks, so their meters are drawn as idle.
+    fn peaks(&self) -> Option<Vec<f32>> {
+        if !self.node.capturing {
+            return None;
+        }
+        let Some(group) = &self.node.group else {
+            return self
+                .node
+                .peaks
+                .as_deref()
+                .map(|peaks| peaks.iter().map(|peak| peak.load()).collect());
+        };
+        if group.peaks.is_empty() {
+            return None;
+        }
+        let mut loudest = Vec::new();
+        for peaks in &group.peaks {
+            if loudest.len() < peaks.len() {
+                loudest.resize(peaks.len(), 0.0);
+            }
+            for (loudest, peak) in loudest.iter_mut().zip(peaks.iter()) {
+                *loudest = f32::max(*loudest, peak.load());
+            }
+        }
+        Some(loudest)
+    }
+
+    /// Updates the peak hold for a channel and returns the held peak, or None
+    /// if peak holding is disabled.
+    fn hold(&self, channel: usize, peak: f32, now: Instant) -> Option<f32> {
+        let meter = &self.config.meter;
+        (meter.hold_ms > 0.0).then(|| {
+            self.node.peak_hold.update(
+                channel,
+                peak,
+                Duration::from_secs_f32(meter.hold_ms / 1000.0),
+                Duration::from_secs_f32(meter.decay_ms / 1000.0),
+                now,
+            )
+        })
+    }
+
+    /// Takes the clip latches set by the capture stream for a channel, or for
+    /// every channel if channel is None. Groups clip if any member did.
+    fn take_clip(&self, channel: Option<usize>) -> bool {
+        // Non-short-circuiting so that every latch is cleared
+        let take = |clips: &Arc<[AtomicBool]>| match channel {
+            Some(channel) => clips
+                .get(channel)
+                .is_some_and(|clip| clip.swap(false, Ordering::Relaxed)),
+            None => clips.iter().fold(false, |clipped, clip| {
+                clip.swap(false, Ordering::Relaxed) | clipped
+            }),
+        };
+        match &self.node.group {
+            Some(group) => group
+                .clips
+                .iter()
+                .fold(false, |clipped, clips| take(clips) | clipped),
+            None => self.node.clips.as_ref().is_some_and(take),
+        }
+    }
+
+    /// Updates the clip indicator for a channel and returns whether it is
+    /// lit.
+    fn clip(&self, channel: usize, now: Instant) -> bool {
+        self.update_clip(channel, self.take_clip(Some(channel)), now)
+    }
+
+    /// Updates the single clip indicator of a mono meter, which any clipping
+    /// channel lights, and returns whether it is lit.
+    fn clip_any(&self, now: Instant) -> bool {
+        self.update_clip(0, self.take_clip(None), now)
+    }
+
+    fn update_clip(
+        &self,
+        indicator: usize,
+        clipped: bool,
+        now: Instant,
+    ) -> bool {
+        self.node.peak_hold.clip(
+            indicator,
+            clipped,
+            Duration::from_secs_f32(self.config.meter.clip_ms / 1000.0),
+            now,
+        )
+    }
+
+    /// Returns the number of channels to meter.
+    fn channel_count(&self, peaks: Option<&[f32]>) -> usize {
+        match peaks {
+            Some(peaks) => peaks.len(),
+            None => self.node.positions.as_ref().map_or(0, Vec::len),
+        }
+    }
+
+    /// Returns the channel indexes in speaker order with their labels, or
+    /// None if the channels don't get separate meters.
+    fn channel_order(&self, count: usize) -> Option<Vec<(usize, String)>> {
+        (self.peaks == Peaks::Channels && count > 1).then(|| {
+            meter::channel_order(
+                self.node.positions.as_deref(),
+                self.node.channel_names.as_deref(),
+                count,
+            )
+        })
+    }
+
+    /// Builds a labeled column for each channel in order.
+    fn channel_columns<'b>(
+        &self,
+        order: &'b [(usize, String)],
+        peaks: Option<&[f32]>,
+        now: Instant,
+    ) -> Vec<meter::Column<'b>> {
+        order
+            .iter()
+            .map(|(channel, label)| {
+                let label = Some(label.as_str());
+                match peaks.and_then(|peaks| peaks.get(*channel)) {
+                    Some(&peak) => meter::Column {
+                        peak,
+                        hold: self.hold(*channel, peak, now),
+                        clipped: self.clip(*channel, now),
+                        label,
+                    },
+                    None => meter::Column {
+                        label,
+                        ..Default::default()
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn render_horizontal(&self, area: Rect, buf: &mut Buffer, now: Instant) {
+        let peaks = self.peaks();
+        if let Some(channel) = self.channel {
+            let peak = peaks.as_deref().and_then(|peaks| peaks.get(channel));
+            let hold = peak.and_then(|&peak| self.hold(channel, peak, now));
+            let clipped = peak.is_some() && self.clip(channel, now);
+            meter::render_mono(
+                area,
+                buf,
+                peak.copied(),
+                hold,
+                clipped,
+                self.config,
+            );
+            return;
+        }
+        if let Some(order) =
+            self.channel_order(self.channel_count(peaks.as_deref()))
+        {
+            let columns = self.channel_columns(&order, peaks.as_deref(), now);
+            let live = peaks.is_some_and(|peaks| !peaks.is_empty());
+            meter::render_channels(area, buf, &columns, live, self.config);
+            return;
+        }
+
+        match peaks.as_deref() {
+            Some(&[left, right]) if self.peaks != Peaks::Mono => {
+                let holds =
+                    self.hold(0, left, now).zip(self.hold(1, right, now));
+                let clips = (self.clip(0, now), self.clip(1, now));
                 meter::render_stereo(
                     area,
                     buf,
-                    Some((left.load(), right.load())),
+                    Some((left, right)),
+                    holds,
+                    clips,
                     self.config,
                 )
             }
             Some(peaks @ [..]) => {
-                let peaks = (!peaks.is_empty()).then_some(
-                    peaks.iter().map(|peak| peak.load()).sum::<f32>()
-                        / peaks.len() as f32,
-                );
-                meter::render_mono(area, buf, peaks, self.config)
+                // Any clipping channel lights the indicator, not just the mean
+                let clipped = self.clip_any(now);
+                let peaks = (!peaks.is_empty())
+                    .then_some(peaks.iter().sum::<f32>() / peaks.len() as f32);
+                let hold = peaks.and_then(|peak| self.hold(0, peak, now));
+                meter::render_mono(area, buf, peaks, hold, clipped, self.config)
+            }
+            _ => match self
+                .node
+                .positions
+                .as_ref()
+                .map(|positions| positions.len())
+            {
+                Some(2) if self.peaks != Peaks::Mono => meter::render_stereo(
+                    area,
+                    buf,
+                    None,
+                    None,
+                    (false, false),
+                    self.config,
+                ),
+                _ => meter::render_mono(
+                    area,
+                    buf,
+                    None,
+                    None,
+                    false,
+                    self.config,
+                ),
+            },
+        }
+    }
+
+    fn render_vertical(&self, area: Rect, buf: &mut Buffer, now: Instant) {
+        let column = |channel, peak, clipped| meter::Column {
+            peak,
+            hold: self.hold(channel, peak, now),
+            clipped,
+            label: None,
+        };
+        let peaks = self.peaks();
+        let order = self.channel_order(self.channel_count(peaks.as_deref()));
+        let columns = match (&order, peaks.as_deref()) {
+            (Some(order), peaks) => self.channel_columns(order, peaks, now),
+            (_, Some(&[left, right])) if self.peaks != Peaks::Mono => {
+                vec![
+                    column(0, left, self.clip(0, now)),
+                    column(1, right, self.clip(1, now)),
+                ]
+            }
+            (_, Some(peaks @ [_, ..])) => {
+                let mean = peaks.iter().sum::<f32>() / peaks.len() as f32;
+                vec![column(0, mean, self.clip_any(now))]
             }
             _ => match self
                 .node
@@ -500,13 +1310,75 @@ impl Widget for MeterWidget<'_> {
                 .as_ref()
                 .map(|positions| positions.len())
             {
-                Some(2) if self.config.peaks != Peaks::Mono => {
-                    meter::render_stereo(area, buf, None, self.config)
+                Some(2) if self.peaks != Peaks::Mono => {
+                    vec![meter::Column::default(); 2]
                 }
-                _ => meter::render_mono(area, buf, None, self.config),
+                _ => vec![meter::Column::default()],
             },
+        };
+        let live = peaks.is_some_and(|peaks| !peaks.is_empty());
+        meter::render_vertical(area, buf, &columns, live, self.config);
+    }
+}
+
+impl Widget for MeterWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let now = Instant::now();
+
+        // Record new peaks for the history sparkline.
+        let history = self.config.meter.history;
+        let dirty = self.node.peaks_dirty.load(Ordering::Relaxed)
+            || self.node.group.as_ref().is_some_and(|group| {
+                group
+                    .peaks_dirty
+                    .iter()
+                    .any(|peaks_dirty| peaks_dirty.load(Ordering::Relaxed))
+            });
+        if history > 0 && dirty {
+            if let Some(peaks) = self.peaks() {
+                self.node.peak_history.push(&peaks, history);
+            }
+        }
+
+        match self.orientation {
+            MeterOrientation::Horizontal => {
+                self.render_horizontal(area, buf, now)
+            }
+            MeterOrientation::Vertical => self.render_vertical(area, buf, now),
         }
 
         self.node.peaks_dirty.store(false, Ordering::Relaxed);
+        if let Some(group) = &self.node.group {
+            for peaks_dirty in &group.peaks_dirty {
+                peaks_dirty.store(false, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Formats peaks as dBFS, with silence as -inf.
+fn format_dbfs(peaks: &[f32]) -> String {
+    let values: Vec<String> = peaks
+        .iter()
+        .map(|&peak| {
+            let db = view::volume_to_decibels(peak);
+            if db.is_finite() {
+                format!("{db:.1}")
+            } else {
+                String::from("-inf")
+            }
+        })
+        .collect();
+    format!("{} dBFS", values.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_dbfs_floors_silence() {
+        assert_eq!(format_dbfs(&[1.0, 0.5]), "0.0, -6.0 dBFS");
+        assert_eq!(format_dbfs(&[0.0]), "-inf dBFS");
     }
 }
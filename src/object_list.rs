@@ -9,18 +9,121 @@ use ratatui::{
     widgets::{ListState, StatefulWidget, Widget},
 };
 
-use crossterm::event::{MouseButton, MouseEventKind};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind,
+};
 use smallvec::smallvec;
 
 use crate::app::{Action, MouseArea};
-use crate::config::Config;
+use crate::card_widget::CardWidget;
+use crate::config::{Config, MeterOrientation, NodeLayout, Peaks, VolumeUnit};
 use crate::device_kind::DeviceKind;
 use crate::device_widget::DeviceWidget;
 use crate::dropdown_widget::DropdownWidget;
-use crate::node_widget::NodeWidget;
+use crate::history_widget::HistoryWidget;
+use crate::meter;
+use crate::mute_memory::MuteMemory;
+use crate::node_widget::{FocusWidget, NodeWidget};
+use crate::target_history::TargetHistory;
 use crate::view::{self, ListKind, VolumeAdjustment};
 use crate::wirehose::ObjectId;
 
+/// Returns a node's channel indexes in the order the focus view shows them,
+/// each with a label.
+fn focus_order(node: &view::Node) -> Vec<(usize, String)> {
+    meter::channel_order(
+        node.positions.as_deref(),
+        node.channel_names.as_deref(),
+        node.volumes.len(),
+    )
+}
+
+/// Changes the volume of a node, or of all members if it represents a group.
+fn node_volume(
+    view: &view::View,
+    node_id: ObjectId,
+    adjustment: VolumeAdjustment,
+    max: Option<f32>,
+) -> bool {
+    match view.nodes.get(&node_id) {
+        Some(node) if node.group.is_some() => {
+            view.group_volume(node_id, adjustment, max)
+        }
+        _ => view.volume(node_id, adjustment, max),
+    }
+}
+
+/// Returns the nodes of a kind for bulk changes. Groups stand in for their
+/// members, which aren't listed themselves.
+fn bulk_nodes(view: &view::View, node_kind: view::NodeKind) -> Vec<ObjectId> {
+    view.full_nodes(node_kind, None)
+        .into_iter()
+        .flat_map(|node| match &node.group {
+            Some(group) => group.members.clone(),
+            None => vec![node.object_id],
+        })
+        .collect()
+}
+
+/// Outcome of [`ObjectList::normalize()`]
+#[derive(Debug, PartialEq)]
+pub enum Normalize {
+    /// There are fewer than two nodes to level
+    Nothing,
+    /// Calling again will set count nodes to volume
+    Preview { volume: f32, count: usize },
+    /// The volumes were requested
+    Applied,
+}
+
+/// A raw metadata property to set with the selected object as the subject.
+#[derive(Debug, PartialEq)]
+struct MetadataProperty {
+    key: String,
+    type_: Option<String>,
+    /// None removes the property
+    value: Option<String>,
+}
+
+impl MetadataProperty {
+    /// Parses key=value or key:type=value. An empty value removes the key.
+    fn parse(input: &str) -> Option<Self> {
+        let (key, value) = input.split_once('=')?;
+        let (key, type_) = match key.split_once(':') {
+            Some((key, type_)) => (key, Some(String::from(type_))),
+            None => (key, None),
+        };
+        if key.is_empty() {
+            return None;
+        }
+        let (type_, value) = if value.is_empty() {
+            (None, None)
+        } else {
+            (type_, Some(String::from(value)))
+        };
+        Some(Self {
+            key: String::from(key),
+            type_,
+            value,
+        })
+    }
+
+    /// Describes exactly what will be sent.
+    fn prompt(&self, view: &view::View, subject: ObjectId) -> String {
+        let metadata = view
+            .metadata_id
+            .map_or(String::from("none"), |id| id.to_string());
+        format!(
+            "Set metadata {metadata} subject {} key {:?} type {:?} value {:?}? \
+             (y/n)",
+            subject.to_string(),
+            self.key,
+            self.type_,
+            self.value,
+        )
+    }
+}
+
 /// ObjectList stores information for filtering and displaying a subset of
 /// objects from a [`View`](`crate::view::View`).
 ///
@@ -37,8 +140,56 @@ pub struct ObjectList {
     device_kind: Option<DeviceKind>,
     /// Target dropdown state
     pub dropdown_state: ListState,
-    /// Targets
-    pub targets: Vec<(view::Target, String)>,
+    /// Targets, their titles, and whether they are available
+    pub targets: Vec<(view::Target, String, bool)>,
+    /// Text typed to narrow down the targets in the dropdown
+    pub dropdown_filter: String,
+    /// Whether typed characters go to dropdown_filter rather than to
+    /// keybindings
+    dropdown_filtering: bool,
+    /// Profile change waiting for confirmation in the dropdown, and the
+    /// profile's title
+    pub confirm_target: Option<(view::Target, String)>,
+    /// Whether profile changes need to be confirmed
+    pub confirm_profile_change: bool,
+    /// Whether muting a device needs to be confirmed by muting again
+    pub confirm_device_mute: bool,
+    /// Device mute waiting for confirmation by [`Self::toggle_mute()`]
+    mute_pending: bool,
+    /// Whether to show the selected node's peak history
+    pub show_history: bool,
+    /// Whether devices are shown as cards with a row for each active route
+    pub cards: bool,
+    /// Position of the selected route within the selected card
+    route: usize,
+    /// Nodes showing a volume bar for each channel
+    expanded: HashSet<ObjectId>,
+    /// Node shown on its own with a fader for each channel, and the index of
+    /// the channel being adjusted
+    focused: Option<(ObjectId, usize)>,
+    /// Nodes whose balance adjustments move volume between sides instead of
+    /// keeping the louder side fixed
+    unlinked: HashSet<ObjectId>,
+    /// Nodes muted by [`Self::mute_all()`], to be unmuted by the next call
+    bulk_muted: HashSet<ObjectId>,
+    /// Volume previewed by [`Self::normalize()`], applied by the next call
+    normalize_pending: Option<f32>,
+    /// Query for filtering objects by title
+    search: Option<String>,
+    /// Whether key presses are going to the search query
+    search_editing: bool,
+    /// Volume percentage being typed for the selected node
+    volume_input: Option<String>,
+    /// Raw metadata property being typed for the selected object
+    metadata_input: Option<String>,
+    /// Raw metadata property waiting for confirmation
+    metadata_pending: Option<MetadataProperty>,
+    /// Determines the height of nodes
+    pub meter_orientation: MeterOrientation,
+    /// Determines the height of nodes and the spacing between them
+    pub node_layout: NodeLayout,
+    /// Peak meter mode for this list
+    pub peaks: Peaks,
 }
 
 impl ObjectList {
@@ -55,8 +206,9 @@ impl ObjectList {
     pub fn down(&mut self, view: &view::View) {
         if self.dropdown_state.selected().is_some() {
             self.dropdown_state.select_next();
-        } else {
-            let new_selected = view.next_id(self.list_kind, self.selected);
+        } else if !self.move_route(view, 1) {
+            let new_selected =
+                view.next_id(self.list_kind, self.search(), self.selected);
             if new_selected.is_some() {
                 self.select(new_selected);
             }
@@ -66,11 +218,54 @@ impl ObjectList {
     pub fn up(&mut self, view: &view::View) {
         if self.dropdown_state.selected().is_some() {
             self.dropdown_state.select_previous();
-        } else {
-            let new_selected = view.previous_id(self.list_kind, self.selected);
+        } else if !self.move_route(view, -1) {
+            let new_selected =
+                view.previous_id(self.list_kind, self.search(), self.selected);
             if new_selected.is_some() {
                 self.select(new_selected);
+                // Enter the card from its last route.
+                self.route = self.route_count(view).saturating_sub(1);
+            }
+        }
+    }
+
+    /// Returns the number of routes in the selected card, or 0 if devices
+    /// aren't shown as cards.
+    fn route_count(&self, view: &view::View) -> usize {
+        if !self.cards {
+            return 0;
+        }
+        self.selected
+            .and_then(|device_id| view.devices.get(&device_id))
+            .map_or(0, |device| device.routes.len())
+    }
+
+    /// Moves the selection between the routes of the selected card. Returns
+    /// false if there is no route in that direction.
+    fn move_route(&mut self, view: &view::View, offset: isize) -> bool {
+        let count = self.route_count(view);
+        match self
+            .route
+            .checked_add_signed(offset)
+            .filter(|&route| route < count)
+        {
+            Some(route) => {
+                self.route = route;
+                true
             }
+            None => false,
+        }
+    }
+
+    /// Returns the position of the selected route within the selected card.
+    pub fn selected_route(&self) -> Option<usize> {
+        self.cards.then_some(self.route)
+    }
+
+    /// Selects the route at position within the selected card.
+    pub fn select_route(&mut self, route: usize) {
+        if self.cards {
+            self.route = route;
         }
     }
 
@@ -86,16 +281,53 @@ impl ObjectList {
         if let Some((targets, index)) = targets {
             if !targets.is_empty() {
                 self.targets = targets;
+                self.dropdown_filter.clear();
+                self.dropdown_filtering = false;
                 self.dropdown_state.select(Some(index));
             }
         }
     }
 
-    fn selected_target(&self) -> Option<&view::Target> {
+    /// Returns the targets with titles containing the dropdown filter.
+    pub fn filtered_targets(
+        &self,
+    ) -> impl Iterator<Item = &(view::Target, String, bool)> {
+        let filter = self.dropdown_filter.to_lowercase();
+        self.targets
+            .iter()
+            .filter(move |(_, title, _)| title.to_lowercase().contains(&filter))
+    }
+
+    fn selected_target(&self) -> Option<&(view::Target, String, bool)> {
         self.dropdown_state
             .selected()
-            .and_then(|index| self.targets.get(index))
-            .map(|(target, _)| target)
+            .and_then(|index| self.filtered_targets().nth(index))
+    }
+
+    /// Returns the titles of the dropdown items. While a profile change is
+    /// being confirmed, these are the choices to switch or cancel.
+    pub fn dropdown_items(&self) -> Vec<String> {
+        match &self.confirm_target {
+            Some((_, title)) => {
+                vec![format!("Switch to {title}"), String::from("Cancel")]
+            }
+            None => self
+                .filtered_targets()
+                .map(|(_, title, _)| title.clone())
+                .collect(),
+        }
+    }
+
+    fn needs_confirmation(&self, target: &view::Target) -> bool {
+        self.confirm_profile_change
+            && matches!(target, view::Target::Profile(..))
+    }
+
+    /// Replaces the dropdown items with a confirmation for target. Cancel is
+    /// selected so that a repeated key press doesn't confirm by accident.
+    fn confirm(&mut self, target: view::Target, title: String) {
+        self.confirm_target = Some((target, title));
+        self.dropdown_state = ListState::default().with_selected(Some(1));
     }
 
     pub fn dropdown_activate(&mut self, view: &view::View) {
@@ -105,33 +337,343 @@ impl ObjectList {
             return;
         }
 
-        if let (Some(object_id), Some(&target)) =
-            (self.selected, self.selected_target())
+        if let Some((target, _)) = self.confirm_target.take() {
+            // The first item confirms and the second cancels.
+            if let (Some(object_id), Some(0)) =
+                (self.selected, self.dropdown_state.selected())
+            {
+                view.set_target(object_id, target);
+            }
+        } else if let (Some(object_id), Some((target, title, _))) =
+            (self.selected, self.selected_target().cloned())
         {
+            if self.needs_confirmation(&target) {
+                self.confirm(target, title);
+                return;
+            }
             view.set_target(object_id, target);
         };
 
-        self.dropdown_state.select(None);
+        self.dropdown_close();
     }
 
     pub fn dropdown_close(&mut self) {
         self.dropdown_state.select(None);
+        self.confirm_target = None;
+        self.dropdown_filter.clear();
+        self.dropdown_filtering = false;
+    }
+
+    pub fn is_dropdown_open(&self) -> bool {
+        self.dropdown_state.selected().is_some()
+    }
+
+    /// Handles a key press while filtering the open target dropdown. Typed
+    /// characters narrow down the targets and select the first match. Enter
+    /// keeps the filter and returns to normal key handling, and Esc clears
+    /// it. Returns false if the key isn't used so that it can be handled
+    /// normally.
+    pub fn dropdown_input(&mut self, key: KeyEvent) -> bool {
+        if !key.modifiers.difference(KeyModifiers::SHIFT).is_empty()
+            || !self.dropdown_filtering
+            || self.confirm_target.is_some()
+        {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(c) => self.dropdown_filter.push(c),
+            KeyCode::Backspace => {
+                self.dropdown_filter.pop();
+            }
+            KeyCode::Enter => {
+                self.dropdown_filtering = false;
+                return true;
+            }
+            KeyCode::Esc => {
+                self.dropdown_filtering = false;
+                self.dropdown_filter.clear();
+            }
+            _ => return false,
+        }
+        self.dropdown_state = ListState::default().with_selected(Some(0));
+        true
     }
 
+    /// Sets the target of the selected object. Profile changes picked from
+    /// the dropdown are confirmed first if configured. Setting the target
+    /// being confirmed counts as confirmation.
     pub fn set_target(&mut self, view: &view::View, target: view::Target) {
-        self.dropdown_state.select(None);
+        let confirmed = self.confirm_target.take().is_some();
+        if !confirmed && self.needs_confirmation(&target) {
+            let title = self
+                .targets
+                .iter()
+                .find(|(t, ..)| *t == target)
+                .map(|(_, title, _)| title.clone())
+                .unwrap_or_default();
+            self.confirm(target, title);
+            return;
+        }
+
+        self.dropdown_close();
         if let Some(object_id) = self.selected {
             view.set_target(object_id, target);
         };
     }
 
-    pub fn toggle_mute(&mut self, view: &view::View) {
+    /// Switch the selected node back to the target it had before its current
+    /// one.
+    pub fn toggle_target(
+        &mut self,
+        view: &view::View,
+        target_history: &mut TargetHistory,
+    ) -> bool {
         if matches!(self.list_kind, ListKind::Device) {
-            return;
+            return false;
         }
-        if let Some(node_id) = self.selected {
-            view.mute(node_id);
+        let Some(node) = self.selected.and_then(|id| view.nodes.get(&id))
+        else {
+            return false;
+        };
+        let Some(previous) = target_history.previous(&node.name) else {
+            return false;
+        };
+        // Make sure the previous target is still available.
+        let is_available =
+            view.node_targets(node.object_id)
+                .is_some_and(|(targets, _)| {
+                    targets.iter().any(|&(target, ..)| target == previous)
+                });
+        if !is_available {
+            return false;
+        }
+
+        self.dropdown_close();
+        view.set_target(node.object_id, previous);
+        target_history.record(&node.name, previous);
+
+        true
+    }
+
+    /// Returns the target after the selected node's current one, or before
+    /// it if not forward, wrapping around. Returns None if that would leave
+    /// the target unchanged.
+    fn adjacent_target(
+        &self,
+        view: &view::View,
+        forward: bool,
+        include_default: bool,
+    ) -> Option<view::Target> {
+        if matches!(self.list_kind, ListKind::Device) {
+            return None;
+        }
+        let node = self.selected.and_then(|id| view.nodes.get(&id))?;
+        let (targets, _) = view.node_targets(node.object_id)?;
+        let targets: Vec<view::Target> = targets
+            .into_iter()
+            .map(|(target, ..)| target)
+            .filter(|&target| {
+                include_default || target != view::Target::Default
+            })
+            .collect();
+        let len = targets.len();
+        if len == 0 {
+            return None;
+        }
+
+        let current = node
+            .target
+            .and_then(|current| targets.iter().position(|&t| t == current));
+        let index = match (current, forward) {
+            (Some(index), true) => (index + 1) % len,
+            (Some(index), false) => (index + len - 1) % len,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        };
+        let target = targets[index];
+
+        (Some(target) != node.target).then_some(target)
+    }
+
+    /// Switch the selected node to the next target, or the previous one if
+    /// not forward. The "Default" target is skipped unless include_default is
+    /// set.
+    pub fn cycle_target(
+        &mut self,
+        view: &view::View,
+        forward: bool,
+        include_default: bool,
+    ) -> bool {
+        let Some(object_id) = self.selected else {
+            return false;
+        };
+        let Some(target) = self.adjacent_target(view, forward, include_default)
+        else {
+            return false;
+        };
+
+        self.dropdown_close();
+        view.set_target(object_id, target);
+
+        true
+    }
+
+    /// Forgets a device mute waiting for confirmation.
+    pub fn cancel_mute(&mut self) {
+        self.mute_pending = false;
+    }
+
+    /// Toggles mute on the selected object. Returns false if muting a device
+    /// is waiting to be confirmed by calling again.
+    pub fn toggle_mute(
+        &mut self,
+        view: &view::View,
+        mute_memory: &mut MuteMemory,
+    ) -> bool {
+        let pending = std::mem::take(&mut self.mute_pending);
+        if matches!(self.list_kind, ListKind::Device) {
+            let Some(device) =
+                self.selected.and_then(|id| view.devices.get(&id))
+            else {
+                return true;
+            };
+            // Devices mute their active routes, and cards the selected one.
+            let route = self.selected_route();
+            let mutes = match route {
+                Some(route) => {
+                    device.routes.get(route).is_some_and(|route| !route.mute)
+                }
+                None => !device.routes.is_empty() && !device.is_muted(),
+            };
+            if self.confirm_device_mute && mutes && !pending {
+                self.mute_pending = true;
+                return false;
+            }
+            match route {
+                Some(route) => view.route_mute(device.object_id, route),
+                None => view.device_mute(device.object_id),
+            };
+            return true;
+        }
+        let Some(node) = self.selected.and_then(|id| view.nodes.get(&id))
+        else {
+            return true;
+        };
+        if let Some(group) = &node.group {
+            // Remember each member's volumes like an individual stream.
+            let members =
+                group.members.iter().filter_map(|id| view.nodes.get(id));
+            for member in members {
+                if group.mute {
+                    if let Some(volumes) = mute_memory.take(member.object_id) {
+                        view.set_volumes(member.object_id, volumes);
+                    }
+                } else if !member.mute {
+                    mute_memory
+                        .record(member.object_id, member.volumes.clone());
+                }
+            }
+            view.group_mute(node.object_id);
+            return true;
+        }
+        if node.mute {
+            // Restore the volume from before muting
+            if let Some(volumes) = mute_memory.take(node.object_id) {
+                view.set_volumes(node.object_id, volumes);
+            }
+        } else {
+            mute_memory.record(node.object_id, node.volumes.clone());
+        }
+        view.mute(node.object_id);
+        true
+    }
+
+    /// Mutes every unmuted node in the list, or unmutes the nodes muted by
+    /// the previous call. Does nothing for devices. Returns true if anything
+    /// was changed.
+    pub fn mute_all(
+        &mut self,
+        view: &view::View,
+        mute_memory: &mut MuteMemory,
+    ) -> bool {
+        let ListKind::Node(node_kind) = self.list_kind else {
+            return false;
+        };
+
+        if !self.bulk_muted.is_empty() {
+            // Leave alone anything that was unmuted in the meantime
+            for node_id in self.bulk_muted.drain() {
+                let Some(node) = view.nodes.get(&node_id) else {
+                    continue;
+                };
+                if node.mute {
+                    if let Some(volumes) = mute_memory.take(node_id) {
+                        view.set_volumes(node_id, volumes);
+                    }
+                    view.mute(node_id);
+                }
+            }
+            return true;
+        }
+
+        for node_id in bulk_nodes(view, node_kind) {
+            let Some(node) = view.nodes.get(&node_id) else {
+                continue;
+            };
+            if !node.mute && self.bulk_muted.insert(node_id) {
+                mute_memory.record(node_id, node.volumes.clone());
+                view.mute(node_id);
+            }
+        }
+
+        !self.bulk_muted.is_empty()
+    }
+
+    /// Sets every unlocked node in the list to their average volume, limited
+    /// to max. The first call only previews the volume. Calling again while
+    /// the outcome is the same applies it.
+    pub fn normalize(
+        &mut self,
+        view: &view::View,
+        max: Option<f32>,
+    ) -> Normalize {
+        let ListKind::Node(node_kind) = self.list_kind else {
+            return Normalize::Nothing;
+        };
+
+        let nodes: Vec<(ObjectId, f32)> = bulk_nodes(view, node_kind)
+            .into_iter()
+            .filter_map(|node_id| {
+                let node = view.nodes.get(&node_id)?;
+                if node.locked || node.volumes.is_empty() {
+                    return None;
+                }
+                let volumes = &node.volumes;
+                let volume = volumes.iter().sum::<f32>() / volumes.len() as f32;
+                Some((node_id, volume.cbrt()))
+            })
+            .collect();
+        if nodes.len() < 2 {
+            self.normalize_pending = None;
+            return Normalize::Nothing;
         }
+        let mut volume = nodes.iter().map(|(_, volume)| volume).sum::<f32>()
+            / nodes.len() as f32;
+        if let Some(max) = max {
+            volume = volume.min(max / 100.0);
+        }
+
+        if self.normalize_pending.take() != Some(volume) {
+            self.normalize_pending = Some(volume);
+            return Normalize::Preview {
+                volume,
+                count: nodes.len(),
+            };
+        }
+        for (node_id, _) in nodes {
+            view.volume(node_id, VolumeAdjustment::Absolute(volume), max);
+        }
+        Normalize::Applied
     }
 
     pub fn set_absolute_volume(
@@ -140,24 +682,104 @@ impl ObjectList {
         volume: f32,
         max: Option<f32>,
     ) -> bool {
-        if matches!(self.list_kind, ListKind::Device) {
+        let adjustment = VolumeAdjustment::Absolute(volume);
+        match (self.list_kind, self.selected) {
+            (ListKind::Device, Some(device_id)) if self.cards => {
+                view.route_volume(device_id, self.route, adjustment, max)
+            }
+            (ListKind::Device, Some(device_id)) => {
+                view.device_volume(device_id, adjustment, max)
+            }
+            (_, Some(node_id)) => node_volume(view, node_id, adjustment, max),
+            (_, None) => false,
+        }
+    }
+
+    /// Sets the selected object's volume to 100%. If unmute is set, a muted
+    /// object is unmuted too. Otherwise, the mute state is left alone.
+    pub fn reset_volume(
+        &mut self,
+        view: &view::View,
+        max: Option<f32>,
+        unmute: bool,
+    ) -> bool {
+        if !self.set_absolute_volume(view, 1.0, max) {
             return false;
         }
-        if let Some(node_id) = self.selected {
-            return view.volume(
-                node_id,
-                VolumeAdjustment::Absolute(volume),
-                max,
-            );
+        if !unmute {
+            return true;
         }
-        false
+
+        match (self.list_kind, self.selected) {
+            (ListKind::Device, Some(device_id)) if self.cards => {
+                if view
+                    .devices
+                    .get(&device_id)
+                    .and_then(|device| device.routes.get(self.route))
+                    .is_some_and(|route| route.mute)
+                {
+                    view.route_mute(device_id, self.route);
+                }
+            }
+            (ListKind::Device, Some(device_id)) => {
+                if view
+                    .devices
+                    .get(&device_id)
+                    .is_some_and(|device| device.is_muted())
+                {
+                    view.device_mute(device_id);
+                }
+            }
+            (_, Some(node_id)) => match view.nodes.get(&node_id) {
+                Some(view::Node {
+                    group: Some(group), ..
+                }) if group.mute => {
+                    view.group_mute(node_id);
+                }
+                Some(node) if node.group.is_none() && node.mute => {
+                    view.mute(node_id);
+                }
+                _ => {}
+            },
+            (_, None) => {}
+        }
+
+        true
     }
 
+    /// Adjusts the volume of the selected object. In decibel mode, volume is
+    /// scaled so that a step of 0.01 is 1 dB.
     pub fn set_relative_volume(
         &mut self,
         view: &view::View,
         volume: f32,
         max: Option<f32>,
+        unit: VolumeUnit,
+    ) -> bool {
+        let adjustment = match unit {
+            VolumeUnit::Percent => VolumeAdjustment::Relative(volume),
+            VolumeUnit::Db => {
+                VolumeAdjustment::RelativeDecibels(volume * 100.0)
+            }
+        };
+        match (self.list_kind, self.selected) {
+            (ListKind::Device, Some(device_id)) if self.cards => {
+                view.route_volume(device_id, self.route, adjustment, max)
+            }
+            (ListKind::Device, Some(device_id)) => {
+                view.device_volume(device_id, adjustment, max)
+            }
+            (_, Some(node_id)) => node_volume(view, node_id, adjustment, max),
+            (_, None) => false,
+        }
+    }
+
+    pub fn set_channel_volume(
+        &mut self,
+        view: &view::View,
+        index: usize,
+        volume: f32,
+        max: Option<f32>,
     ) -> bool {
         if matches!(self.list_kind, ListKind::Device) {
             return false;
@@ -165,60 +787,495 @@ impl ObjectList {
         if let Some(node_id) = self.selected {
             return view.volume(
                 node_id,
-                VolumeAdjustment::Relative(volume),
+                VolumeAdjustment::Channel {
+                    index,
+                    value: volume,
+                },
                 max,
             );
         }
         false
     }
 
-    pub fn set_default(&mut self, view: &view::View) {
+    /// Show or hide per-channel volume bars for the selected node.
+    pub fn toggle_expanded(&mut self) {
         if matches!(self.list_kind, ListKind::Device) {
             return;
         }
-        if let (Some(node_id), Some(device_kind)) =
-            (self.selected, self.device_kind)
+        if let Some(node_id) = self.selected {
+            if !self.expanded.remove(&node_id) {
+                self.expanded.insert(node_id);
+            }
+        }
+    }
+
+    /// Shows the selected node on its own with a volume bar and peak meter
+    /// for each channel, or returns to the list.
+    pub fn toggle_focus(&mut self, view: &view::View) {
+        if self.focused.take().is_some()
+            || matches!(self.list_kind, ListKind::Device)
         {
-            view.set_default(node_id, device_kind);
+            return;
+        }
+        // Groups don't have channels of their own.
+        let Some(node) = self
+            .selected
+            .and_then(|node_id| view.nodes.get(&node_id))
+            .filter(|node| node.group.is_none())
+        else {
+            return;
+        };
+        if let Some(&(index, _)) = focus_order(node).first() {
+            self.focused = Some((node.object_id, index));
         }
     }
 
-    fn selected_index(&self, view: &view::View) -> Option<usize> {
-        self.selected
-            .and_then(|selected| view.position(self.list_kind, selected))
+    pub fn is_focused(&self) -> bool {
+        self.focused.is_some()
     }
 
-    fn select(&mut self, object_id: Option<ObjectId>) {
-        self.selected = object_id;
-        // Close the dropdown in case it is open for the previously-selected
-        // object. This can happen when the object is removed from PipeWire
-        // while the dropdown is open.
-        self.dropdown_close();
+    /// Returns the index of the focused channel.
+    pub fn focused_channel(&self) -> Option<usize> {
+        self.focused.map(|(_, index)| index)
     }
 
-    /// Returns a set of object IDs of the visible objects. This includes all
-    /// dependencies that affect the display of the objects.
-    pub fn visible_objects(
-        &self,
-        area: &Rect,
-        view: &view::View,
-    ) -> HashSet<ObjectId> {
-        let objects = view.object_ids(self.list_kind);
+    /// Moves the focus to the next channel in the order they are shown.
+    pub fn focus_down(&mut self, view: &view::View) {
+        self.move_focus(view, 1);
+    }
 
-        let last = cmp::min(objects.len(), self.top + self.visible_count(area));
+    /// Moves the focus to the previous channel in the order they are shown.
+    pub fn focus_up(&mut self, view: &view::View) {
+        self.move_focus(view, -1);
+    }
 
-        // Always include object 0 - the global PipeWire state.
-        let mut visible_objects = HashSet::from([ObjectId::from_raw_id(0)]);
+    fn move_focus(&mut self, view: &view::View, offset: isize) {
+        let Some((node_id, index)) = self.focused else {
+            return;
+        };
+        let Some(node) = view.nodes.get(&node_id) else {
+            return;
+        };
+        let order = focus_order(node);
+        let Some(position) = order.iter().position(|&(i, _)| i == index) else {
+            return;
+        };
+        if let Some(&(index, _)) = position
+            .checked_add_signed(offset)
+            .and_then(|position| order.get(position))
+        {
+            self.focused = Some((node_id, index));
+        }
+    }
 
-        for object_id in objects[self.top..last].iter().cloned() {
-            visible_objects.insert(object_id);
-            if let Some(node) = view.nodes.get(&object_id) {
-                // Add linked client and device.
-                visible_objects.extend(node.client_id);
-                visible_objects.extend(node.device_info.map(|(id, _, _)| id));
+    /// Adjusts the volume of the focused channel. In decibel mode, volume is
+    /// scaled so that a step of 0.01 is 1 dB.
+    pub fn set_focused_relative_volume(
+        &mut self,
+        view: &view::View,
+        delta: f32,
+        max: Option<f32>,
+        unit: VolumeUnit,
+    ) -> bool {
+        let Some((node_id, index)) = self.focused else {
+            return false;
+        };
+        let Some(&volume) = view
+            .nodes
+            .get(&node_id)
+            .and_then(|node| node.volumes.get(index))
+        else {
+            return false;
+        };
+        let value = match unit {
+            VolumeUnit::Percent => volume.cbrt() + delta,
+            VolumeUnit::Db => {
+                let db = view::volume_to_decibels(volume)
+                    .max(view::MIN_DECIBELS)
+                    + delta * 100.0;
+                if db <= view::MIN_DECIBELS {
+                    0.0
+                } else {
+                    view::decibels_to_volume(db).cbrt()
+                }
+            }
+        };
+        view.volume(node_id, VolumeAdjustment::Channel { index, value }, max)
+    }
 
-                // Add the target and any linked client and device.
-                if let ListKind::Node(node_kind) = self.list_kind {
+    /// Shifts the balance of the selected node toward the right by delta.
+    pub fn set_balance(
+        &mut self,
+        view: &view::View,
+        delta: f32,
+        max: Option<f32>,
+    ) -> bool {
+        if matches!(self.list_kind, ListKind::Device) {
+            return false;
+        }
+        let Some(node) = self.selected.and_then(|id| view.nodes.get(&id))
+        else {
+            return false;
+        };
+        if node.group.is_some() {
+            // Groups don't have channels of their own.
+            return false;
+        }
+        let linked = !self.unlinked.contains(&node.object_id);
+        view.balance(node.object_id, delta, linked, max)
+    }
+
+    /// Evens out the balance of the selected node.
+    pub fn center_balance(
+        &mut self,
+        view: &view::View,
+        max: Option<f32>,
+    ) -> bool {
+        if matches!(self.list_kind, ListKind::Device) {
+            return false;
+        }
+        let Some(node) = self.selected.and_then(|id| view.nodes.get(&id))
+        else {
+            return false;
+        };
+        if node.group.is_some() {
+            return false;
+        }
+        let linked = !self.unlinked.contains(&node.object_id);
+        view.center_balance(node.object_id, linked, max)
+    }
+
+    pub fn toggle_balance_link(&mut self) {
+        if matches!(self.list_kind, ListKind::Device) {
+            return;
+        }
+        if let Some(node_id) = self.selected {
+            if !self.unlinked.remove(&node_id) {
+                self.unlinked.insert(node_id);
+            }
+        }
+    }
+
+    pub fn set_default(&mut self, view: &view::View) {
+        if matches!(self.list_kind, ListKind::Device) {
+            return;
+        }
+        if let (Some(node_id), Some(device_kind)) =
+            (self.selected, self.device_kind)
+        {
+            view.set_default(node_id, device_kind);
+        }
+    }
+
+    /// Makes the node after the current default sink or source the default,
+    /// wrapping around at the end of the list. The selection is left alone.
+    pub fn cycle_default(&mut self, view: &view::View) -> bool {
+        let (ListKind::Node(_), Some(device_kind)) =
+            (self.list_kind, self.device_kind)
+        else {
+            return false;
+        };
+        let current = view
+            .nodes
+            .values()
+            .find(|node| match device_kind {
+                DeviceKind::Sink => node.is_default_sink,
+                DeviceKind::Source => node.is_default_source,
+            })
+            .map(|node| node.object_id)
+            // The default might not be in this list, e.g. when filtered.
+            .filter(|&node_id| {
+                view.position(self.list_kind, self.search(), node_id)
+                    .is_some()
+            });
+        let Some(next) = view
+            .next_id(self.list_kind, self.search(), current)
+            .or_else(|| view.next_id(self.list_kind, self.search(), None))
+        else {
+            return false;
+        };
+        if Some(next) == current {
+            return false;
+        }
+        view.set_default(next, device_kind);
+        true
+    }
+
+    /// Selects the default sink or source, whichever this list has. Returns
+    /// false if it isn't in the list or is already selected.
+    pub fn select_default(&mut self, view: &view::View) -> bool {
+        let ListKind::Node(_) = self.list_kind else {
+            return false;
+        };
+        let default = view
+            .nodes
+            .values()
+            .filter(|node| match self.device_kind {
+                Some(DeviceKind::Sink) => node.is_default_sink,
+                Some(DeviceKind::Source) => node.is_default_source,
+                None => node.is_default_sink || node.is_default_source,
+            })
+            .filter_map(|node| {
+                let position = view.position(
+                    self.list_kind,
+                    self.search(),
+                    node.object_id,
+                )?;
+                Some((position, node.object_id))
+            })
+            // Lists with both defaults go to the first one.
+            .min()
+            .map(|(_, node_id)| node_id);
+        if default.is_none() || default == self.selected {
+            return false;
+        }
+        self.select(default);
+        true
+    }
+
+    /// Selects object_id if this list shows it. Returns false if it doesn't.
+    pub fn select_listed(
+        &mut self,
+        view: &view::View,
+        object_id: ObjectId,
+    ) -> bool {
+        if view
+            .position(self.list_kind, self.search(), object_id)
+            .is_none()
+        {
+            return false;
+        }
+        self.select(Some(object_id));
+        true
+    }
+
+    fn selected_index(&self, view: &view::View) -> Option<usize> {
+        self.selected.and_then(|selected| {
+            view.position(self.list_kind, self.search(), selected)
+        })
+    }
+
+    fn search(&self) -> Option<&str> {
+        self.search.as_deref()
+    }
+
+    /// Opens the search input, or starts filtering the targets if the
+    /// dropdown is open.
+    pub fn start_search(&mut self) {
+        if self.is_dropdown_open() {
+            self.dropdown_filtering = true;
+            return;
+        }
+        self.dropdown_close();
+        self.search.get_or_insert_with(String::new);
+        self.search_editing = true;
+    }
+
+    /// Clears the search query and restores the full list.
+    pub fn clear_search(&mut self) {
+        self.search = None;
+        self.search_editing = false;
+    }
+
+    pub fn is_search_editing(&self) -> bool {
+        self.search_editing
+    }
+
+    /// Handles a key press while editing the search query. Returns false if
+    /// the key isn't used for editing so that it can be handled normally.
+    pub fn search_input(&mut self, key: KeyEvent) -> bool {
+        if !key.modifiers.difference(KeyModifiers::SHIFT).is_empty() {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(c) => {
+                self.search.get_or_insert_with(String::new).push(c);
+            }
+            KeyCode::Backspace => {
+                if let Some(search) = &mut self.search {
+                    search.pop();
+                }
+            }
+            // Keep the filter but return to normal key handling.
+            KeyCode::Enter => self.search_editing = false,
+            KeyCode::Esc => self.clear_search(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Opens the volume input for the selected node.
+    pub fn start_volume_input(&mut self) {
+        if matches!(self.list_kind, ListKind::Device) || self.selected.is_none()
+        {
+            return;
+        }
+        self.dropdown_close();
+        self.volume_input = Some(String::new());
+    }
+
+    pub fn is_volume_editing(&self) -> bool {
+        self.volume_input.is_some()
+    }
+
+    /// Handles a key press while typing a volume. Enter sets the volume of the
+    /// selected node and Esc cancels. Returns false if the key isn't used so
+    /// that it can be handled normally.
+    pub fn volume_input(
+        &mut self,
+        key: KeyEvent,
+        view: &view::View,
+        max: Option<f32>,
+    ) -> bool {
+        if !key.modifiers.difference(KeyModifiers::SHIFT).is_empty() {
+            return false;
+        }
+        let Some(input) = &mut self.volume_input else {
+            return false;
+        };
+        match key.code {
+            KeyCode::Char(c)
+                if c.is_ascii_digit() || (c == '.' && !input.contains('.')) =>
+            {
+                input.push(c);
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                if let Ok(percent) = input.parse::<f32>() {
+                    self.set_absolute_volume(view, percent / 100.0, max);
+                }
+                self.volume_input = None;
+            }
+            KeyCode::Esc => self.volume_input = None,
+            // Ignore anything else so that it doesn't trigger keybindings.
+            _ => {}
+        }
+        true
+    }
+
+    /// Returns the actions to hint at in the status bar, with short
+    /// descriptions.
+    pub fn hints(&self) -> Vec<(Action, &'static str)> {
+        if self.focused.is_some() {
+            return vec![
+                (Action::ToggleMute, "mute"),
+                (Action::Focus, "back"),
+                (Action::Help, "help"),
+            ];
+        }
+        let mut hints = Vec::new();
+        if matches!(self.list_kind, ListKind::Node(_)) || self.cards {
+            hints.push((Action::ToggleMute, "mute"));
+        }
+        if self.device_kind.is_some() {
+            hints.push((Action::SetDefault, "default"));
+        }
+        let dropdown = match self.list_kind {
+            ListKind::Node(_) => "target",
+            ListKind::Device => "profile",
+        };
+        hints.push((Action::ActivateDropdown, dropdown));
+        hints.push((Action::Help, "help"));
+        hints
+    }
+
+    /// Opens the raw metadata input for the selected object.
+    pub fn start_metadata_input(&mut self) {
+        if self.selected.is_none() {
+            return;
+        }
+        self.dropdown_close();
+        self.metadata_input = Some(String::new());
+    }
+
+    pub fn is_metadata_editing(&self) -> bool {
+        self.metadata_input.is_some() || self.metadata_pending.is_some()
+    }
+
+    /// Handles a key press while typing a raw metadata property. Enter asks
+    /// for confirmation, which is given with y, and anything else cancels.
+    /// Returns false if the key isn't used so that it can be handled
+    /// normally.
+    pub fn metadata_input(&mut self, key: KeyEvent, view: &view::View) -> bool {
+        if !key.modifiers.difference(KeyModifiers::SHIFT).is_empty() {
+            return false;
+        }
+        if let Some(property) = self.metadata_pending.take() {
+            if let (KeyCode::Char('y'), Some(object_id)) =
+                (key.code, self.selected)
+            {
+                view.set_metadata(
+                    object_id,
+                    property.key,
+                    property.type_,
+                    property.value,
+                );
+            }
+            return true;
+        }
+        let Some(input) = &mut self.metadata_input else {
+            return false;
+        };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                self.metadata_pending = MetadataProperty::parse(input);
+                self.metadata_input = None;
+            }
+            KeyCode::Esc => self.metadata_input = None,
+            _ => {}
+        }
+        true
+    }
+
+    fn select(&mut self, object_id: Option<ObjectId>) {
+        self.selected = object_id;
+        // Close the dropdown in case it is open for the previously-selected
+        // object. This can happen when the object is removed from PipeWire
+        // while the dropdown is open.
+        self.dropdown_close();
+        // Likewise, don't apply a metadata property to a different subject.
+        self.metadata_input = None;
+        self.metadata_pending = None;
+        self.normalize_pending = None;
+        self.mute_pending = false;
+        self.route = 0;
+    }
+
+    /// Returns a set of object IDs of the visible objects. This includes all
+    /// dependencies that affect the display of the objects.
+    pub fn visible_objects(
+        &self,
+        area: &Rect,
+        view: &view::View,
+    ) -> HashSet<ObjectId> {
+        let objects = view.filtered_ids(self.list_kind, self.search());
+
+        let last =
+            cmp::min(objects.len(), self.top + self.visible_count(area, view));
+
+        // Always include object 0 - the global PipeWire state.
+        let mut visible_objects = HashSet::from([ObjectId::from_raw_id(0)]);
+
+        for object_id in objects[self.top..last].iter().cloned() {
+            visible_objects.insert(object_id);
+            if let Some(node) = view.nodes.get(&object_id) {
+                // Add the other members of a group for its meter.
+                if let Some(group) = &node.group {
+                    visible_objects.extend(&group.members);
+                }
+
+                // Add linked client and device.
+                visible_objects.extend(node.client_id);
+                visible_objects.extend(node.device_info.map(|(id, _, _)| id));
+
+                // Add the target and any linked client and device.
+                if let ListKind::Node(node_kind) = self.list_kind {
                     if let Some(target_id) = node
                         .target
                         .and_then(|target| target.resolve(view, node_kind))
@@ -238,39 +1295,145 @@ impl ObjectList {
         visible_objects
     }
 
-    /// Returns the number of objects visible.
-    fn visible_count(&self, area: &Rect) -> usize {
-        let (_, list_area, _) = self.areas(area);
-        let full_height = match self.list_kind {
-            ListKind::Node(_) => {
-                NodeWidget::height().saturating_add(NodeWidget::spacing())
+    /// Returns the (spacing, height) of an unexpanded object.
+    fn object_dimensions(&self) -> (u16, u16) {
+        match self.list_kind {
+            ListKind::Node(_) => match self.node_layout {
+                NodeLayout::Full => (
+                    NodeWidget::spacing(),
+                    NodeWidget::height(self.meter_orientation),
+                ),
+                NodeLayout::Compact => (
+                    NodeWidget::compact_spacing(),
+                    NodeWidget::compact_height(),
+                ),
+            },
+            ListKind::Device if self.cards => {
+                (CardWidget::spacing(), CardWidget::height(0))
             }
             ListKind::Device => {
-                DeviceWidget::height().saturating_add(DeviceWidget::spacing())
+                (DeviceWidget::spacing(), DeviceWidget::height())
             }
+        }
+    }
+
+    /// Returns the height of the object at the given index of objects, which
+    /// should be this list's filtered IDs, not including spacing. Indexes past
+    /// the end of the list get the unexpanded height.
+    fn object_height(
+        &self,
+        view: &view::View,
+        objects: &[ObjectId],
+        index: usize,
+    ) -> u16 {
+        let (_, height) = self.object_dimensions();
+        if self.cards {
+            return objects
+                .get(index)
+                .and_then(|device_id| view.devices.get(device_id))
+                .map_or(height, |device| {
+                    CardWidget::height(device.routes.len())
+                });
+        }
+        if self.expanded.is_empty() {
+            return height;
+        }
+        let Some(object_id) = objects.get(index) else {
+            return height;
         };
-        (list_area.height / full_height) as usize
+        if !self.expanded.contains(object_id) {
+            return height;
+        }
+        // Groups don't show channels.
+        view.nodes
+            .get(object_id)
+            .filter(|node| node.group.is_none())
+            .map_or(height, |node| {
+                NodeWidget::expanded_height(height, node.volumes.len())
+            })
+    }
+
+    /// Returns the number of objects that fully fit in list_height starting
+    /// from index top.
+    fn fitting_count(
+        &self,
+        view: &view::View,
+        objects: &[ObjectId],
+        list_height: u16,
+        top: usize,
+    ) -> usize {
+        let (spacing, _) = self.object_dimensions();
+        let mut remaining = list_height;
+        let mut count = 0;
+        loop {
+            let full_height = self
+                .object_height(view, objects, top.saturating_add(count))
+                .saturating_add(spacing);
+            if full_height > remaining {
+                return count;
+            }
+            remaining -= full_height;
+            count += 1;
+        }
+    }
+
+    /// Returns the number of objects visible.
+    fn visible_count(&self, area: &Rect, view: &view::View) -> usize {
+        let (_, list_area, _, _, _) = self.areas(area);
+        let objects = view.filtered_ids(self.list_kind, self.search());
+        self.fitting_count(view, &objects, list_area.height, self.top)
+    }
+
+    /// Returns the topmost index for which the object at index bottom is
+    /// still fully visible.
+    fn top_for_bottom(
+        &self,
+        view: &view::View,
+        objects: &[ObjectId],
+        area: &Rect,
+        bottom: usize,
+    ) -> usize {
+        let (_, list_area, _, _, _) = self.areas(area);
+        let (spacing, _) = self.object_dimensions();
+        let mut remaining = list_area.height;
+        let mut top = bottom;
+        for index in (0..=bottom).rev() {
+            let full_height = self
+                .object_height(view, objects, index)
+                .saturating_add(spacing);
+            if full_height > remaining {
+                break;
+            }
+            remaining -= full_height;
+            top = index;
+        }
+        top
     }
 
     /// Reconciles changes to objects, viewport, and selection.
     pub fn update(&mut self, area: Rect, view: &view::View) {
+        // A selection that is still in the list is kept as it is, so the
+        // selection and viewport survive switching tabs and re-rendering.
         let selected_index = self.selected_index(view).or_else(|| {
             // There's nothing selected! Select the first item and try again.
-            self.select(view.next_id(self.list_kind, None));
+            self.select(view.next_id(self.list_kind, self.search(), None));
             self.selected_index(view)
         });
 
-        let objects_len = view.len(self.list_kind);
-
-        let visible_count = self.visible_count(&area);
+        let objects = view.filtered_ids(self.list_kind, self.search());
+        let objects_len = objects.len();
 
         // If objects were removed and the viewport is now below the visible
         // objects, move the viewport up so that the bottom of the object list
         // is visible.
         if self.top >= objects_len {
-            self.top = objects_len.saturating_sub(visible_count);
+            self.top = objects_len.checked_sub(1).map_or(0, |last| {
+                self.top_for_bottom(view, &objects, &area, last)
+            });
         }
 
+        let visible_count = self.visible_count(&area, view);
+
         // Make sure the selected object is visible and adjust the viewport
         // if necessary.
         if self.selected.is_some() {
@@ -280,10 +1443,12 @@ impl ObjectList {
                     {
                         // The selection is below the viewport. Reposition the
                         // viewport so that the selected item is at the bottom.
-                        let visible_count_except_last =
-                            visible_count.saturating_sub(1);
-                        self.top = selected_index
-                            .saturating_sub(visible_count_except_last);
+                        self.top = self.top_for_bottom(
+                            view,
+                            &objects,
+                            &area,
+                            selected_index,
+                        );
                     } else if selected_index < self.top {
                         // The selected item is above the viewport. Reposition
                         // so that it's the first visible item.
@@ -293,19 +1458,49 @@ impl ObjectList {
                 None => self.select(None), // The selected object is gone!
             }
         }
+
+        // Keep the selected route within the selected card.
+        let route_count = self.route_count(view);
+        if self.route >= route_count {
+            self.route = route_count.saturating_sub(1);
+        }
+
+        // Return to the list if the focused node is gone.
+        if let Some((node_id, _)) = self.focused {
+            if self.selected != Some(node_id) {
+                self.focused = None;
+            }
+        }
     }
 
-    fn areas(&self, area: &Rect) -> (Rect, Rect, Rect) {
+    fn areas(&self, area: &Rect) -> (Rect, Rect, Rect, Rect, Rect) {
+        let prompt_height = if self.search.is_some()
+            || self.volume_input.is_some()
+            || self.is_metadata_editing()
+        {
+            1
+        } else {
+            0
+        };
+        let history_height = if self.show_history
+            && matches!(self.list_kind, ListKind::Node(_))
+        {
+            1
+        } else {
+            0
+        };
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(1), // header_area
-                Constraint::Min(0),    // list_area
-                Constraint::Length(1), // footer_area
+                Constraint::Length(1),              // header_area
+                Constraint::Min(0),                 // list_area
+                Constraint::Length(1),              // footer_area
+                Constraint::Length(history_height), // history_area
+                Constraint::Length(prompt_height),  // prompt_area
             ])
             .split(*area);
 
-        (layout[0], layout[1], layout[2])
+        (layout[0], layout[1], layout[2], layout[3], layout[4])
     }
 }
 
@@ -330,7 +1525,8 @@ impl ObjectListWidget<'_, '_> {
         buf: &mut Buffer,
         mouse_areas: &mut Vec<MouseArea>,
     ) {
-        let all_objects = self.view.full_nodes(node_kind);
+        let all_objects =
+            self.view.full_nodes(node_kind, self.object_list.search());
         let objects = all_objects
             .iter()
             .skip(self.object_list.top)
@@ -346,11 +1542,15 @@ impl ObjectListWidget<'_, '_> {
                 .selected
                 .map(|id| id == object.object_id)
                 .unwrap_or_default();
+            let expanded =
+                self.object_list.expanded.contains(&object.object_id);
             NodeWidget::new(
                 self.config,
+                self.object_list.peaks,
                 self.object_list.device_kind,
                 object,
                 selected,
+                expanded,
             )
             .render(object_area, buf, mouse_areas);
         }
@@ -387,7 +1587,7 @@ impl ObjectListWidget<'_, '_> {
         buf: &mut Buffer,
         mouse_areas: &mut Vec<MouseArea>,
     ) {
-        let all_objects = self.view.full_devices();
+        let all_objects = self.view.full_devices(self.object_list.search());
         let objects = all_objects
             .iter()
             .skip(self.object_list.top)
@@ -403,11 +1603,22 @@ impl ObjectListWidget<'_, '_> {
                 .selected
                 .map(|id| id == object.object_id)
                 .unwrap_or_default();
-            DeviceWidget::new(object, selected, self.config).render(
-                object_area,
-                buf,
-                mouse_areas,
-            );
+            if self.object_list.cards {
+                let selected_route = selected
+                    .then(|| self.object_list.selected_route())
+                    .flatten();
+                CardWidget::new(object, selected_route, self.config).render(
+                    object_area,
+                    buf,
+                    mouse_areas,
+                );
+            } else {
+                DeviceWidget::new(object, selected, self.config).render(
+                    object_area,
+                    buf,
+                    mouse_areas,
+                );
+            }
         }
 
         // Show the target dropdown?
@@ -442,9 +1653,31 @@ impl StatefulWidget for &mut ObjectListWidget<'_, '_> {
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let mouse_areas = state;
 
-        let (header_area, list_area, footer_area) =
+        let (header_area, list_area, footer_area, history_area, prompt_area) =
             self.object_list.areas(&area);
 
+        if let Some(node) = self
+            .object_list
+            .selected
+            .and_then(|object_id| self.view.nodes.get(&object_id))
+        {
+            HistoryWidget::new(self.config, node).render(history_area, buf);
+        }
+
+        if let Some(input) = &self.object_list.volume_input {
+            Line::from(format!("Volume: {input}%")).render(prompt_area, buf);
+        } else if let Some(input) = &self.object_list.metadata_input {
+            Line::from(format!("Metadata: {input}")).render(prompt_area, buf);
+        } else if let (Some(property), Some(object_id)) = (
+            &self.object_list.metadata_pending,
+            self.object_list.selected,
+        ) {
+            Line::from(property.prompt(self.view, object_id))
+                .render(prompt_area, buf);
+        } else if let Some(search) = &self.object_list.search {
+            Line::from(format!("/{search}")).render(prompt_area, buf);
+        }
+
         mouse_areas.push((
             header_area,
             smallvec![MouseEventKind::Down(MouseButton::Left)],
@@ -469,17 +1702,48 @@ impl StatefulWidget for &mut ObjectListWidget<'_, '_> {
             smallvec![Action::MoveDown],
         ));
 
-        let (spacing, height) = match self.object_list.list_kind {
-            ListKind::Node(_) => (NodeWidget::spacing(), NodeWidget::height()),
-            ListKind::Device => {
-                (DeviceWidget::spacing(), DeviceWidget::height())
+        if let Some((node_id, index)) = self.object_list.focused {
+            if let Some(node) = self.view.nodes.get(&node_id) {
+                FocusWidget::new(
+                    self.config,
+                    self.object_list.peaks,
+                    self.object_list.device_kind,
+                    node,
+                    index,
+                )
+                .render(list_area, buf, mouse_areas);
+                return;
             }
-        };
+        }
+
+        let (spacing, _) = self.object_list.object_dimensions();
+        let top = self.object_list.top;
+        let objects = self.view.filtered_ids(
+            self.object_list.list_kind,
+            self.object_list.search(),
+        );
+        let objects_visible = self.object_list.fitting_count(
+            self.view,
+            &objects,
+            list_area.height,
+            top,
+        );
 
-        let full_object_height = height.saturating_add(spacing);
-        let objects_visible = (list_area.height / full_object_height) as usize;
+        // Heights of the fully visible objects and of a partial last object
+        let bottom = top.saturating_add(objects_visible);
+        let heights: Vec<u16> = (top..bottom)
+            .map(|index| {
+                self.object_list.object_height(self.view, &objects, index)
+            })
+            .collect();
+        let partial_height =
+            self.object_list.object_height(self.view, &objects, bottom);
+        let used_height = heights
+            .iter()
+            .map(|height| height.saturating_add(spacing))
+            .fold(0u16, |acc, height| acc.saturating_add(height));
 
-        let len = self.view.len(self.object_list.list_kind);
+        let len = objects.len();
 
         // Indicate we can scroll up if there are objects above the viewport.
         if self.object_list.top > 0 {
@@ -499,7 +1763,7 @@ impl StatefulWidget for &mut ObjectListWidget<'_, '_> {
             self.object_list.top.saturating_add(objects_visible)
                 == len.saturating_sub(1);
         let is_bottom_enough =
-            (list_area.height % full_object_height) >= height;
+            list_area.height.saturating_sub(used_height) >= partial_height;
         if self.object_list.top.saturating_add(objects_visible) < len
             && !(is_bottom_last && is_bottom_enough)
         {
@@ -512,11 +1776,12 @@ impl StatefulWidget for &mut ObjectListWidget<'_, '_> {
         }
 
         let objects_layout = {
-            let object_height = height;
-            let mut constraints =
-                vec![Constraint::Length(object_height); objects_visible];
+            let mut constraints: Vec<Constraint> = heights
+                .iter()
+                .map(|&height| Constraint::Length(height))
+                .collect();
             // A variable-length constraint for a partial last object
-            constraints.push(Constraint::Max(object_height));
+            constraints.push(Constraint::Max(partial_height));
             let constraints = constraints;
 
             Layout::default()
@@ -563,6 +1828,8 @@ mod tests {
     use crate::mock;
     use crate::view::{ListKind, NodeKind, View};
     use crate::wirehose::{state::State, PropertyStore, StateEvent};
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
     use std::sync::Arc;
 
     fn init() -> (State, mock::WirehoseHandle<'static>) {
@@ -589,6 +1856,7 @@ mod tests {
                     object_id,
                     rate: 44100,
                     peaks: Arc::new([0.0.into(), 0.0.into()]),
+                    clips: Arc::new([]),
                 },
                 StateEvent::NodeVolumes {
                     object_id,
@@ -640,9 +1908,12 @@ mod tests {
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
         // + 2 for header and footer
         let rect = Rect::new(0, 0, 80, height * 3 + 2);
         let mut object_list =
@@ -666,9 +1937,12 @@ mod tests {
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
         // + 2 for header and footer
         let rect = Rect::new(0, 0, 80, height * 3 + 2);
         let mut object_list =
@@ -690,33 +1964,80 @@ mod tests {
     }
 
     #[test]
-    fn visible_objects_changes_with_scroll() {
+    fn object_list_keeps_viewport_across_updates() {
         let (state, wirehose) = init();
         let view = View::from(
             &wirehose,
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
-        // 3 nodes + 2 lines for header and footer
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
+        // + 2 for header and footer
         let rect = Rect::new(0, 0, 80, height * 3 + 2);
         let mut object_list =
             ObjectList::new(ListKind::Node(NodeKind::All), None);
+        for _ in 0..5 {
+            object_list.down(&view);
+        }
+        object_list.update(rect, &view);
+        assert_eq!(object_list.top, 2);
+        assert_eq!(object_list.selected, Some(ObjectId::from_raw_id(5)));
 
-        // Start at top
-        let visible = object_list.visible_objects(&rect, &view);
-        assert_eq!(visible.len(), 4);
-        assert!(visible.contains(&ObjectId::from_raw_id(0)));
-        assert!(visible.contains(&ObjectId::from_raw_id(1)));
-        assert!(visible.contains(&ObjectId::from_raw_id(2)));
-        assert!(visible.contains(&ObjectId::from_raw_id(3)));
+        // Moving within the viewport doesn't scroll.
+        object_list.up(&view);
+        object_list.update(rect, &view);
+        assert_eq!(object_list.top, 2);
+        assert_eq!(object_list.selected, Some(ObjectId::from_raw_id(4)));
 
-        // Scroll down
-        object_list.top = 5;
-        let visible = object_list.visible_objects(&rect, &view);
-        assert_eq!(visible.len(), 4);
+        // Another tab is shown and updated in the meantime.
+        let mut other_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        other_list.update(rect, &view);
+
+        // Switching back re-renders without moving anything.
+        for _ in 0..3 {
+            object_list.update(rect, &view);
+            assert_eq!(object_list.top, 2);
+            assert_eq!(object_list.selected, Some(ObjectId::from_raw_id(4)));
+        }
+    }
+
+    #[test]
+    fn visible_objects_changes_with_scroll() {
+        let (state, wirehose) = init();
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
+        // 3 nodes + 2 lines for header and footer
+        let rect = Rect::new(0, 0, 80, height * 3 + 2);
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::All), None);
+
+        // Start at top
+        let visible = object_list.visible_objects(&rect, &view);
+        assert_eq!(visible.len(), 4);
+        assert!(visible.contains(&ObjectId::from_raw_id(0)));
+        assert!(visible.contains(&ObjectId::from_raw_id(1)));
+        assert!(visible.contains(&ObjectId::from_raw_id(2)));
+        assert!(visible.contains(&ObjectId::from_raw_id(3)));
+
+        // Scroll down
+        object_list.top = 5;
+        let visible = object_list.visible_objects(&rect, &view);
+        assert_eq!(visible.len(), 4);
         assert!(visible.contains(&ObjectId::from_raw_id(0)));
         assert!(visible.contains(&ObjectId::from_raw_id(6)));
         assert!(visible.contains(&ObjectId::from_raw_id(7)));
@@ -732,6 +2053,54 @@ mod tests {
         assert!(visible.contains(&ObjectId::from_raw_id(7)));
     }
 
+    #[test]
+    fn vertical_meters_fit_fewer_nodes() {
+        let (state, wirehose) = init();
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let height = NodeWidget::height(MeterOrientation::Horizontal)
+            + NodeWidget::spacing();
+        // 3 horizontal nodes + 2 lines for header and footer
+        let rect = Rect::new(0, 0, 80, height * 3 + 2);
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::All), None);
+        assert_eq!(object_list.visible_count(&rect, &view), 3);
+
+        object_list.meter_orientation = MeterOrientation::Vertical;
+        assert_eq!(object_list.visible_count(&rect, &view), 1);
+    }
+
+    #[test]
+    fn compact_nodes_take_one_line() {
+        let (state, wirehose) = init();
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        // 12 lines + 2 lines for header and footer
+        let rect = Rect::new(0, 0, 80, 14);
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::All), None);
+        object_list.node_layout = NodeLayout::Compact;
+        assert_eq!(object_list.visible_count(&rect, &view), 12);
+
+        // Orientation doesn't matter when the meter is inline.
+        object_list.meter_orientation = MeterOrientation::Vertical;
+        assert_eq!(object_list.visible_count(&rect, &view), 12);
+    }
+
     #[test]
     fn visible_objects_includes_linked_clients() {
         let (mut state, wirehose) = init();
@@ -754,9 +2123,12 @@ mod tests {
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
         // 1 node + 2 lines for header and footer
         let rect = Rect::new(0, 0, 80, height + 2);
         let object_list = ObjectList::new(ListKind::Node(NodeKind::All), None);
@@ -813,9 +2185,12 @@ mod tests {
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
         // 1 node + 2 lines for header and footer
         let rect = Rect::new(0, 0, 80, height + 2);
         let object_list = ObjectList::new(ListKind::Node(NodeKind::All), None);
@@ -865,9 +2240,12 @@ mod tests {
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
         let rect = Rect::new(0, 0, 80, height + 2);
         let object_list =
             ObjectList::new(ListKind::Node(NodeKind::Playback), None);
@@ -934,9 +2312,12 @@ mod tests {
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
         let rect = Rect::new(0, 0, 80, height + 2);
         let object_list =
             ObjectList::new(ListKind::Node(NodeKind::Playback), None);
@@ -1026,9 +2407,12 @@ mod tests {
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
         let rect = Rect::new(0, 0, 80, height + 2);
         let object_list =
             ObjectList::new(ListKind::Node(NodeKind::Playback), None);
@@ -1070,11 +2454,14 @@ mod tests {
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
         assert!(view.default_sink.is_some());
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
         let rect = Rect::new(0, 0, 80, height + 2);
         let object_list =
             ObjectList::new(ListKind::Node(NodeKind::Playback), None);
@@ -1115,11 +2502,14 @@ mod tests {
             &state,
             &config::Names::default(),
             &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
         );
 
         assert!(view.default_source.is_some());
 
-        let height = NodeWidget::height() + NodeWidget::spacing();
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
         let rect = Rect::new(0, 0, 80, height + 2);
         let object_list =
             ObjectList::new(ListKind::Node(NodeKind::Recording), None);
@@ -1128,4 +2518,1156 @@ mod tests {
         assert!(visible.contains(&stream_id));
         assert!(visible.contains(&source_id));
     }
+
+    #[test]
+    fn toggle_target_switches_to_previous() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let stream_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, stream_id, "Stream/Output/Audio", "stream");
+
+        let sink_a_id = ObjectId::from_raw_id(100);
+        create_node(&mut state, sink_a_id, "Audio/Sink", "sink_a");
+        let sink_b_id = ObjectId::from_raw_id(101);
+        create_node(&mut state, sink_b_id, "Audio/Sink", "sink_b");
+
+        state.update(StateEvent::Link {
+            object_id: ObjectId::from_raw_id(200),
+            output_id: stream_id,
+            input_id: sink_a_id,
+        });
+
+        let metadata_id = ObjectId::from_raw_id(300);
+        state.update(StateEvent::MetadataMetadataName {
+            object_id: metadata_id,
+            metadata_name: String::from("default"),
+        });
+        state.update(StateEvent::MetadataProperty {
+            object_id: metadata_id,
+            subject: u32::from(stream_id),
+            key: Some(String::from("target.node")),
+            value: Some(String::from("100")),
+        });
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        object_list.selected = Some(stream_id);
+
+        // No history yet, so there is nothing to toggle to.
+        let mut target_history = TargetHistory::default();
+        target_history.update(&view);
+        assert!(!object_list.toggle_target(&view, &mut target_history));
+
+        // Pretend the stream was on sink B before it moved to sink A.
+        target_history.record("stream", view::Target::Node(sink_b_id));
+        target_history.update(&view);
+        assert!(object_list.toggle_target(&view, &mut target_history));
+        assert_eq!(
+            target_history.previous("stream"),
+            Some(view::Target::Node(sink_a_id))
+        );
+    }
+
+    #[test]
+    fn adjacent_target_wraps_and_skips_default() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let stream_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, stream_id, "Stream/Output/Audio", "stream");
+
+        // Targets are ordered by title, so give the sinks distinct ones.
+        let sink_a_id = ObjectId::from_raw_id(100);
+        let sink_b_id = ObjectId::from_raw_id(101);
+        for (object_id, name) in [(sink_a_id, "sink_a"), (sink_b_id, "sink_b")]
+        {
+            create_node(&mut state, object_id, "Audio/Sink", name);
+            let mut props = PropertyStore::default();
+            props.set_node_description(String::from(name));
+            props.set_media_class(String::from("Audio/Sink"));
+            props.set_node_name(String::from(name));
+            props.set_object_serial(u32::from(object_id) as u64);
+            state.update(StateEvent::NodeProperties { object_id, props });
+        }
+
+        state.update(StateEvent::Link {
+            object_id: ObjectId::from_raw_id(200),
+            output_id: stream_id,
+            input_id: sink_b_id,
+        });
+
+        let metadata_id = ObjectId::from_raw_id(300);
+        state.update(StateEvent::MetadataMetadataName {
+            object_id: metadata_id,
+            metadata_name: String::from("default"),
+        });
+        state.update(StateEvent::MetadataProperty {
+            object_id: metadata_id,
+            subject: u32::from(stream_id),
+            key: Some(String::from("target.node")),
+            value: Some(String::from("101")),
+        });
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        object_list.selected = Some(stream_id);
+
+        // Forward from the last target wraps past "Default" to the first sink.
+        assert_eq!(
+            object_list.adjacent_target(&view, true, false),
+            Some(view::Target::Node(sink_a_id))
+        );
+        assert_eq!(
+            object_list.adjacent_target(&view, false, false),
+            Some(view::Target::Node(sink_a_id))
+        );
+        assert_eq!(
+            object_list.adjacent_target(&view, true, true),
+            Some(view::Target::Default)
+        );
+    }
+
+    #[test]
+    fn device_volume_uses_active_routes() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let with_route_id = ObjectId::from_raw_id(101);
+        let without_route_id = ObjectId::from_raw_id(102);
+        for object_id in [with_route_id, without_route_id] {
+            let mut props = PropertyStore::default();
+            props.set_device_nick(String::from("Device"));
+            props.set_object_serial(u32::from(object_id) as u64);
+            state.update(StateEvent::DeviceProperties { object_id, props });
+            state.update(StateEvent::DeviceEnumProfile {
+                object_id,
+                index: 1,
+                description: String::from("Profile"),
+                available: true,
+                classes: Vec::new(),
+            });
+            state.update(StateEvent::DeviceProfile {
+                object_id,
+                index: 1,
+            });
+        }
+        // Only the first route belongs to the current profile.
+        for (index, profile) in [(0, 1), (1, 2)] {
+            state.update(StateEvent::DeviceRoute {
+                object_id: with_route_id,
+                index,
+                device: index,
+                profiles: vec![profile],
+                description: String::new(),
+                available: true,
+                channel_volumes: vec![0.125, 0.125],
+                mute: false,
+            });
+        }
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let device = view.devices.get(&with_route_id).unwrap();
+        assert_eq!(device.routes.len(), 1);
+        assert_eq!(device.mean_volume(), Some(0.125));
+        assert!(!device.is_muted());
+
+        let mut object_list = ObjectList::new(ListKind::Device, None);
+        object_list.selected = Some(with_route_id);
+        assert!(object_list.set_absolute_volume(&view, 0.5, Some(150.0)));
+        assert!(!object_list.set_absolute_volume(&view, 2.0, Some(150.0)));
+
+        object_list.selected = Some(without_route_id);
+        assert!(!object_list.set_absolute_volume(&view, 0.5, None));
+        assert!(!view.device_mute(without_route_id));
+        assert_eq!(
+            view.devices.get(&without_route_id).unwrap().mean_volume(),
+            None
+        );
+    }
+
+    #[test]
+    fn card_adjusts_selected_route() {
+        let mut state = State::default();
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+
+        let first_id = ObjectId::from_raw_id(101);
+        let second_id = ObjectId::from_raw_id(102);
+        for object_id in [first_id, second_id] {
+            let mut props = PropertyStore::default();
+            props.set_device_nick(String::from("Card"));
+            props.set_object_serial(u32::from(object_id) as u64);
+            state.update(StateEvent::DeviceProperties { object_id, props });
+            state.update(StateEvent::DeviceEnumProfile {
+                object_id,
+                index: 1,
+                description: String::from("Profile"),
+                available: true,
+                classes: Vec::new(),
+            });
+            state.update(StateEvent::DeviceProfile {
+                object_id,
+                index: 1,
+            });
+        }
+        // Speakers and headphones on the first card
+        for device in [0, 1] {
+            state.update(StateEvent::DeviceRoute {
+                object_id: first_id,
+                index: device,
+                device,
+                profiles: vec![1],
+                description: String::new(),
+                available: true,
+                channel_volumes: vec![0.125, 0.125],
+                mute: false,
+            });
+        }
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list = ObjectList::new(ListKind::Device, None);
+        object_list.cards = true;
+        object_list.update(Rect::new(0, 0, 80, 24), &view);
+        assert_eq!(object_list.selected, Some(first_id));
+        assert_eq!(object_list.selected_route(), Some(0));
+
+        // Down moves through the routes before moving to the next card.
+        object_list.down(&view);
+        assert_eq!(object_list.selected, Some(first_id));
+        assert_eq!(object_list.selected_route(), Some(1));
+        assert!(object_list.set_absolute_volume(&view, 0.5, None));
+        object_list.toggle_mute(&view, &mut MuteMemory::default());
+        assert_eq!(
+            commands.borrow_mut().drain(..).collect::<Vec<_>>(),
+            vec![
+                mock::MockCommand::DeviceVolumes(
+                    first_id,
+                    1,
+                    vec![0.125, 0.125]
+                ),
+                mock::MockCommand::DeviceMute(first_id, 1, true),
+            ]
+        );
+
+        object_list.down(&view);
+        assert_eq!(object_list.selected, Some(second_id));
+        assert_eq!(object_list.selected_route(), Some(0));
+        assert!(!object_list.set_absolute_volume(&view, 0.5, None));
+
+        // Up enters the previous card from its last route.
+        object_list.up(&view);
+        assert_eq!(object_list.selected, Some(first_id));
+        assert_eq!(object_list.selected_route(), Some(1));
+    }
+
+    #[test]
+    fn device_mute_confirmed() {
+        let mut state = State::default();
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+
+        let object_id = ObjectId::from_raw_id(101);
+        let mut props = PropertyStore::default();
+        props.set_device_nick(String::from("Card"));
+        props.set_object_serial(101);
+        state.update(StateEvent::DeviceProperties { object_id, props });
+        state.update(StateEvent::DeviceEnumProfile {
+            object_id,
+            index: 1,
+            description: String::from("Profile"),
+            available: true,
+            classes: Vec::new(),
+        });
+        state.update(StateEvent::DeviceProfile {
+            object_id,
+            index: 1,
+        });
+        state.update(StateEvent::DeviceRoute {
+            object_id,
+            index: 0,
+            device: 2,
+            profiles: vec![1],
+            description: String::new(),
+            available: true,
+            channel_volumes: vec![1.0, 1.0],
+            mute: false,
+        });
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list = ObjectList::new(ListKind::Device, None);
+        object_list.confirm_device_mute = true;
+        object_list.update(Rect::new(0, 0, 80, 24), &view);
+        let mut mute_memory = MuteMemory::default();
+
+        // The first press only asks for confirmation.
+        assert!(!object_list.toggle_mute(&view, &mut mute_memory));
+        assert!(commands.borrow().is_empty());
+
+        assert!(object_list.toggle_mute(&view, &mut mute_memory));
+        assert_eq!(
+            commands.borrow_mut().drain(..).collect::<Vec<_>>(),
+            vec![mock::MockCommand::DeviceMute(object_id, 2, true)]
+        );
+    }
+
+    #[test]
+    fn profile_change_confirmation() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let device_id = ObjectId::from_raw_id(101);
+        let mut props = PropertyStore::default();
+        props.set_device_nick(String::from("Device"));
+        props.set_object_serial(101);
+        state.update(StateEvent::DeviceProperties {
+            object_id: device_id,
+            props,
+        });
+        for (index, description) in [(0, "Off"), (1, "Duplex")] {
+            state.update(StateEvent::DeviceEnumProfile {
+                object_id: device_id,
+                index,
+                description: String::from(description),
+                available: true,
+                classes: Vec::new(),
+            });
+        }
+        state.update(StateEvent::DeviceProfile {
+            object_id: device_id,
+            index: 1,
+        });
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list = ObjectList::new(ListKind::Device, None);
+        object_list.confirm_profile_change = true;
+        object_list.selected = Some(device_id);
+
+        // Picking a profile asks for confirmation with Cancel selected.
+        object_list.dropdown_activate(&view);
+        object_list.up(&view);
+        object_list.dropdown_activate(&view);
+        assert_eq!(
+            object_list.confirm_target,
+            Some((view::Target::Profile(device_id, 0), String::from("Off")))
+        );
+        assert_eq!(
+            object_list.dropdown_items(),
+            vec![String::from("Switch to Off"), String::from("Cancel")]
+        );
+        assert_eq!(object_list.dropdown_state.selected(), Some(1));
+
+        // Cancel closes the dropdown.
+        object_list.dropdown_activate(&view);
+        assert_eq!(object_list.confirm_target, None);
+        assert_eq!(object_list.dropdown_state.selected(), None);
+
+        // So does Esc.
+        object_list.set_target(&view, view::Target::Profile(device_id, 0));
+        assert!(object_list.confirm_target.is_some());
+        object_list.dropdown_close();
+        assert_eq!(object_list.confirm_target, None);
+
+        // Confirming closes the dropdown too.
+        object_list.set_target(&view, view::Target::Profile(device_id, 0));
+        object_list.up(&view);
+        object_list.dropdown_activate(&view);
+        assert_eq!(object_list.confirm_target, None);
+        assert_eq!(object_list.dropdown_state.selected(), None);
+
+        // Without the option, profiles change immediately.
+        object_list.confirm_profile_change = false;
+        object_list.set_target(&view, view::Target::Profile(device_id, 0));
+        assert_eq!(object_list.confirm_target, None);
+    }
+
+    #[test]
+    fn dropdown_filter() {
+        let device_id = ObjectId::from_raw_id(0);
+        let mut object_list = ObjectList::new(ListKind::Device, None);
+        object_list.targets = ["Off", "Analog Stereo Duplex", "HDMI Stereo"]
+            .into_iter()
+            .enumerate()
+            .map(|(index, title)| {
+                let target = view::Target::Profile(device_id, index as i32);
+                (target, String::from(title), true)
+            })
+            .collect();
+        object_list.dropdown_state.select(Some(2));
+
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        // Keys are left to keybindings until filtering starts.
+        assert!(!object_list.dropdown_input(key(KeyCode::Char('j'))));
+        object_list.start_search();
+        assert!(object_list.is_dropdown_open());
+        for c in "sTe".chars() {
+            assert!(object_list.dropdown_input(key(KeyCode::Char(c))));
+        }
+        assert_eq!(
+            object_list.dropdown_items(),
+            vec![
+                String::from("Analog Stereo Duplex"),
+                String::from("HDMI Stereo")
+            ]
+        );
+        assert_eq!(object_list.dropdown_state.selected(), Some(0));
+
+        object_list.dropdown_input(key(KeyCode::Char('x')));
+        assert!(object_list.dropdown_items().is_empty());
+        assert_eq!(object_list.selected_target(), None);
+
+        object_list.dropdown_input(key(KeyCode::Backspace));
+        object_list.dropdown_input(key(KeyCode::Char('r')));
+        object_list.dropdown_input(key(KeyCode::Char('e')));
+        assert_eq!(
+            object_list.selected_target(),
+            Some(&(
+                view::Target::Profile(device_id, 1),
+                String::from("Analog Stereo Duplex"),
+                true
+            ))
+        );
+
+        // Navigation keys are left alone.
+        assert!(!object_list.dropdown_input(key(KeyCode::Down)));
+
+        // Enter keeps the filter but stops typing into it.
+        assert!(object_list.dropdown_input(key(KeyCode::Enter)));
+        assert!(!object_list.dropdown_input(key(KeyCode::Char('j'))));
+        assert_eq!(object_list.dropdown_filter, "re");
+
+        object_list.dropdown_close();
+        assert!(object_list.dropdown_filter.is_empty());
+    }
+
+    #[test]
+    fn set_channel_volume_out_of_range() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let stream_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, stream_id, "Stream/Output/Audio", "stream");
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        object_list.selected = Some(stream_id);
+
+        // The node has two channels.
+        assert!(object_list.set_channel_volume(&view, 1, 0.5, None));
+        assert!(!object_list.set_channel_volume(&view, 2, 0.5, None));
+    }
+
+    #[test]
+    fn focus_adjusts_one_channel() {
+        let mut state = State::default();
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+
+        let stream_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, stream_id, "Stream/Output/Audio", "stream");
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        object_list.selected = Some(stream_id);
+        object_list.toggle_focus(&view);
+        assert_eq!(object_list.focused_channel(), Some(0));
+
+        // The focus stops at the last channel.
+        object_list.focus_down(&view);
+        object_list.focus_down(&view);
+        assert_eq!(object_list.focused_channel(), Some(1));
+
+        assert!(object_list.set_focused_relative_volume(
+            &view,
+            -0.5,
+            None,
+            VolumeUnit::Percent
+        ));
+        assert_eq!(
+            commands.borrow_mut().pop_front(),
+            Some(mock::MockCommand::NodeVolumes(stream_id, vec![1.0, 0.125]))
+        );
+
+        object_list.toggle_focus(&view);
+        assert!(!object_list.is_focused());
+        assert!(!object_list.set_focused_relative_volume(
+            &view,
+            -0.5,
+            None,
+            VolumeUnit::Percent
+        ));
+    }
+
+    #[test]
+    fn reset_volume_respects_max() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let stream_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, stream_id, "Stream/Output/Audio", "stream");
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        assert!(!object_list.reset_volume(&view, None, true));
+
+        object_list.selected = Some(stream_id);
+        assert!(object_list.reset_volume(&view, None, true));
+        assert!(object_list.reset_volume(&view, Some(150.0), false));
+        assert!(!object_list.reset_volume(&view, Some(50.0), false));
+    }
+
+    #[test]
+    fn mute_all_remembers_muted() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+        let mut mute_memory = MuteMemory::default();
+
+        let playing_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, playing_id, "Stream/Output/Audio", "playing");
+        let muted_id = ObjectId::from_raw_id(1);
+        create_node(&mut state, muted_id, "Stream/Output/Audio", "muted");
+        state.update(StateEvent::NodeMute {
+            object_id: muted_id,
+            mute: true,
+        });
+        let sink_id = ObjectId::from_raw_id(2);
+        create_node(&mut state, sink_id, "Audio/Sink", "sink");
+
+        let view = |state: &State| {
+            View::from(
+                &wirehose,
+                state,
+                &config::Names::default(),
+                &Vec::new(),
+                &config::Sort::default(),
+                &Default::default(),
+            )
+        };
+
+        let mut object_list = ObjectList::new(ListKind::Device, None);
+        assert!(!object_list.mute_all(&view(&state), &mut mute_memory));
+
+        // Only the unmuted stream is muted and remembered.
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        assert!(object_list.mute_all(&view(&state), &mut mute_memory));
+        assert_eq!(object_list.bulk_muted, HashSet::from([playing_id]));
+
+        state.update(StateEvent::NodeMute {
+            object_id: playing_id,
+            mute: true,
+        });
+        assert!(object_list.mute_all(&view(&state), &mut mute_memory));
+        assert!(object_list.bulk_muted.is_empty());
+    }
+
+    #[test]
+    fn normalize_previews_then_applies() {
+        let mut state = State::default();
+        let commands = RefCell::new(VecDeque::new());
+        let wirehose = mock::WirehoseHandle::with_commands(&commands);
+
+        let loud_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, loud_id, "Stream/Output/Audio", "loud");
+        let quiet_id = ObjectId::from_raw_id(1);
+        create_node(&mut state, quiet_id, "Stream/Output/Audio", "quiet");
+        state.update(StateEvent::NodeVolumes {
+            object_id: quiet_id,
+            volumes: vec![0.125, 0.125],
+        });
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        assert_eq!(
+            object_list.normalize(&view, None),
+            Normalize::Preview {
+                volume: 0.75,
+                count: 2
+            }
+        );
+        // A different outcome needs another preview.
+        assert_eq!(
+            object_list.normalize(&view, Some(50.0)),
+            Normalize::Preview {
+                volume: 0.5,
+                count: 2
+            }
+        );
+        assert!(commands.borrow().is_empty());
+
+        assert_eq!(
+            object_list.normalize(&view, Some(50.0)),
+            Normalize::Applied
+        );
+        assert_eq!(commands.borrow().len(), 2);
+
+        let mut object_list = ObjectList::new(ListKind::Device, None);
+        assert_eq!(object_list.normalize(&view, None), Normalize::Nothing);
+    }
+
+    #[test]
+    fn cycle_default_needs_another_node() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let sink_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, sink_id, "Audio/Sink", "sink");
+
+        let mut view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+        let mut object_list = ObjectList::new(
+            ListKind::Node(NodeKind::Output),
+            Some(DeviceKind::Sink),
+        );
+        // With no default, the first node becomes the default.
+        assert!(object_list.cycle_default(&view));
+
+        // A lone default has nothing to cycle to.
+        view.nodes.get_mut(&sink_id).unwrap().is_default_sink = true;
+        assert!(!object_list.cycle_default(&view));
+
+        // Only lists of sinks or sources have defaults.
+        let mut object_list = ObjectList::new(ListKind::Device, None);
+        assert!(!object_list.cycle_default(&view));
+    }
+
+    #[test]
+    fn select_default_in_list() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let sink_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, sink_id, "Audio/Sink", "sink");
+        let default_id = ObjectId::from_raw_id(1);
+        create_node(&mut state, default_id, "Audio/Sink", "default");
+
+        let mut view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+        let mut object_list = ObjectList::new(
+            ListKind::Node(NodeKind::Output),
+            Some(DeviceKind::Sink),
+        );
+        // No default is a no-op.
+        assert!(!object_list.select_default(&view));
+
+        view.nodes.get_mut(&default_id).unwrap().is_default_sink = true;
+        assert!(object_list.select_default(&view));
+        assert_eq!(object_list.selected, Some(default_id));
+        assert!(!object_list.select_default(&view));
+
+        // Sources don't have the default sink.
+        let mut object_list = ObjectList::new(
+            ListKind::Node(NodeKind::Input),
+            Some(DeviceKind::Source),
+        );
+        assert!(!object_list.select_default(&view));
+    }
+
+    #[test]
+    fn select_listed_only_in_own_list() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let sink_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, sink_id, "Audio/Sink", "sink");
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Input), None);
+        assert!(!object_list.select_listed(&view, sink_id));
+        assert_eq!(object_list.selected, None);
+
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Output), None);
+        assert!(object_list.select_listed(&view, sink_id));
+        assert_eq!(object_list.selected, Some(sink_id));
+    }
+
+    #[test]
+    fn expanded_node_reduces_visible_count() {
+        let (state, wirehose) = init();
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
+        // 3 nodes + 2 lines for header and footer
+        let rect = Rect::new(0, 0, 80, height * 3 + 2);
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::All), None);
+        object_list.update(rect, &view);
+        assert_eq!(object_list.visible_objects(&rect, &view).len(), 4);
+
+        // Expanding the first node pushes the third out of the viewport.
+        object_list.toggle_expanded();
+        let visible = object_list.visible_objects(&rect, &view);
+        assert_eq!(visible.len(), 3);
+        assert!(!visible.contains(&ObjectId::from_raw_id(3)));
+
+        // Selecting the third node scrolls it back into view.
+        object_list.down(&view);
+        object_list.down(&view);
+        object_list.update(rect, &view);
+        assert_eq!(object_list.top, 1);
+
+        object_list.up(&view);
+        object_list.up(&view);
+        object_list.toggle_expanded();
+        object_list.update(rect, &view);
+        assert_eq!(object_list.visible_objects(&rect, &view).len(), 4);
+    }
+
+    #[test]
+    fn search_filters_navigation() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        for (id, name) in [(1, "Firefox"), (2, "mpv"), (3, "firefox-beta")] {
+            let object_id = ObjectId::from_raw_id(id);
+            create_node(&mut state, object_id, "Stream/Output/Audio", name);
+        }
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
+        let rect = Rect::new(0, 0, 80, height * 3 + 3);
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        object_list.update(rect, &view);
+        assert_eq!(object_list.selected, Some(ObjectId::from_raw_id(1)));
+
+        object_list.start_search();
+        for c in "FIREFOX".chars() {
+            let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::SHIFT);
+            assert!(object_list.search_input(key));
+        }
+        object_list.update(rect, &view);
+
+        // The selection still matches, so it stays selected.
+        assert_eq!(object_list.selected, Some(ObjectId::from_raw_id(1)));
+
+        // mpv is skipped.
+        object_list.down(&view);
+        assert_eq!(object_list.selected, Some(ObjectId::from_raw_id(3)));
+        object_list.down(&view);
+        assert_eq!(object_list.selected, Some(ObjectId::from_raw_id(3)));
+
+        let visible = object_list.visible_objects(&rect, &view);
+        assert!(!visible.contains(&ObjectId::from_raw_id(2)));
+
+        // Esc restores the full list.
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(object_list.search_input(key));
+        object_list.up(&view);
+        assert_eq!(object_list.selected, Some(ObjectId::from_raw_id(2)));
+    }
+
+    #[test]
+    fn search_removes_unmatched_selection() {
+        let (state, wirehose) = init();
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let height = NodeWidget::height(MeterOrientation::default())
+            + NodeWidget::spacing();
+        let rect = Rect::new(0, 0, 80, height * 3 + 3);
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::All), None);
+        object_list.update(rect, &view);
+        assert!(object_list.selected.is_some());
+
+        object_list.start_search();
+        let key = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE);
+        assert!(object_list.search_input(key));
+        object_list.update(rect, &view);
+        assert_eq!(object_list.selected, None);
+    }
+
+    #[test]
+    fn sort_keeps_selection() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let ids: Vec<_> = (0..3).map(ObjectId::from_raw_id).collect();
+        // "C" sorts last by name, ignoring case
+        for (&object_id, (name, volume)) in
+            ids.iter().zip([("C", 0.5), ("a", 0.2), ("b", 0.9)])
+        {
+            create_node(&mut state, object_id, "Stream/Output/Audio", name);
+            state.update(StateEvent::NodeVolumes {
+                object_id,
+                volumes: vec![volume, volume],
+            });
+        }
+        let view_for = |sort_mode| {
+            let sort = config::Sort {
+                playback: sort_mode,
+                ..Default::default()
+            };
+            View::from(
+                &wirehose,
+                &state,
+                &config::Names::default(),
+                &Vec::new(),
+                &sort,
+                &Default::default(),
+            )
+        };
+        let list_kind = ListKind::Node(NodeKind::Playback);
+
+        let view = view_for(config::SortMode::Serial);
+        assert_eq!(view.object_ids(list_kind), &[ids[0], ids[1], ids[2]]);
+
+        let rect = Rect::new(0, 0, 80, 100);
+        let mut object_list = ObjectList::new(list_kind, None);
+        object_list.selected = Some(ids[2]);
+        object_list.update(rect, &view);
+
+        let view = view_for(config::SortMode::Name);
+        assert_eq!(view.object_ids(list_kind), &[ids[1], ids[2], ids[0]]);
+        object_list.update(rect, &view);
+        assert_eq!(object_list.selected, Some(ids[2]));
+
+        let view = view_for(config::SortMode::Volume);
+        assert_eq!(view.object_ids(list_kind), &[ids[2], ids[0], ids[1]]);
+        object_list.update(rect, &view);
+        assert_eq!(object_list.selected, Some(ids[2]));
+    }
+
+    #[test]
+    fn node_format_omits_missing() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+
+        let bare_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, bare_id, "Audio/Sink", "bare");
+
+        let object_id = ObjectId::from_raw_id(1);
+        let mut props = PropertyStore::default();
+        props.set_media_class(String::from("Audio/Sink"));
+        props.set_node_name(String::from("sink"));
+        props.set_node_description(String::from("Sink"));
+        props.set_object_serial(1);
+        props.set_audio_rate(48000);
+        props.set_audio_format(String::from("F32LE"));
+        state.update(StateEvent::NodeProperties { object_id, props });
+        state.update(StateEvent::NodeVolumes {
+            object_id,
+            volumes: vec![1.0, 1.0],
+        });
+        state.update(StateEvent::NodeMute {
+            object_id,
+            mute: false,
+        });
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+        assert_eq!(view.nodes[&bare_id].format, None);
+        assert_eq!(
+            view.nodes[&object_id].format.as_deref(),
+            Some("48000 Hz, F32LE")
+        );
+    }
+
+    #[test]
+    fn volume_input_rejects_non_numeric() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+        let object_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, object_id, "Stream/Output/Audio", "stream");
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        object_list.selected = Some(object_id);
+        object_list.start_volume_input();
+        assert!(object_list.is_volume_editing());
+
+        for c in ['7', 'x', '5', '.', '.', '5'] {
+            let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+            assert!(object_list.volume_input(key, &view, None));
+        }
+        assert_eq!(object_list.volume_input.as_deref(), Some("75.5"));
+
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(object_list.volume_input(esc, &view, None));
+        assert!(!object_list.is_volume_editing());
+
+        // Keys with modifiers are left for keybindings
+        object_list.start_volume_input();
+        let key = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(!object_list.volume_input(key, &view, None));
+    }
+
+    #[test]
+    fn metadata_property_parse() {
+        assert_eq!(
+            MetadataProperty::parse("target.node:Spa:Id=42"),
+            Some(MetadataProperty {
+                key: String::from("target.node"),
+                type_: Some(String::from("Spa:Id")),
+                value: Some(String::from("42")),
+            })
+        );
+        assert_eq!(
+            MetadataProperty::parse("target.object="),
+            Some(MetadataProperty {
+                key: String::from("target.object"),
+                type_: None,
+                value: None,
+            })
+        );
+        assert_eq!(MetadataProperty::parse("target.node"), None);
+        assert_eq!(MetadataProperty::parse("=42"), None);
+    }
+
+    #[test]
+    fn metadata_input_needs_confirmation() {
+        let mut state = State::default();
+        let wirehose = mock::WirehoseHandle::default();
+        let object_id = ObjectId::from_raw_id(0);
+        create_node(&mut state, object_id, "Stream/Output/Audio", "stream");
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+
+        let mut object_list =
+            ObjectList::new(ListKind::Node(NodeKind::Playback), None);
+        object_list.selected = Some(object_id);
+        object_list.start_metadata_input();
+
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        for c in "restore=".chars() {
+            assert!(object_list.metadata_input(key(KeyCode::Char(c)), &view));
+        }
+        assert!(object_list.metadata_input(key(KeyCode::Enter), &view));
+        assert_eq!(object_list.metadata_input, None);
+        assert!(object_list.metadata_pending.is_some());
+        assert!(object_list.is_metadata_editing());
+
+        // Anything but y cancels.
+        assert!(object_list.metadata_input(key(KeyCode::Char('n')), &view));
+        assert!(!object_list.is_metadata_editing());
+    }
+
+    #[test]
+    fn collapse_groups_merges_client_streams() {
+        let (mut state, wirehose) = init();
+
+        for i in 1..4 {
+            let object_id = ObjectId::from_raw_id(i);
+            let mut props = state.nodes.get(&object_id).unwrap().props.clone();
+            props.set_client_id(ObjectId::from_raw_id(101));
+            state.update(StateEvent::NodeProperties { object_id, props });
+        }
+        state.update(StateEvent::NodeVolumes {
+            object_id: ObjectId::from_raw_id(2),
+            volumes: vec![1.0, 1.0],
+        });
+
+        let mut view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+        let client = view.clients.get(&ObjectId::from_raw_id(101)).unwrap();
+        assert_eq!(
+            client.nodes,
+            (1..4).map(ObjectId::from_raw_id).collect::<Vec<_>>()
+        );
+
+        // Expanded groups are left alone
+        view.collapse_groups(&HashSet::from([ObjectId::from_raw_id(101)]));
+        assert_eq!(view.nodes_playback.len(), 10);
+
+        view.collapse_groups(&HashSet::new());
+        assert_eq!(view.nodes_playback.len(), 8);
+        assert_eq!(view.nodes_all.len(), 8);
+        assert!(!view.nodes_playback.contains(&ObjectId::from_raw_id(2)));
+
+        let node = view.nodes.get(&ObjectId::from_raw_id(1)).unwrap();
+        assert_eq!(node.title, "Node name (3 streams)");
+        let group = node.group.as_ref().unwrap();
+        assert_eq!(group.members.len(), 3);
+        assert_eq!(group.volume, 1.0 / 3.0);
+        assert!(!group.mute);
+        assert_eq!(group.peaks.len(), 3);
+    }
+
+    #[test]
+    fn latency_from_node_latency() {
+        let (mut state, wirehose) = init();
+
+        for (i, latency) in [(1, "1024/48000"), (2, "441"), (3, "fast")] {
+            let object_id = ObjectId::from_raw_id(i);
+            let mut props = state.nodes.get(&object_id).unwrap().props.clone();
+            props.set_node_latency(String::from(latency));
+            state.update(StateEvent::NodeProperties { object_id, props });
+        }
+
+        let view = View::from(
+            &wirehose,
+            &state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        );
+        let latency = |i| {
+            view.nodes
+                .get(&ObjectId::from_raw_id(i))
+                .unwrap()
+                .latency_ms
+        };
+        assert_eq!(latency(1), Some(1024.0 * 1000.0 / 48000.0));
+        // A bare quantum uses the node's rate
+        assert_eq!(latency(2), Some(10.0));
+        assert_eq!(latency(3), None);
+        assert_eq!(latency(4), None);
+    }
 }
@@ -2,13 +2,13 @@
 
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 use crate::config::{self, TabKind};
 
 const VERSION: &str = concat!("v", env!("CARGO_PKG_VERSION"));
 
-#[derive(Parser, Default)]
+#[derive(Parser, Default, Clone)]
 #[clap(name = "wiremix", about = "PipeWire mixer")]
 #[command(version = VERSION)]
 pub struct Opt {
@@ -32,6 +32,11 @@ pub struct Opt {
     #[clap(short, long, value_name = "NAME")]
     pub theme: Option<String>,
 
+    /// Remove colors from the theme, keeping other styling (also set by the
+    /// NO_COLOR environment variable)
+    #[clap(long)]
+    pub no_color: bool,
+
     /// Audio peak meters
     #[clap(short, long, value_parser = clap::value_parser!(config::Peaks))]
     pub peaks: Option<config::Peaks>,
@@ -57,6 +62,14 @@ pub struct Opt {
     #[clap(short = 'T', long, num_args = 1.., value_enum)]
     pub tabs: Option<Vec<TabKind>>,
 
+    /// Select the node with this name or node.name once connected
+    #[clap(long, value_name = "NODE")]
+    pub select: Option<String>,
+
+    /// Exit after changing a volume, mute state, or default
+    #[clap(long)]
+    pub oneshot: bool,
+
     /// Maximum volume for volume sliders
     #[clap(short = 'm', long, value_name = "PERCENT")]
     pub max_volume_percent: Option<f32>,
@@ -69,6 +82,10 @@ pub struct Opt {
     #[clap(long, conflicts_with = "no_enforce_max_volume")]
     pub enforce_max_volume: bool,
 
+    /// Unit for displaying and adjusting volume
+    #[clap(short = 'u', long, value_enum)]
+    pub volume_unit: Option<config::VolumeUnit>,
+
     /// Monitor peak levels of all nodes
     #[clap(long, conflicts_with = "lazy_capture")]
     pub no_lazy_capture: bool,
@@ -78,9 +95,48 @@ pub struct Opt {
     #[clap(long, conflicts_with = "no_lazy_capture")]
     pub lazy_capture: bool,
 
+    /// Print the current mixer state as JSON and exit
+    #[clap(long)]
+    pub dump_state: bool,
+
+    /// Print fatal errors to stderr as JSON with a kind (connection, config,
+    /// or runtime) and a message
+    #[clap(long)]
+    pub json_errors: bool,
+
     #[cfg(debug_assertions)]
     #[clap(short, long)]
     pub dump_events: bool,
+
+    /// Run a single command without the UI
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum Command {
+    /// Set the volume of a node
+    SetVolume {
+        /// Name of the node as displayed, or its node.name
+        node: String,
+        /// Volume percentage
+        percent: f32,
+    },
+    /// Mute a node
+    Mute {
+        /// Name of the node as displayed, or its node.name
+        node: String,
+    },
+    /// Unmute a node
+    Unmute {
+        /// Name of the node as displayed, or its node.name
+        node: String,
+    },
+    /// Print a live peak meter for a node until interrupted
+    Meter {
+        /// Name of the node as displayed, or its node.name
+        node: String,
+    },
 }
 
 impl Opt {
@@ -0,0 +1,272 @@
+//! Remote control over OSC (Open Sound Control).
+//!
+//! [`spawn()`] starts a thread listening for OSC messages on a UDP socket.
+//! The supported messages are:
+//!
+//! - `/wiremix/node/<name>/volume <float>` sets the volume, where 1.0 is 100%
+//! - `/wiremix/node/<name>/mute [<int or bool>]` sets or toggles mute
+//! - `/wiremix/node/<name>/default` makes a sink or source the default
+//!
+//! Nodes are addressed by their displayed name or by node.name. Malformed or
+//! unsupported packets are ignored.
+
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use anyhow::{Context, Result};
+
+use crate::event::Event;
+
+/// Largest packet accepted. Anything longer is truncated and fails to parse.
+const MAX_PACKET_SIZE: usize = 1536;
+
+const ADDRESS_PREFIX: &str = "/wiremix/node/";
+
+/// A command received over OSC
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Volume {
+        node: String,
+        volume: f32,
+    },
+    /// Set mute to the given state, or toggle it if None
+    Mute {
+        node: String,
+        mute: Option<bool>,
+    },
+    SetDefault {
+        node: String,
+    },
+}
+
+impl Command {
+    /// The name of the node the command applies to.
+    pub fn node(&self) -> &str {
+        match self {
+            Command::Volume { node, .. }
+            | Command::Mute { node, .. }
+            | Command::SetDefault { node } => node,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Argument {
+    Int(i32),
+    Float(f32),
+    Bool(bool),
+}
+
+/// Spawns a thread to listen for OSC messages on addr.
+///
+/// Commands are sent to tx as [`Event::Osc`](`crate::event::Event::Osc`).
+/// The socket is bound before returning so that failures are reported to the
+/// caller.
+pub fn spawn(tx: Arc<mpsc::Sender<Event>>, addr: SocketAddr) -> Result<()> {
+    let socket = UdpSocket::bind(addr)
+        .with_context(|| format!("Failed to listen for OSC on {addr}"))?;
+
+    thread::spawn(move || {
+        let mut buf = [0u8; MAX_PACKET_SIZE];
+        while let Ok(len) = socket.recv(&mut buf) {
+            let Some(command) = parse(&buf[..len]) else {
+                continue;
+            };
+            if tx.send(Event::Osc(command)).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Parses an OSC message packet into a command.
+///
+/// Returns None for anything that isn't a well-formed, supported message.
+/// Bundles aren't supported.
+pub fn parse(packet: &[u8]) -> Option<Command> {
+    let (address, rest) = read_string(packet)?;
+    let (type_tags, mut rest) = read_string(rest)?;
+    let type_tags = type_tags.strip_prefix(',')?;
+
+    let mut arguments = Vec::new();
+    for tag in type_tags.chars() {
+        let argument = match tag {
+            'i' | 'f' => {
+                let bytes: [u8; 4] = rest.get(..4)?.try_into().ok()?;
+                rest = &rest[4..];
+                match tag {
+                    'i' => Argument::Int(i32::from_be_bytes(bytes)),
+                    _ => Argument::Float(f32::from_be_bytes(bytes)),
+                }
+            }
+            'T' => Argument::Bool(true),
+            'F' => Argument::Bool(false),
+            _ => return None,
+        };
+        arguments.push(argument);
+    }
+
+    let (node, method) =
+        address.strip_prefix(ADDRESS_PREFIX)?.rsplit_once('/')?;
+    if node.is_empty() {
+        return None;
+    }
+    let node = String::from(node);
+
+    match (method, arguments.as_slice()) {
+        ("volume", [argument]) => {
+            let volume = match *argument {
+                Argument::Float(volume) => volume,
+                Argument::Int(volume) => volume as f32,
+                Argument::Bool(_) => return None,
+            };
+            volume.is_finite().then_some(Command::Volume {
+                node,
+                volume: volume.max(0.0),
+            })
+        }
+        ("mute", []) => Some(Command::Mute { node, mute: None }),
+        ("mute", [argument]) => {
+            let mute = match *argument {
+                Argument::Bool(mute) => mute,
+                Argument::Int(mute) => mute != 0,
+                Argument::Float(mute) => mute != 0.0,
+            };
+            Some(Command::Mute {
+                node,
+                mute: Some(mute),
+            })
+        }
+        ("default", []) => Some(Command::SetDefault { node }),
+        _ => None,
+    }
+}
+
+/// Reads a null-terminated string padded to a multiple of 4 bytes, returning
+/// it and the remainder of the buffer.
+fn read_string(buf: &[u8]) -> Option<(&str, &[u8])> {
+    let len = buf.iter().position(|&b| b == 0)?;
+    let padded = (len + 4) & !3;
+    if padded > buf.len() {
+        return None;
+    }
+    let s = std::str::from_utf8(&buf[..len]).ok()?;
+    Some((s, &buf[padded..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(s: &str) -> Vec<u8> {
+        let mut bytes = s.as_bytes().to_vec();
+        bytes.push(0);
+        while bytes.len() % 4 != 0 {
+            bytes.push(0);
+        }
+        bytes
+    }
+
+    fn message(address: &str, type_tags: &str, args: &[[u8; 4]]) -> Vec<u8> {
+        let mut packet = string(address);
+        packet.extend(string(type_tags));
+        for arg in args {
+            packet.extend(arg);
+        }
+        packet
+    }
+
+    #[test]
+    fn volume() {
+        let packet = message(
+            "/wiremix/node/Speakers/volume",
+            ",f",
+            &[0.5f32.to_be_bytes()],
+        );
+        assert_eq!(
+            parse(&packet),
+            Some(Command::Volume {
+                node: String::from("Speakers"),
+                volume: 0.5,
+            })
+        );
+    }
+
+    #[test]
+    fn mute() {
+        let packet = message("/wiremix/node/Speakers/mute", ",", &[]);
+        assert_eq!(
+            parse(&packet),
+            Some(Command::Mute {
+                node: String::from("Speakers"),
+                mute: None,
+            })
+        );
+
+        let packet =
+            message("/wiremix/node/Speakers/mute", ",i", &[1i32.to_be_bytes()]);
+        assert_eq!(
+            parse(&packet),
+            Some(Command::Mute {
+                node: String::from("Speakers"),
+                mute: Some(true),
+            })
+        );
+
+        let packet = message("/wiremix/node/Speakers/mute", ",F", &[]);
+        assert_eq!(
+            parse(&packet),
+            Some(Command::Mute {
+                node: String::from("Speakers"),
+                mute: Some(false),
+            })
+        );
+    }
+
+    #[test]
+    fn set_default() {
+        let packet = message("/wiremix/node/alsa.output/default", ",", &[]);
+        assert_eq!(
+            parse(&packet),
+            Some(Command::SetDefault {
+                node: String::from("alsa.output"),
+            })
+        );
+    }
+
+    #[test]
+    fn bad_messages_are_ignored() {
+        // Unknown method
+        let packet = message("/wiremix/node/Speakers/pan", ",", &[]);
+        assert_eq!(parse(&packet), None);
+        // Missing argument
+        let packet = message("/wiremix/node/Speakers/volume", ",f", &[]);
+        assert_eq!(parse(&packet), None);
+        // Wrong argument type
+        let packet = message("/wiremix/node/Speakers/volume", ",T", &[]);
+        assert_eq!(parse(&packet), None);
+        // Unsupported argument type
+        let packet = message("/wiremix/node/Speakers/mute", ",s", &[]);
+        assert_eq!(parse(&packet), None);
+        // Non-finite volume
+        let packet = message(
+            "/wiremix/node/Speakers/volume",
+            ",f",
+            &[f32::NAN.to_be_bytes()],
+        );
+        assert_eq!(parse(&packet), None);
+        // Wrong prefix
+        let packet = message("/other/node/Speakers/mute", ",", &[]);
+        assert_eq!(parse(&packet), None);
+        // Empty name
+        let packet = message("/wiremix/node//mute", ",", &[]);
+        assert_eq!(parse(&packet), None);
+        // Truncated
+        let packet = message("/wiremix/node/Speakers/mute", ",", &[]);
+        assert_eq!(parse(&packet[..6]), None);
+        assert_eq!(parse(&[]), None);
+    }
+}
@@ -0,0 +1,70 @@
+//! Recent peak levels for drawing a meter's history.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+#[derive(Default, Debug)]
+struct History {
+    /// Number of channels the peaks were recorded from
+    channels: usize,
+    /// Loudest channel of each recorded peak, oldest first
+    peaks: VecDeque<f32>,
+}
+
+/// Keeps a bounded history of a meter's peaks.
+///
+/// This is updated while rendering, so it uses interior mutability.
+#[derive(Default, Debug)]
+pub struct PeakHistory {
+    history: RefCell<History>,
+}
+
+impl PeakHistory {
+    /// Records the loudest of peaks, keeping at most len of the most recent
+    /// values.
+    ///
+    /// The history starts over if the number of channels changes, since the
+    /// earlier peaks were measured from a different stream.
+    pub fn push(&self, peaks: &[f32], len: usize) {
+        let mut history = self.history.borrow_mut();
+        if history.channels != peaks.len() {
+            history.channels = peaks.len();
+            history.peaks.clear();
+        }
+
+        history
+            .peaks
+            .push_back(peaks.iter().copied().fold(0.0, f32::max));
+        while history.peaks.len() > len {
+            history.peaks.pop_front();
+        }
+    }
+
+    /// Returns the recorded peaks, oldest first.
+    pub fn peaks(&self) -> Vec<f32> {
+        self.history.borrow().peaks.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_most_recent() {
+        let history = PeakHistory::default();
+        for peak in [0.1, 0.2, 0.3, 0.4] {
+            history.push(&[peak, peak / 2.0], 3);
+        }
+        assert_eq!(history.peaks(), vec![0.2, 0.3, 0.4]);
+    }
+
+    #[test]
+    fn resets_on_channel_change() {
+        let history = PeakHistory::default();
+        history.push(&[0.1, 0.2], 3);
+        history.push(&[0.3, 0.4], 3);
+        history.push(&[0.5], 3);
+        assert_eq!(history.peaks(), vec![0.5]);
+    }
+}
@@ -0,0 +1,196 @@
+//! Peak-hold state for peak meters.
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+struct Held {
+    value: f32,
+    /// When the held value was reached
+    since: Instant,
+    /// When the held value was last updated
+    updated: Instant,
+}
+
+/// Tracks the maximum recent peak of each channel of a meter and how long its
+/// clip indicator stays lit.
+///
+/// This is updated while rendering, so it uses interior mutability.
+#[derive(Default, Debug)]
+pub struct PeakHold {
+    channels: RefCell<Vec<Option<Held>>>,
+    /// When each channel last clipped
+    clips: RefCell<Vec<Option<Instant>>>,
+}
+
+impl PeakHold {
+    /// Updates a channel with its current peak and returns the held peak.
+    ///
+    /// The held peak is kept for `hold` after it was reached and then falls
+    /// toward the current peak with time constant `decay`.
+    pub fn update(
+        &self,
+        channel: usize,
+        peak: f32,
+        hold: Duration,
+        decay: Duration,
+        now: Instant,
+    ) -> f32 {
+        let mut channels = self.channels.borrow_mut();
+        if channels.len() <= channel {
+            channels.resize(channel + 1, None);
+        }
+
+        let held = match channels[channel] {
+            Some(held) if held.value > peak => {
+                let value = if now.duration_since(held.since) < hold {
+                    held.value
+                } else if decay.is_zero() {
+                    peak
+                } else {
+                    let elapsed =
+                        now.duration_since(held.updated).as_secs_f32();
+                    peak + (held.value - peak)
+                        * (-elapsed / decay.as_secs_f32()).exp()
+                };
+                Held {
+                    value,
+                    since: held.since,
+                    updated: now,
+                }
+            }
+            _ => Held {
+                value: peak,
+                since: now,
+                updated: now,
+            },
+        };
+        channels[channel] = Some(held);
+
+        held.value
+    }
+
+    /// Latches a channel's clip indicator if the capture stream saw it clip
+    /// since the last call and returns whether the indicator is lit.
+    ///
+    /// The indicator stays lit until cleared, or for timeout after the most
+    /// recent clip if timeout isn't zero.
+    pub fn clip(
+        &self,
+        channel: usize,
+        clipped: bool,
+        timeout: Duration,
+        now: Instant,
+    ) -> bool {
+        let mut clips = self.clips.borrow_mut();
+        if clips.len() <= channel {
+            clips.resize(channel + 1, None);
+        }
+
+        if clipped {
+            clips[channel] = Some(now);
+        } else if clips[channel].is_some_and(|clipped| {
+            !timeout.is_zero() && now.duration_since(clipped) >= timeout
+        }) {
+            clips[channel] = None;
+        }
+
+        clips[channel].is_some()
+    }
+
+    /// Turns off all clip indicators.
+    pub fn clear_clips(&self) {
+        self.clips.borrow_mut().clear();
+    }
+
+    /// Drops the held peaks and turns off the clip indicators.
+    pub fn reset(&self) {
+        self.channels.borrow_mut().clear();
+        self.clear_clips();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOLD: Duration = Duration::from_millis(1500);
+    const DECAY: Duration = Duration::from_millis(300);
+
+    #[test]
+    fn holds_maximum() {
+        let peak_hold = PeakHold::default();
+        let start = Instant::now();
+
+        assert_eq!(peak_hold.update(0, 0.8, HOLD, DECAY, start), 0.8);
+        let now = start + Duration::from_millis(500);
+        assert_eq!(peak_hold.update(0, 0.2, HOLD, DECAY, now), 0.8);
+    }
+
+    #[test]
+    fn higher_peak_replaces_held() {
+        let peak_hold = PeakHold::default();
+        let start = Instant::now();
+
+        peak_hold.update(0, 0.5, HOLD, DECAY, start);
+        let now = start + Duration::from_millis(100);
+        assert_eq!(peak_hold.update(0, 0.9, HOLD, DECAY, now), 0.9);
+    }
+
+    #[test]
+    fn decays_after_hold() {
+        let peak_hold = PeakHold::default();
+        let start = Instant::now();
+
+        peak_hold.update(0, 0.8, HOLD, DECAY, start);
+        let now = start + HOLD - Duration::from_millis(1);
+        assert_eq!(peak_hold.update(0, 0.2, HOLD, DECAY, now), 0.8);
+
+        // After one time constant, about 37% of the difference remains.
+        let now = now + DECAY;
+        let held = peak_hold.update(0, 0.2, HOLD, DECAY, now);
+        assert!(held > 0.4 && held < 0.45);
+
+        // Instant decay without a time constant
+        let now = now + Duration::from_millis(100);
+        assert_eq!(peak_hold.update(0, 0.2, HOLD, Duration::ZERO, now), 0.2);
+    }
+
+    #[test]
+    fn clip_latches_until_cleared() {
+        let peak_hold = PeakHold::default();
+        let start = Instant::now();
+
+        assert!(!peak_hold.clip(0, false, Duration::ZERO, start));
+        assert!(peak_hold.clip(0, true, Duration::ZERO, start));
+        let now = start + Duration::from_secs(60);
+        assert!(peak_hold.clip(0, false, Duration::ZERO, now));
+        assert!(!peak_hold.clip(1, false, Duration::ZERO, now));
+
+        peak_hold.clear_clips();
+        assert!(!peak_hold.clip(0, false, Duration::ZERO, now));
+    }
+
+    #[test]
+    fn clip_times_out() {
+        let peak_hold = PeakHold::default();
+        let start = Instant::now();
+        let timeout = Duration::from_secs(5);
+
+        assert!(peak_hold.clip(0, true, timeout, start));
+        let now = start + Duration::from_secs(4);
+        assert!(peak_hold.clip(0, false, timeout, now));
+        let now = start + Duration::from_secs(5);
+        assert!(!peak_hold.clip(0, false, timeout, now));
+    }
+
+    #[test]
+    fn channels_are_independent() {
+        let peak_hold = PeakHold::default();
+        let start = Instant::now();
+
+        peak_hold.update(1, 0.8, HOLD, DECAY, start);
+        assert_eq!(peak_hold.update(0, 0.1, HOLD, DECAY, start), 0.1);
+        assert_eq!(peak_hold.update(1, 0.1, HOLD, DECAY, start), 0.8);
+    }
+}
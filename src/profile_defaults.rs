@@ -0,0 +1,234 @@
+//! Per-profile default sinks and sources to restore after profile changes.
+
+use std::collections::HashMap;
+
+use crate::device_kind::DeviceKind;
+use crate::view::{Node, Target, View};
+use crate::wirehose::ObjectId;
+
+const KINDS: [DeviceKind; 2] = [DeviceKind::Sink, DeviceKind::Source];
+
+/// A remembered default waiting to be restored.
+#[derive(Debug)]
+enum Pending {
+    /// Waiting for the node to appear under the new profile
+    Waiting { name: String },
+    /// Requested, and waiting for PipeWire to report the change. previous
+    /// is the default at the time of the request.
+    Requested { previous: Option<String> },
+}
+
+/// Remembers the default sink and source chosen under each device profile
+/// and restores them when the device switches back to that profile.
+///
+/// Switching profiles replaces a device's nodes, and the session manager
+/// doesn't always pick the same default again. This is best-effort, since
+/// the new nodes show up some time after the profile change.
+#[derive(Default, Debug)]
+pub struct ProfileDefaults {
+    /// Last seen profile index of each device
+    profiles: HashMap<ObjectId, i32>,
+    /// node.name of the default chosen under each device profile
+    defaults: HashMap<(ObjectId, i32, DeviceKind), String>,
+    /// Defaults to restore on devices whose profiles changed
+    pending: HashMap<(ObjectId, DeviceKind), Pending>,
+}
+
+impl ProfileDefaults {
+    /// Notices profile changes, requests the defaults remembered for the new
+    /// profiles, and records the current defaults.
+    pub fn update(&mut self, view: &View) {
+        for (&device_id, device) in &view.devices {
+            let Some(Target::Profile(_, profile)) = device.target else {
+                continue;
+            };
+            let previous = self.profiles.insert(device_id, profile);
+            if previous.map_or(true, |previous| previous == profile) {
+                continue;
+            }
+            for kind in KINDS {
+                match self.defaults.get(&(device_id, profile, kind)) {
+                    Some(name) => {
+                        let name = name.clone();
+                        self.pending.insert(
+                            (device_id, kind),
+                            Pending::Waiting { name },
+                        );
+                    }
+                    None => {
+                        self.pending.remove(&(device_id, kind));
+                    }
+                }
+            }
+        }
+
+        self.pending.retain(|&(device_id, kind), pending| {
+            restore(view, device_id, kind, pending)
+        });
+
+        // Don't mistake whatever was picked in the meantime for a choice.
+        for kind in KINDS {
+            let Some(node) = default_node(view, kind) else {
+                continue;
+            };
+            let Some((device_id, ..)) = node.device_info else {
+                continue;
+            };
+            if self.pending.contains_key(&(device_id, kind)) {
+                continue;
+            }
+            if let Some(&profile) = self.profiles.get(&device_id) {
+                self.defaults
+                    .insert((device_id, profile, kind), node.name.clone());
+            }
+        }
+    }
+}
+
+fn default_node(view: &View, kind: DeviceKind) -> Option<&Node> {
+    view.nodes.values().find(|node| match kind {
+        DeviceKind::Sink => node.is_default_sink,
+        DeviceKind::Source => node.is_default_source,
+    })
+}
+
+/// Requests a pending default once its node appears. Returns false once the
+/// default has been restored or something else has changed it.
+fn restore(
+    view: &View,
+    device_id: ObjectId,
+    kind: DeviceKind,
+    pending: &mut Pending,
+) -> bool {
+    let current = default_node(view, kind).map(|node| node.name.clone());
+    match pending {
+        Pending::Waiting { name } => {
+            if current.as_deref() == Some(name.as_str()) {
+                return false;
+            }
+            let appeared = view.nodes.values().any(|node| {
+                node.name == *name
+                    && node.device_info.is_some_and(|(id, ..)| id == device_id)
+            });
+            if appeared {
+                view.set_default_name(name, kind);
+                *pending = Pending::Requested { previous: current };
+            }
+            true
+        }
+        Pending::Requested { previous } => current == *previous,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+    use crate::view::Device;
+
+    fn device(view: &mut View, profile: i32) {
+        let device_id = ObjectId::from_raw_id(1);
+        view.devices.insert(
+            device_id,
+            Device {
+                object_id: device_id,
+                object_serial: 1,
+                title: String::from("Card"),
+                profiles: Vec::new(),
+                target_title: String::new(),
+                target: Some(Target::Profile(device_id, profile)),
+                routes: Vec::new(),
+            },
+        );
+    }
+
+    fn sink(view: &mut View, id: u32, name: &str, is_default: bool) {
+        let object_id = ObjectId::from_raw_id(id);
+        view.nodes.insert(
+            object_id,
+            Node {
+                object_id,
+                object_serial: id as u64,
+                name: String::from(name),
+                title: String::from(name),
+                media_class: String::from("Audio/Sink"),
+                routes: None,
+                target_title: String::new(),
+                target: None,
+                volumes: vec![1.0],
+                mute: false,
+                peaks: None,
+                clips: None,
+                peaks_dirty: Default::default(),
+                peak_hold: Default::default(),
+                peak_history: Default::default(),
+                capturing: false,
+                locked: false,
+                positions: None,
+                channel_names: None,
+                format: None,
+                latency_ms: None,
+                process: None,
+                role: None,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                device_info: Some((ObjectId::from_raw_id(1), 0, 0)),
+                is_default_sink: is_default,
+                is_default_source: false,
+                is_virtual: false,
+                is_network: false,
+                client_id: None,
+                group: None,
+            },
+        );
+    }
+
+    #[test]
+    fn restores_default_for_profile() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut defaults = ProfileDefaults::default();
+        let key = (ObjectId::from_raw_id(1), DeviceKind::Sink);
+
+        // The HDMI sink is chosen under profile 1.
+        let mut view = View::new(&wirehose);
+        device(&mut view, 1);
+        sink(&mut view, 10, "hdmi", true);
+        defaults.update(&view);
+
+        // Profile 2 picks the analog sink.
+        let mut view = View::new(&wirehose);
+        device(&mut view, 2);
+        sink(&mut view, 11, "analog", true);
+        defaults.update(&view);
+        assert!(defaults.pending.is_empty());
+
+        // Back on profile 1, the HDMI sink is requested once it appears.
+        let mut view = View::new(&wirehose);
+        device(&mut view, 1);
+        defaults.update(&view);
+        assert!(matches!(
+            defaults.pending.get(&key),
+            Some(Pending::Waiting { .. })
+        ));
+        sink(&mut view, 12, "analog", true);
+        sink(&mut view, 13, "hdmi", false);
+        defaults.update(&view);
+        assert!(matches!(
+            defaults.pending.get(&key),
+            Some(Pending::Requested { .. })
+        ));
+        // The analog sink isn't remembered for profile 1 while waiting.
+        assert_eq!(
+            defaults.defaults.get(&(key.0, 1, DeviceKind::Sink)),
+            Some(&String::from("hdmi"))
+        );
+
+        // Done once the change is seen.
+        let mut view = View::new(&wirehose);
+        device(&mut view, 1);
+        sink(&mut view, 12, "analog", false);
+        sink(&mut view, 13, "hdmi", true);
+        defaults.update(&view);
+        assert!(defaults.pending.is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+//! A popup for switching between PipeWire remotes while running.
+
+use std::env;
+use std::fs;
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
+
+use ratatui::widgets::ListState;
+
+/// The remotes to choose from and which one is highlighted.
+#[derive(Debug)]
+pub struct RemotePicker {
+    /// Remote names, with None for PipeWire's default remote
+    pub remotes: Vec<Option<String>>,
+    pub list_state: ListState,
+}
+
+impl RemotePicker {
+    /// Offers the default remote, the current one, the configured ones, and
+    /// the sockets found in the PipeWire runtime directory. The current
+    /// remote is highlighted.
+    pub fn new(configured: &[String], current: Option<&str>) -> Self {
+        let found = runtime_dir().map(|dir| sockets(&dir)).unwrap_or_default();
+        Self::with_remotes(configured, found, current, &default_remote())
+    }
+
+    fn with_remotes(
+        configured: &[String],
+        found: Vec<String>,
+        current: Option<&str>,
+        default: &str,
+    ) -> Self {
+        let mut remotes = vec![None];
+        // The default entry already stands for the default socket.
+        let found = found.into_iter().filter(|name| name != default);
+        let names = current
+            .map(String::from)
+            .into_iter()
+            .chain(configured.iter().cloned())
+            .chain(found);
+        for name in names {
+            if !remotes.iter().any(|remote| remote.as_ref() == Some(&name)) {
+                remotes.push(Some(name));
+            }
+        }
+        let index = remotes
+            .iter()
+            .position(|remote| remote.as_deref() == current)
+            .unwrap_or_default();
+        Self {
+            remotes,
+            list_state: ListState::default().with_selected(Some(index)),
+        }
+    }
+
+    /// Returns the title of each remote.
+    pub fn titles(&self) -> Vec<String> {
+        self.remotes
+            .iter()
+            .map(|remote| match remote {
+                Some(name) => name.clone(),
+                None => format!("Default ({})", default_remote()),
+            })
+            .collect()
+    }
+
+    /// Returns the highlighted remote.
+    pub fn selected(&self) -> Option<&Option<String>> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.remotes.get(index))
+    }
+}
+
+/// Returns the name of the remote PipeWire connects to by default.
+fn default_remote() -> String {
+    env::var("PIPEWIRE_REMOTE").unwrap_or_else(|_| String::from("pipewire-0"))
+}
+
+/// Returns the directory PipeWire creates its sockets in.
+fn runtime_dir() -> Option<PathBuf> {
+    ["PIPEWIRE_RUNTIME_DIR", "XDG_RUNTIME_DIR"]
+        .into_iter()
+        .find_map(env::var_os)
+        .map(PathBuf::from)
+}
+
+/// Returns the names of the PipeWire sockets in dir, sorted, leaving out the
+/// session manager sockets.
+fn sockets(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut sockets: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_ok_and(|file_type| file_type.is_socket())
+        })
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| {
+            name.starts_with("pipewire-") && !name.ends_with("-manager")
+        })
+        .collect();
+    sockets.sort();
+    sockets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remotes_listed_once() {
+        let picker = RemotePicker::with_remotes(
+            &[String::from("remote-box"), String::from("pipewire-0")],
+            vec![String::from("pipewire-0"), String::from("pipewire-1")],
+            Some("pipewire-1"),
+            "pipewire-2",
+        );
+        assert_eq!(
+            picker.remotes,
+            vec![
+                None,
+                Some(String::from("pipewire-1")),
+                Some(String::from("remote-box")),
+                Some(String::from("pipewire-0")),
+            ]
+        );
+        assert_eq!(picker.selected(), Some(&Some(String::from("pipewire-1"))));
+
+        let picker =
+            RemotePicker::with_remotes(&[], Vec::new(), None, "pipewire-0");
+        assert_eq!(picker.selected(), Some(&None));
+    }
+
+    #[test]
+    fn default_socket_not_repeated() {
+        let picker = RemotePicker::with_remotes(
+            &[],
+            vec![String::from("pipewire-0"), String::from("pipewire-1")],
+            None,
+            "pipewire-0",
+        );
+        assert_eq!(
+            picker.remotes,
+            vec![None, Some(String::from("pipewire-1"))]
+        );
+    }
+
+    #[test]
+    fn sockets_skip_managers() {
+        let dir = env::temp_dir()
+            .join(format!("wiremix-sockets-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["pipewire-0", "pipewire-0-manager", "pipewire-1"] {
+            std::os::unix::net::UnixListener::bind(dir.join(name)).unwrap();
+        }
+        fs::write(dir.join("pipewire-2"), "").unwrap();
+
+        let found = sockets(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            found,
+            vec![String::from("pipewire-0"), String::from("pipewire-1")]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Volumes and mute states remembered across restarts by node name.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::view::{Node, View};
+use crate::wirehose::ObjectId;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    volumes: Vec<f32>,
+    mute: bool,
+}
+
+impl From<&Node> for Entry {
+    fn from(node: &Node) -> Self {
+        Self {
+            volumes: node.volumes.clone(),
+            mute: node.mute,
+        }
+    }
+}
+
+/// What is known about a node currently present.
+#[derive(Debug)]
+enum Seen {
+    /// A restore was requested, and is waiting for PipeWire to report a
+    /// change from previous.
+    Restoring { previous: Entry },
+    /// Changes are recorded
+    Tracking,
+}
+
+/// Records the last volume and mute state of each node by node.name, and
+/// restores them when a node with the same name appears, such as after a
+/// device reconnects or wiremix restarts.
+#[derive(Default, Debug)]
+pub struct SavedVolumes {
+    /// Where entries are loaded from and saved to
+    path: Option<PathBuf>,
+    /// Last known state of each node.name
+    entries: HashMap<String, Entry>,
+    /// Nodes present in the last update
+    nodes: HashMap<ObjectId, Seen>,
+    /// Have entries changed since loading or saving?
+    dirty: bool,
+}
+
+impl SavedVolumes {
+    /// Returns the state file path.
+    pub fn default_path() -> Option<PathBuf> {
+        if let Ok(xdg_data) = env::var("XDG_DATA_HOME") {
+            return Some(Path::new(&xdg_data).join("wiremix/volumes.json"));
+        }
+
+        if let Ok(home) = env::var("HOME") {
+            return Some(
+                Path::new(&home).join(".local/share/wiremix/volumes.json"),
+            );
+        }
+
+        None
+    }
+
+    /// Loads entries from the file at path. A missing or corrupt file is
+    /// treated as empty, and is replaced on the next save.
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let entries = path
+            .as_deref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| parse(&contents))
+            .unwrap_or_default();
+        Self {
+            path,
+            entries,
+            ..Default::default()
+        }
+    }
+
+    /// Writes entries to the file if they have changed.
+    pub fn save(&mut self) -> Result<()> {
+        let Some(path) = self.path.as_deref().filter(|_| self.dirty) else {
+            return Ok(());
+        };
+        let context =
+            || format!("Failed to save volumes to '{}'", path.display());
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(context)?;
+        }
+        let contents = serde_json::to_string(&self.entries)?;
+        // Write to a temporary file first so that the state file is never
+        // left half-written.
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, contents).with_context(context)?;
+        fs::rename(&temp_path, path).with_context(context)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Restores the saved state of nodes that have appeared and records the
+    /// state of the others.
+    pub fn update(&mut self, view: &View) {
+        self.nodes
+            .retain(|node_id, _| view.nodes.contains_key(node_id));
+
+        for (&node_id, node) in &view.nodes {
+            // Wait until the volumes have been reported.
+            if node.volumes.is_empty() {
+                continue;
+            }
+            let current = Entry::from(node);
+
+            match self.nodes.get(&node_id) {
+                None => {
+                    let restoring = !node.locked
+                        && self.entries.get(&node.name).is_some_and(|saved| {
+                            restore(view, node_id, node, saved)
+                        });
+                    if restoring {
+                        self.nodes.insert(
+                            node_id,
+                            Seen::Restoring { previous: current },
+                        );
+                        continue;
+                    }
+                    self.nodes.insert(node_id, Seen::Tracking);
+                }
+                Some(Seen::Restoring { previous }) => {
+                    if *previous == current {
+                        continue;
+                    }
+                    self.nodes.insert(node_id, Seen::Tracking);
+                }
+                Some(Seen::Tracking) => {}
+            }
+
+            if self.entries.get(&node.name) != Some(&current) {
+                self.entries.insert(node.name.clone(), current);
+                self.dirty = true;
+            }
+        }
+    }
+}
+
+/// Parses the state file contents, ignoring them if they are corrupt.
+fn parse(contents: &str) -> HashMap<String, Entry> {
+    serde_json::from_str(contents).unwrap_or_default()
+}
+
+/// Requests the saved state for a node. Volumes are only restored if the
+/// channel count is the same. Returns false if there was nothing to request.
+fn restore(view: &View, node_id: ObjectId, node: &Node, saved: &Entry) -> bool {
+    let mut requested = false;
+    if saved.volumes.len() == node.volumes.len()
+        && saved.volumes != node.volumes
+    {
+        requested |= view.set_volumes(node_id, saved.volumes.clone());
+    }
+    if saved.mute != node.mute {
+        // View::mute() toggles
+        view.mute(node_id);
+        requested = true;
+    }
+    requested
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+    use crate::mock;
+    use crate::wirehose::{state::State, PropertyStore, StateEvent};
+
+    fn view_with<'a>(
+        wirehose: &'a mock::WirehoseHandle<'a>,
+        state: &mut State,
+        node_id: ObjectId,
+        volumes: Vec<f32>,
+        mute: bool,
+    ) -> View<'a> {
+        let mut props = PropertyStore::default();
+        props.set_media_class(String::from("Audio/Sink"));
+        props.set_node_name(String::from("sink"));
+        props.set_object_serial(0);
+        state.update(StateEvent::NodeProperties {
+            object_id: node_id,
+            props,
+        });
+        state.update(StateEvent::NodeVolumes {
+            object_id: node_id,
+            volumes,
+        });
+        state.update(StateEvent::NodeMute {
+            object_id: node_id,
+            mute,
+        });
+        View::from(
+            wirehose,
+            state,
+            &config::Names::default(),
+            &Vec::new(),
+            &config::Sort::default(),
+            &Default::default(),
+        )
+    }
+
+    #[test]
+    fn restores_reappearing_node() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut saved = SavedVolumes::default();
+
+        // The first node is recorded as it changes.
+        let mut state = State::default();
+        let node_id = ObjectId::from_raw_id(0);
+        let view =
+            view_with(&wirehose, &mut state, node_id, vec![0.5, 0.5], false);
+        saved.update(&view);
+        let view =
+            view_with(&wirehose, &mut state, node_id, vec![0.2, 0.2], true);
+        saved.update(&view);
+        assert!(saved.dirty);
+
+        // It comes back under a new ID at the default volume, which isn't
+        // recorded while the restore is pending.
+        let mut state = State::default();
+        let node_id = ObjectId::from_raw_id(1);
+        let view =
+            view_with(&wirehose, &mut state, node_id, vec![1.0, 1.0], false);
+        saved.update(&view);
+        assert!(matches!(
+            saved.nodes.get(&node_id),
+            Some(Seen::Restoring { .. })
+        ));
+        assert_eq!(
+            saved.entries.get("sink"),
+            Some(&Entry {
+                volumes: vec![0.2, 0.2],
+                mute: true,
+            })
+        );
+
+        // Tracking resumes once the restore takes effect.
+        let view =
+            view_with(&wirehose, &mut state, node_id, vec![0.2, 0.2], true);
+        saved.update(&view);
+        assert!(matches!(saved.nodes.get(&node_id), Some(Seen::Tracking)));
+    }
+
+    #[test]
+    fn corrupt_file_is_empty() {
+        assert!(parse("{ not json").is_empty());
+        assert_eq!(
+            parse(r#"{"sink":{"volumes":[0.5],"mute":false}}"#).get("sink"),
+            Some(&Entry {
+                volumes: vec![0.5],
+                mute: false,
+            })
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Handling of Unix signals.
+//!
+//! [`spawn()`] starts the signal thread.
+
+use std::fs::File;
+use std::io;
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use anyhow::Result;
+use nix::sys::signal::{SigSet, Signal};
+use nix::sys::termios;
+
+use crate::config::Config;
+use crate::event::Event;
+
+/// Spawns a thread to reload the configuration on SIGHUP.
+///
+/// The configuration is parsed by reload and the result is sent to tx as an
+/// [`Event::ReloadConfig`](`crate::event::Event::ReloadConfig`). If the SIGHUP
+/// came from the terminal going away, [`Event::Hangup`] is sent instead.
+///
+/// SIGHUP is blocked in the calling thread so that only the signal thread
+/// receives it. This must be called before spawning any other threads so they
+/// inherit the signal mask.
+pub fn spawn<F>(tx: Arc<mpsc::Sender<Event>>, reload: F) -> Result<()>
+where
+    F: Fn() -> Result<Config> + Send + 'static,
+{
+    let mut mask = SigSet::empty();
+    mask.add(Signal::SIGHUP);
+    mask.thread_block()?;
+
+    thread::spawn(move || {
+        while mask.wait().is_ok() {
+            // Check the terminal wiremix draws on, whatever stdin is.
+            let event = hangup_event(File::open("/dev/tty"), &reload);
+            if tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Returns the event for a SIGHUP, given the controlling terminal as opened
+/// from /dev/tty. The kernel sends SIGHUP when the controlling terminal hangs
+/// up, after which it can't be opened and no longer belongs to a session.
+/// Otherwise, the signal was sent to ask for a reload.
+fn hangup_event<F>(terminal: io::Result<File>, reload: &F) -> Event
+where
+    F: Fn() -> Result<Config>,
+{
+    if terminal.map_or(true, |terminal| termios::tcgetsid(terminal).is_err()) {
+        return Event::Hangup;
+    }
+    Event::ReloadConfig(reload().map(Box::new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nix::errno::Errno;
+    use std::env;
+    use std::process::{Command, Stdio};
+
+    #[test]
+    fn hangup_without_terminal() {
+        let reload = || Ok(Config::from_toml_str(""));
+
+        // The controlling terminal is gone.
+        let terminal = Err(io::Error::from(Errno::ENXIO));
+        assert!(matches!(hangup_event(terminal, &reload), Event::Hangup));
+
+        // Not a terminal at all
+        let null = File::open("/dev/null");
+        assert!(matches!(hangup_event(null, &reload), Event::Hangup));
+    }
+
+    #[test]
+    fn reload_with_stdin_redirected() {
+        // Only possible with a controlling terminal to reload for.
+        if File::open("/dev/tty").is_err() {
+            return;
+        }
+        let status = Command::new(env::current_exe().unwrap())
+            .args(["--exact", "signal::tests::reload_in_child", "--ignored"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    #[ignore = "run by reload_with_stdin_redirected"]
+    fn reload_in_child() {
+        let reload = || Ok(Config::from_toml_str(""));
+        let terminal = File::open("/dev/tty");
+        if terminal.is_ok() {
+            assert!(matches!(
+                hangup_event(terminal, &reload),
+                Event::ReloadConfig(Ok(_))
+            ));
+        }
+    }
+}
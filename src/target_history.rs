@@ -0,0 +1,98 @@
+//! Per-node history of targets for toggling between the last two.
+
+use std::collections::HashMap;
+
+use crate::view::{Target, View};
+
+/// Remembers the last two distinct targets of each node.
+///
+/// History is keyed by node.name rather than object ID so that it survives a
+/// node disappearing and reappearing, as happens when an application restarts
+/// playback.
+#[derive(Default, Debug)]
+pub struct TargetHistory {
+    /// The most recent target followed by the one before it
+    history: HashMap<String, [Option<Target>; 2]>,
+}
+
+impl TargetHistory {
+    /// Record the current targets of all nodes in the view.
+    pub fn update(&mut self, view: &View) {
+        for node in view.nodes.values() {
+            if let Some(target) = node.target {
+                self.record(&node.name, target);
+            }
+        }
+    }
+
+    /// Record a node's new target. Does nothing if the target is the same as
+    /// the most recent one.
+    pub fn record(&mut self, name: &str, target: Target) {
+        let entry = self.history.entry(String::from(name)).or_default();
+        if entry[0] != Some(target) {
+            *entry = [Some(target), entry[0]];
+        }
+    }
+
+    /// Returns the target a node had before its current one.
+    pub fn previous(&self, name: &str) -> Option<Target> {
+        self.history.get(name).and_then(|entry| entry[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wirehose::ObjectId;
+
+    fn sink(id: u32) -> Target {
+        Target::Node(ObjectId::from_raw_id(id))
+    }
+
+    #[test]
+    fn no_previous_for_single_target() {
+        let mut history = TargetHistory::default();
+        history.record("node", sink(1));
+        assert_eq!(history.previous("node"), None);
+    }
+
+    #[test]
+    fn previous_is_last_distinct_target() {
+        let mut history = TargetHistory::default();
+        history.record("node", sink(1));
+        history.record("node", sink(2));
+        assert_eq!(history.previous("node"), Some(sink(1)));
+
+        // Repeating the current target doesn't affect history.
+        history.record("node", sink(2));
+        assert_eq!(history.previous("node"), Some(sink(1)));
+
+        history.record("node", sink(3));
+        assert_eq!(history.previous("node"), Some(sink(2)));
+    }
+
+    #[test]
+    fn toggling_alternates() {
+        let mut history = TargetHistory::default();
+        history.record("node", sink(1));
+        history.record("node", sink(2));
+
+        let previous = history.previous("node").unwrap();
+        history.record("node", previous);
+        assert_eq!(history.previous("node"), Some(sink(2)));
+
+        let previous = history.previous("node").unwrap();
+        history.record("node", previous);
+        assert_eq!(history.previous("node"), Some(sink(1)));
+    }
+
+    #[test]
+    fn history_is_per_node() {
+        let mut history = TargetHistory::default();
+        history.record("a", sink(1));
+        history.record("a", sink(2));
+        history.record("b", sink(3));
+        assert_eq!(history.previous("a"), Some(sink(1)));
+        assert_eq!(history.previous("b"), None);
+    }
+}
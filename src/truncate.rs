@@ -0,0 +1,63 @@
+//! Shortening strings to fit a number of terminal columns.
+
+use std::borrow::Cow;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const ELLIPSES: &str = "...";
+
+/// Truncates s so that it is at most width columns wide, replacing the end
+/// with "..." if anything was removed. Widths are measured in display
+/// columns, so wide characters count as two.
+pub fn with_ellipses(s: &str, width: usize) -> Cow<'_, str> {
+    if s.width() <= width {
+        return Cow::Borrowed(s);
+    }
+
+    let Some(available) = width.checked_sub(ELLIPSES.len()) else {
+        // Not even the ellipses fit.
+        return Cow::Owned(ELLIPSES[..width].to_string());
+    };
+
+    let mut result = String::new();
+    let mut used = 0;
+    for ch in s.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width > available {
+            break;
+        }
+        used += ch_width;
+        result.push(ch);
+    }
+    result.push_str(ELLIPSES);
+
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits() {
+        assert_eq!(with_ellipses("hello", 5), "hello");
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(with_ellipses("hello world", 8), "hello...");
+    }
+
+    #[test]
+    fn wide_characters() {
+        // Each of these is two columns wide.
+        assert_eq!(with_ellipses("日本語のテキスト", 8), "日本...");
+        assert_eq!(with_ellipses("日本語のテキスト", 9), "日本語...");
+    }
+
+    #[test]
+    fn narrower_than_ellipses() {
+        assert_eq!(with_ellipses("hello", 2), "..");
+        assert_eq!(with_ellipses("hello", 0), "");
+    }
+}
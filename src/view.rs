@@ -1,16 +1,21 @@
 //! View representing PipeWire state in a convenient format for rendering.
 
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 use std::sync::atomic::AtomicBool;
 
 use std::sync::Arc;
 
+use serde::Serialize;
 use serde_json::json;
 
 use crate::atomic_f32::AtomicF32;
 use crate::config::{self, property_key::PropertyResolver};
 use crate::device_kind::DeviceKind;
+use crate::peak_history::PeakHistory;
+use crate::peak_hold::PeakHold;
 use crate::wirehose::{media_class, state, CommandSender, ObjectId};
 
 /// A view for transforming [`State`](`state::State`) into a better format for
@@ -30,11 +35,16 @@ pub struct View<'a> {
     pub nodes_all: Vec<ObjectId>,
     pub nodes_playback: Vec<ObjectId>,
     pub nodes_recording: Vec<ObjectId>,
+    /// Playback and recording streams together
+    pub nodes_streams: Vec<ObjectId>,
     pub nodes_output: Vec<ObjectId>,
     pub nodes_input: Vec<ObjectId>,
 
     pub devices_all: Vec<ObjectId>,
 
+    /// Clients with stream nodes, for grouping streams by application
+    pub clients: HashMap<ObjectId, Client>,
+
     pub sinks: Vec<(Target, String)>,
     pub sources: Vec<(Target, String)>,
 
@@ -42,9 +52,28 @@ pub struct View<'a> {
     pub default_source: Option<Target>,
 
     pub metadata_id: Option<ObjectId>,
+
+    /// Volume percentage that adjustments are clamped to at the bottom
+    pub min_volume_percent: f32,
+    /// Whether moving past either end of a list continues from the other
+    pub wrap_navigation: bool,
+    /// Metadata keys written by set_default()
+    pub default_keys: config::DefaultKeys,
+}
+
+/// What [`View::from`] leaves out of its lists
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Hidden {
+    /// Sinks' monitors, from the sources offered as recording targets
+    pub monitor_sources: bool,
+    /// Muted streams, from the playback, recording, and streams lists
+    pub muted_streams: bool,
+    /// Streams that have no peaks because they aren't being captured, from
+    /// the playback, recording, and streams lists
+    pub idle_streams: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
 pub enum Target {
     Node(ObjectId),
     Route(ObjectId, i32, i32),
@@ -79,7 +108,7 @@ impl Target {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Node {
     pub object_id: ObjectId,
     pub object_serial: u64,
@@ -87,7 +116,8 @@ pub struct Node {
     pub title: String,
     pub media_class: String,
 
-    pub routes: Option<Vec<(Target, String)>>,
+    /// Route targets, their titles, and whether they are available
+    pub routes: Option<Vec<(Target, String, bool)>>,
 
     pub target_title: String,
     pub target: Option<Target>,
@@ -95,9 +125,44 @@ pub struct Node {
     pub volumes: Vec<f32>,
     pub mute: bool,
 
+    #[serde(skip)]
     pub peaks: Option<Arc<[AtomicF32]>>,
+    /// Clip latches set by the capture stream
+    #[serde(skip)]
+    pub clips: Option<Arc<[AtomicBool]>>,
+    #[serde(skip)]
     pub peaks_dirty: Arc<AtomicBool>,
+    /// Shared with the previous View so that it survives rebuilds
+    #[serde(skip)]
+    pub peak_hold: Rc<PeakHold>,
+    /// Recent peaks, shared with the previous View like peak_hold
+    #[serde(skip)]
+    pub peak_history: Rc<PeakHistory>,
+    /// Whether peaks are being captured, so that an idle meter can be told
+    /// apart from a silent one
+    #[serde(skip)]
+    pub capturing: bool,
+    /// Whether volume adjustments are rejected
+    #[serde(skip)]
+    pub locked: bool,
     pub positions: Option<Vec<u32>>,
+    /// Labels for each channel from node.channel-names. Empty names are kept
+    /// so that the rest line up with their channels.
+    pub channel_names: Option<Vec<String>>,
+    /// Sample rate, channel count, and sample format, e.g. "48000 Hz, 2ch,
+    /// F32LE", omitting any that aren't known
+    pub format: Option<String>,
+    /// Latency in milliseconds from node.latency
+    pub latency_ms: Option<f32>,
+    /// Binary name and PID of the process owning a stream, e.g. "firefox,
+    /// PID 1234", omitting any that aren't known
+    pub process: Option<String>,
+    /// media.role, or media.category if there is no role
+    pub role: Option<String>,
+    /// Titles of the nodes linked into this one
+    pub inputs: Vec<String>,
+    /// Titles of the nodes this one is linked to
+    pub outputs: Vec<String>,
 
     /// If this is a device/endpoint node, store the (device_id, route_index,
     /// card_device) here because they are needed for changing volumes and
@@ -107,25 +172,267 @@ pub struct Node {
     pub is_default_sink: bool,
     pub is_default_source: bool,
 
+    /// node.virtual, set for nodes like null sinks and loopbacks
+    pub is_virtual: bool,
+    /// node.network, set for nodes streaming over the network
+    pub is_network: bool,
+
     pub client_id: Option<ObjectId>,
+
+    /// Set if this node stands in for its client's collapsed group of
+    /// streams
+    #[serde(skip)]
+    pub group: Option<Group>,
 }
 
+/// A client and the stream nodes belonging to it.
+#[derive(Debug, Serialize)]
+pub struct Client {
+    pub object_id: ObjectId,
+    /// The application name, or the node.name of its first stream
+    pub title: String,
+    /// Stream node IDs in creation order
+    pub nodes: Vec<ObjectId>,
+}
+
+/// Streams of one client collapsed into a single list entry.
 #[derive(Debug)]
+pub struct Group {
+    /// Member stream IDs, including the node representing the group
+    pub members: Vec<ObjectId>,
+    /// Mean volume of the members
+    pub volume: f32,
+    /// True if every member is muted
+    pub mute: bool,
+    pub peaks: Vec<Arc<[AtomicF32]>>,
+    pub clips: Vec<Arc<[AtomicBool]>>,
+    pub peaks_dirty: Vec<Arc<AtomicBool>>,
+}
+
+impl Group {
+    fn new(members: &[&Node]) -> Self {
+        let volume = members.iter().map(|node| node.mean_volume()).sum::<f32>()
+            / members.len() as f32;
+        Self {
+            members: members.iter().map(|node| node.object_id).collect(),
+            volume,
+            mute: members.iter().all(|node| node.mute),
+            peaks: members
+                .iter()
+                .filter_map(|node| node.peaks.as_ref().map(Arc::clone))
+                .collect(),
+            clips: members
+                .iter()
+                .filter_map(|node| node.clips.as_ref().map(Arc::clone))
+                .collect(),
+            peaks_dirty: members
+                .iter()
+                .map(|node| Arc::clone(&node.peaks_dirty))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
 pub struct Device {
     pub object_id: ObjectId,
     pub object_serial: u64,
     pub title: String,
 
-    pub profiles: Vec<(Target, String)>,
+    /// Profile targets, their titles, and whether they are available
+    pub profiles: Vec<(Target, String, bool)>,
 
     pub target_title: String,
     pub target: Option<Target>,
+
+    /// Routes in use by the device's card devices under its current profile
+    pub routes: Vec<DeviceRoute>,
+}
+
+/// A route in use by one of a device's card devices.
+#[derive(Debug, Serialize)]
+pub struct DeviceRoute {
+    pub index: i32,
+    pub card_device: i32,
+    pub description: String,
+    pub available: bool,
+    pub volumes: Vec<f32>,
+    pub mute: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum VolumeAdjustment {
     Relative(f32),
+    /// Relative adjustment in decibels
+    RelativeDecibels(f32),
     Absolute(f32),
+    /// Multiply every channel's linear volume
+    Scale(f32),
+    /// Set a single channel, leaving the others unchanged
+    Channel {
+        index: usize,
+        value: f32,
+    },
+    /// Shift the balance between a left and a right channel toward the right
+    /// by delta, leaving the other channels unchanged. Linked channels keep
+    /// the louder one fixed. Otherwise volume moves from one side to the
+    /// other.
+    Balance {
+        left: usize,
+        right: usize,
+        delta: f32,
+        linked: bool,
+    },
+    /// Even out a left and a right channel. Linked channels are raised to
+    /// the louder one. Otherwise both are set to their mean.
+    CenterBalance {
+        left: usize,
+        right: usize,
+        linked: bool,
+    },
+}
+
+/// Relative adjustments in decibels treat anything quieter than this as
+/// silence.
+pub const MIN_DECIBELS: f32 = -90.0;
+
+/// Applies adjustment to volumes, raising any below the min percentage to it.
+/// Returns None if there are no volumes, the adjustment doesn't apply or
+/// isn't a number, or the result would exceed max.
+fn adjusted_volumes(
+    volumes: &[f32],
+    adjustment: VolumeAdjustment,
+    max: Option<f32>,
+    min: f32,
+) -> Option<Vec<f32>> {
+    if volumes.is_empty() {
+        return None;
+    }
+    // f32::max() ignores NaN, so a NaN adjustment would otherwise quietly
+    // become silence.
+    let value = match adjustment {
+        VolumeAdjustment::Relative(value)
+        | VolumeAdjustment::RelativeDecibels(value)
+        | VolumeAdjustment::Absolute(value)
+        | VolumeAdjustment::Scale(value)
+        | VolumeAdjustment::Channel { value, .. }
+        | VolumeAdjustment::Balance { delta: value, .. } => value,
+        VolumeAdjustment::CenterBalance { .. } => 0.0,
+    };
+    if !value.is_finite() {
+        return None;
+    }
+    let mut volumes = volumes.to_vec();
+    match adjustment {
+        VolumeAdjustment::Relative(delta) => {
+            let avg = volumes.iter().sum::<f32>() / volumes.len() as f32;
+            volumes.fill((avg.cbrt() + delta).max(0.0).powi(3));
+        }
+        VolumeAdjustment::RelativeDecibels(delta) => {
+            let avg = volumes.iter().sum::<f32>() / volumes.len() as f32;
+            let db = volume_to_decibels(avg).max(MIN_DECIBELS) + delta;
+            volumes.fill(if db <= MIN_DECIBELS {
+                0.0
+            } else {
+                decibels_to_volume(db)
+            });
+        }
+        VolumeAdjustment::Absolute(volume) => {
+            volumes.fill(volume.max(0.0).powi(3));
+        }
+        VolumeAdjustment::Scale(factor) => {
+            volumes
+                .iter_mut()
+                .for_each(|volume| *volume *= factor.max(0.0));
+        }
+        VolumeAdjustment::Channel { index, value } => {
+            *volumes.get_mut(index)? = value.max(0.0).powi(3);
+        }
+        VolumeAdjustment::Balance {
+            left,
+            right,
+            delta,
+            linked,
+        } => {
+            let (l, r) =
+                (volumes.get(left)?.cbrt(), volumes.get(right)?.cbrt());
+            let (l, r) = if linked {
+                linked_balance(l, r, delta)
+            } else {
+                ((l - delta).max(0.0), (r + delta).max(0.0))
+            };
+            volumes[left] = l.powi(3);
+            volumes[right] = r.powi(3);
+        }
+        VolumeAdjustment::CenterBalance {
+            left,
+            right,
+            linked,
+        } => {
+            let (l, r) =
+                (volumes.get(left)?.cbrt(), volumes.get(right)?.cbrt());
+            let volume = if linked { l.max(r) } else { (l + r) / 2.0 };
+            volumes[left] = volume.powi(3);
+            volumes[right] = volume.powi(3);
+        }
+    }
+
+    // Clamp to the floor rather than refusing the adjustment, so that
+    // decreases still reach it.
+    if min > 0.0 {
+        let floor = (min / 100.0).powi(3);
+        for volume in volumes.iter_mut() {
+            *volume = volume.max(floor);
+        }
+    }
+    let volumes = volumes;
+
+    if volumes.iter().any(|volume| !volume.is_finite()) {
+        return None;
+    }
+
+    if let Some(max) = max {
+        if volumes
+            .iter()
+            .any(|volume| (volume.cbrt() * 100.0).round() > max)
+        {
+            return None;
+        }
+    }
+
+    Some(volumes)
+}
+
+/// Shifts the balance of cube-root-scaled left and right volumes toward the
+/// right by delta while the louder side stays at its volume.
+fn linked_balance(left: f32, right: f32, delta: f32) -> (f32, f32) {
+    let volume = left.max(right);
+    if volume <= 0.0 {
+        return (left, right);
+    }
+    // -1.0 is fully left, 1.0 is fully right
+    let balance = if right >= left {
+        1.0 - left / volume
+    } else {
+        right / volume - 1.0
+    };
+    let balance = (balance + delta).clamp(-1.0, 1.0);
+    if balance >= 0.0 {
+        (volume * (1.0 - balance), volume)
+    } else {
+        (volume, volume * (1.0 + balance))
+    }
+}
+
+/// Converts a linear PipeWire volume to decibels relative to full scale.
+/// Returns negative infinity for zero.
+pub fn volume_to_decibels(volume: f32) -> f32 {
+    20.0 * volume.log10()
+}
+
+/// Converts decibels relative to full scale to a linear PipeWire volume.
+pub fn decibels_to_volume(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -136,6 +443,8 @@ pub enum NodeKind {
     Input,
     #[default]
     All,
+    /// Playback and recording streams
+    Streams,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -162,7 +471,7 @@ impl ListKind {
 fn route_targets(
     device: &state::Device,
     media_class: &String,
-) -> Option<Vec<(Target, String)>> {
+) -> Option<Vec<(Target, String, bool)>> {
     let profile_index = device.profile_index?;
     let profile = device.profiles.get(&profile_index)?;
     let profile_devices = profile
@@ -189,6 +498,7 @@ fn route_targets(
                 Some((
                     Target::Route(device.object_id, route.index, *route_device),
                     title,
+                    route.available,
                 ))
             })
             .collect(),
@@ -258,7 +568,7 @@ impl Node {
 
             let mut routes: Vec<_> =
                 route_targets(device, &media_class).unwrap_or_default();
-            routes.sort_by(|(_, a), (_, b)| a.cmp(b));
+            routes.sort_by(|(_, a, _), (_, b, _)| a.cmp(b));
             let routes = routes;
 
             let (target, target_title) = match active_route(device, card_device)
@@ -339,16 +649,67 @@ impl Node {
             volumes,
             mute,
             peaks: node.peaks.as_ref().map(Arc::clone),
+            clips: node.clips.as_ref().map(Arc::clone),
             peaks_dirty: Arc::clone(&node.peaks_dirty),
+            peak_hold: Default::default(),
+            peak_history: Default::default(),
+            capturing: false,
+            locked: false,
             positions: node.positions.clone(),
+            channel_names: node.props.node_channel_names().map(|names| {
+                names
+                    .split(',')
+                    .map(|name| name.trim().to_owned())
+                    .collect()
+            }),
+            format: audio_format(node),
+            latency_ms: latency_ms(node),
+            process: process(state, node),
+            role: node
+                .props
+                .media_role()
+                .or(node.props.media_category())
+                .cloned(),
+            inputs: linked_titles(state, names, state.inputs(object_id)),
+            outputs: linked_titles(state, names, state.outputs(object_id)),
             device_info,
             is_default_sink: default_sink_name.as_ref()
                 == node.props.node_name(),
             is_default_source: default_source_name.as_ref()
                 == node.props.node_name(),
+            is_virtual: node.props.node_virtual().copied().unwrap_or_default(),
+            is_network: node.props.node_network().copied().unwrap_or_default(),
             client_id: node.props.client_id().copied(),
+            group: None,
         })
     }
+
+    /// Returns the mean volume of all channels.
+    pub fn mean_volume(&self) -> f32 {
+        if self.volumes.is_empty() {
+            return 0.0;
+        }
+        self.volumes.iter().sum::<f32>() / self.volumes.len() as f32
+    }
+
+    /// Returns the indexes of the front left and right channels, or of the
+    /// two channels of a node with unknown positions.
+    pub fn front_channels(&self) -> Option<(usize, usize)> {
+        // SPA_AUDIO_CHANNEL_FL and SPA_AUDIO_CHANNEL_FR
+        const FL: u32 = 3;
+        const FR: u32 = 4;
+
+        let position = |channel| {
+            self.positions
+                .as_ref()?
+                .iter()
+                .position(|&position| position == channel)
+        };
+        match (position(FL), position(FR)) {
+            (Some(left), Some(right)) => Some((left, right)),
+            _ => (self.volumes.len() == 2).then_some((0, 1)),
+        }
+    }
 }
 
 impl Device {
@@ -365,18 +726,22 @@ impl Device {
             .profiles
             .values()
             .map(|profile| {
-                let title = if profile.available {
-                    profile.description.clone()
-                } else {
-                    format!("{} (unavailable)", profile.description)
-                };
-                (profile.index, title)
+                let mut title = profile.description.clone();
+                if let Some(ports) = profile_ports(&profile.classes) {
+                    title = format!("{title} ({ports})");
+                }
+                if !profile.available {
+                    title = format!("{title} (unavailable)");
+                }
+                (profile.index, title, profile.available)
             })
             .collect();
-        profiles.sort_by_key(|&(index, _)| index);
+        profiles.sort_by_key(|&(index, ..)| index);
         let profiles = profiles
             .into_iter()
-            .map(|(index, title)| (Target::Profile(object_id, index), title))
+            .map(|(index, title, available)| {
+                (Target::Profile(object_id, index), title, available)
+            })
             .collect();
 
         let target_profile = device.profiles.get(&device.profile_index?)?;
@@ -390,6 +755,22 @@ impl Device {
 
         let object_serial = *device.props.object_serial()?;
 
+        let mut routes: Vec<_> = device
+            .routes
+            .keys()
+            .filter_map(|&card_device| {
+                active_route(device, card_device).map(|route| DeviceRoute {
+                    index: route.index,
+                    card_device,
+                    description: route.description.clone(),
+                    available: route.available,
+                    volumes: route.volumes.clone(),
+                    mute: route.mute,
+                })
+            })
+            .collect();
+        routes.sort_by_key(|route| route.card_device);
+
         Some(Device {
             object_id,
             object_serial,
@@ -397,8 +778,47 @@ impl Device {
             profiles,
             target_title,
             target,
+            routes,
         })
     }
+
+    /// Returns true if the device has active routes and all of them are
+    /// muted.
+    pub fn is_muted(&self) -> bool {
+        !self.routes.is_empty() && self.routes.iter().all(|route| route.mute)
+    }
+
+    /// Returns the mean volume of all channels of the active routes, or None
+    /// if there are none.
+    pub fn mean_volume(&self) -> Option<f32> {
+        let volumes = self.routes.iter().flat_map(|route| &route.volumes);
+        let count = volumes.clone().count();
+        (count > 0).then(|| volumes.sum::<f32>() / count as f32)
+    }
+}
+
+/// Summarizes the sinks and sources a profile provides, e.g. "2 out, 1 in",
+/// or returns None if it provides neither. These are counts of PCM devices,
+/// each of which becomes a node, rather than of channels. A duplex device
+/// both plays and records, so it counts in both directions.
+fn profile_ports(classes: &[(String, Vec<i32>)]) -> Option<String> {
+    let count = |is_class: fn(&str) -> bool| -> usize {
+        classes
+            .iter()
+            .filter(|(media_class, _)| is_class(media_class))
+            .map(|(_, devices)| devices.len())
+            .sum()
+    };
+    let ports = [
+        (count(media_class::is_sink), "out"),
+        (count(media_class::is_source), "in"),
+    ];
+    let ports: Vec<String> = ports
+        .into_iter()
+        .filter(|&(count, _)| count > 0)
+        .map(|(count, direction)| format!("{count} {direction}"))
+        .collect();
+    (!ports.is_empty()).then(|| ports.join(", "))
 }
 
 fn default_for(state: &state::State, which: &str) -> Option<String> {
@@ -434,6 +854,68 @@ fn has_target(state: &state::State, node_id: ObjectId) -> bool {
     }
 }
 
+fn audio_format(node: &state::Node) -> Option<String> {
+    let rate = node
+        .props
+        .audio_rate()
+        .copied()
+        .or(node.rate)
+        .map(|rate| format!("{rate} Hz"));
+    let channels = node
+        .props
+        .audio_channels()
+        .map(|channels| format!("{channels}ch"));
+    let format = node.props.audio_format().cloned();
+
+    let parts: Vec<_> =
+        [rate, channels, format].into_iter().flatten().collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// Converts a node.latency fraction like "1024/48000" into milliseconds. A bare
+/// quantum uses the node's rate.
+fn latency_ms(node: &state::Node) -> Option<f32> {
+    let latency = node.props.node_latency()?;
+    let (quantum, rate) = match latency.split_once('/') {
+        Some((quantum, rate)) => (quantum, rate.trim().parse().ok()?),
+        None => (
+            latency.as_str(),
+            node.props.audio_rate().copied().or(node.rate)?,
+        ),
+    };
+    let quantum: u32 = quantum.trim().parse().ok()?;
+    (rate > 0).then(|| quantum as f32 * 1000.0 / rate as f32)
+}
+
+fn process(state: &state::State, node: &state::Node) -> Option<String> {
+    let client = state.clients.get(node.props.client_id()?)?;
+    let binary = client.props.application_process_binary();
+    let pid = client.props.application_process_id();
+    match (binary, pid) {
+        (Some(binary), Some(pid)) => Some(format!("{binary}, PID {pid}")),
+        (Some(binary), None) => Some(binary.clone()),
+        (None, Some(pid)) => Some(format!("PID {pid}")),
+        (None, None) => None,
+    }
+}
+
+/// Returns the titles of the nodes in object_ids. Nodes linked by several
+/// ports appear once.
+fn linked_titles(
+    state: &state::State,
+    names: &config::Names,
+    object_ids: Vec<ObjectId>,
+) -> Vec<String> {
+    object_ids
+        .into_iter()
+        .sorted()
+        .dedup()
+        .filter_map(|object_id| {
+            names.resolve(state, state.nodes.get(&object_id)?)
+        })
+        .collect()
+}
+
 fn is_filtered(
     filters: &[config::MatchCondition],
     state: &state::State,
@@ -446,6 +928,43 @@ fn is_filtered(
     })
 }
 
+/// Sorts node_ids, which should already be in serial order, by sort_mode.
+/// Names are compared ignoring case. The sort is stable so ties stay in serial
+/// order.
+fn sort_nodes(
+    node_ids: &mut [ObjectId],
+    nodes: &HashMap<ObjectId, Node>,
+    sort_mode: config::SortMode,
+) {
+    match sort_mode {
+        config::SortMode::Serial => {}
+        config::SortMode::Name => node_ids.sort_by_cached_key(|node_id| {
+            nodes.get(node_id).map(|node| node.title.to_lowercase())
+        }),
+        config::SortMode::Volume => node_ids.sort_by(|a, b| {
+            let a = nodes.get(a).map_or(0.0, Node::mean_volume);
+            let b = nodes.get(b).map_or(0.0, Node::mean_volume);
+            b.total_cmp(&a)
+        }),
+    }
+}
+
+/// Like [`sort_nodes()`], but devices have no volume, so
+/// [`SortMode::Volume`](`config::SortMode::Volume`) keeps serial order.
+fn sort_devices(
+    device_ids: &mut [ObjectId],
+    devices: &HashMap<ObjectId, Device>,
+    sort_mode: config::SortMode,
+) {
+    if sort_mode == config::SortMode::Name {
+        device_ids.sort_by_cached_key(|device_id| {
+            devices
+                .get(device_id)
+                .map(|device| device.title.to_lowercase())
+        });
+    }
+}
+
 impl<'a> View<'a> {
     pub fn new(wirehose: &'a dyn CommandSender) -> View<'a> {
         Self {
@@ -455,23 +974,31 @@ impl<'a> View<'a> {
             nodes_all: Default::default(),
             nodes_playback: Default::default(),
             nodes_recording: Default::default(),
+            nodes_streams: Default::default(),
             nodes_output: Default::default(),
             nodes_input: Default::default(),
             devices_all: Default::default(),
+            clients: Default::default(),
             sinks: Default::default(),
             sources: Default::default(),
             default_sink: Default::default(),
             default_source: Default::default(),
             metadata_id: Default::default(),
+            min_volume_percent: Default::default(),
+            wrap_navigation: Default::default(),
+            default_keys: Default::default(),
         }
     }
 
-    /// Create a View from scratch from a provided State.
+    /// Create a View from scratch from a provided State, ordering each tab
+    /// according to sort and leaving out what hidden says to.
     pub fn from(
         wirehose: &'a dyn CommandSender,
         state: &state::State,
         names: &config::Names,
         filters: &[config::MatchCondition],
+        sort: &config::Sort,
+        hidden: &Hidden,
     ) -> View<'a> {
         let default_sink_name = default_for(state, "default.audio.sink");
         let default_source_name = default_for(state, "default.audio.source");
@@ -524,7 +1051,9 @@ impl<'a> View<'a> {
                 if media_class::is_source(node.props.media_class()?) {
                     let title = names.resolve(state, node)?;
                     Some((Target::Node(node.object_id), title))
-                } else if media_class::is_sink(node.props.media_class()?) {
+                } else if !hidden.monitor_sources
+                    && media_class::is_sink(node.props.media_class()?)
+                {
                     let title = names.resolve(state, node)?;
                     Some((
                         Target::Node(node.object_id),
@@ -572,8 +1101,13 @@ impl<'a> View<'a> {
         let mut nodes_all = Vec::new();
         let mut nodes_playback = Vec::new();
         let mut nodes_recording = Vec::new();
+        let mut nodes_streams = Vec::new();
         let mut nodes_output = Vec::new();
         let mut nodes_input = Vec::new();
+        let is_hidden_stream = |node: &Node| {
+            (hidden.muted_streams && node.mute)
+                || (hidden.idle_streams && node.peaks.is_none())
+        };
         for (id, node) in nodes
             .iter()
             .sorted_by_key(|(_, node)| node.object_serial)
@@ -582,12 +1116,20 @@ impl<'a> View<'a> {
             })
         {
             nodes_all.push(*id);
-            if media_class::is_sink_input(&node.media_class) {
+            let shown_stream = !is_hidden_stream(node);
+            if media_class::is_sink_input(&node.media_class) && shown_stream {
                 nodes_playback.push(*id);
             }
-            if media_class::is_source_output(&node.media_class) {
+            if media_class::is_source_output(&node.media_class) && shown_stream
+            {
                 nodes_recording.push(*id);
             }
+            if (media_class::is_sink_input(&node.media_class)
+                || media_class::is_source_output(&node.media_class))
+                && shown_stream
+            {
+                nodes_streams.push(*id);
+            }
             if media_class::is_sink(&node.media_class) {
                 nodes_output.push(*id);
             }
@@ -595,17 +1137,58 @@ impl<'a> View<'a> {
                 nodes_input.push(*id);
             }
         }
+        sort_nodes(&mut nodes_all, &nodes, sort.all);
+        sort_nodes(&mut nodes_playback, &nodes, sort.playback);
+        sort_nodes(&mut nodes_recording, &nodes, sort.recording);
+        sort_nodes(&mut nodes_streams, &nodes, sort.streams);
+        sort_nodes(&mut nodes_output, &nodes, sort.output);
+        sort_nodes(&mut nodes_input, &nodes, sort.input);
         let nodes_all = nodes_all;
         let nodes_playback = nodes_playback;
         let nodes_recording = nodes_recording;
+        let nodes_streams = nodes_streams;
         let nodes_output = nodes_output;
         let nodes_input = nodes_input;
 
-        let devices_all = devices
+        let mut devices_all: Vec<_> = devices
             .iter()
             .sorted_by_key(|(_, device)| device.object_serial)
             .map(|(&id, _)| id)
             .collect();
+        sort_devices(&mut devices_all, &devices, sort.configuration);
+        let devices_all = devices_all;
+
+        let mut clients: HashMap<ObjectId, Client> = HashMap::new();
+        for node in nodes_all.iter().filter_map(|id| nodes.get(id)) {
+            let Some(client_id) = node.client_id else {
+                continue;
+            };
+            if !media_class::is_sink_input(&node.media_class)
+                && !media_class::is_source_output(&node.media_class)
+            {
+                continue;
+            }
+            // Groups are made of the streams that are shown, so a group is
+            // only hidden once all of its streams are.
+            if is_hidden_stream(node) {
+                continue;
+            }
+            clients
+                .entry(client_id)
+                .or_insert_with(|| Client {
+                    object_id: client_id,
+                    title: state
+                        .clients
+                        .get(&client_id)
+                        .and_then(|client| client.props.application_name())
+                        .unwrap_or(&node.name)
+                        .clone(),
+                    nodes: Vec::new(),
+                })
+                .nodes
+                .push(node.object_id);
+        }
+        let clients = clients;
 
         Self {
             wirehose,
@@ -614,14 +1197,95 @@ impl<'a> View<'a> {
             nodes_all,
             nodes_playback,
             nodes_recording,
+            nodes_streams,
             nodes_output,
             nodes_input,
             devices_all,
+            clients,
             sinks,
             sources,
             default_sink,
             default_source,
             metadata_id: state.metadatas_by_name.get("default").copied(),
+            min_volume_percent: Default::default(),
+            wrap_navigation: Default::default(),
+            default_keys: Default::default(),
+        }
+    }
+
+    /// Marks the nodes in locked as locked against volume changes. A group is
+    /// locked if any of its members are.
+    pub fn set_locked(&mut self, locked: &HashSet<ObjectId>) {
+        for (object_id, node) in self.nodes.iter_mut() {
+            node.locked = locked.contains(object_id)
+                || node.group.as_ref().is_some_and(|group| {
+                    group.members.iter().any(|member| locked.contains(member))
+                });
+        }
+    }
+
+    /// Marks the nodes in capturing as being captured. A group is captured
+    /// if any of its members are.
+    pub fn set_capturing(&mut self, capturing: &HashSet<ObjectId>) {
+        for (object_id, node) in self.nodes.iter_mut() {
+            node.capturing = capturing.contains(object_id)
+                || node.group.as_ref().is_some_and(|group| {
+                    group
+                        .members
+                        .iter()
+                        .any(|member| capturing.contains(member))
+                });
+        }
+    }
+
+    /// Collapses each client's playback streams and its recording streams
+    /// into the first stream of each, unless the client is in expanded.
+    /// The other streams are removed from the lists, and the remaining one
+    /// gets a [`Group`] and the client's title.
+    pub fn collapse_groups(&mut self, expanded: &HashSet<ObjectId>) {
+        let mut hidden = HashSet::new();
+        for client in self.clients.values() {
+            if expanded.contains(&client.object_id) {
+                continue;
+            }
+            for is_playback in [true, false] {
+                let members: Vec<&Node> = client
+                    .nodes
+                    .iter()
+                    .filter_map(|id| self.nodes.get(id))
+                    .filter(|node| {
+                        media_class::is_sink_input(&node.media_class)
+                            == is_playback
+                    })
+                    .collect();
+                let [first, rest @ ..] = members.as_slice() else {
+                    continue;
+                };
+                if rest.is_empty() {
+                    continue;
+                }
+                let group_id = first.object_id;
+                let group = Group::new(&members);
+                hidden.extend(rest.iter().map(|node| node.object_id));
+
+                if let Some(node) = self.nodes.get_mut(&group_id) {
+                    node.title = format!(
+                        "{} ({} streams)",
+                        client.title,
+                        group.members.len()
+                    );
+                    node.group = Some(group);
+                }
+            }
+        }
+
+        for list in [
+            &mut self.nodes_all,
+            &mut self.nodes_playback,
+            &mut self.nodes_recording,
+            &mut self.nodes_streams,
+        ] {
+            list.retain(|id| !hidden.contains(id));
         }
     }
 
@@ -631,21 +1295,28 @@ impl<'a> View<'a> {
         let Some(node) = self.nodes.get(&node_id) else {
             return;
         };
+
+        self.set_default_name(&node.name, device_kind);
+    }
+
+    /// Sets the node with the provided node.name as the default source/sink,
+    /// depending on device_kind. The node doesn't need to exist yet.
+    pub fn set_default_name(&self, node_name: &str, device_kind: DeviceKind) {
         let Some(metadata_id) = self.metadata_id else {
             return;
         };
 
         let key = match device_kind {
-            DeviceKind::Source => "default.configured.audio.source",
-            DeviceKind::Sink => "default.configured.audio.sink",
+            DeviceKind::Source => &self.default_keys.source,
+            DeviceKind::Sink => &self.default_keys.sink,
         };
 
         self.wirehose.metadata_set_property(
             metadata_id,
             0,
-            String::from(key),
+            key.clone(),
             Some(String::from("Spa:String:JSON")),
-            Some(json!({ "name": &node.name }).to_string()),
+            Some(json!({ "name": node_name }).to_string()),
         );
     }
 
@@ -701,6 +1372,41 @@ impl<'a> View<'a> {
         }
     }
 
+    /// Sets an arbitrary property with the provided subject on the default
+    /// metadata. A value of None removes the property.
+    pub fn set_metadata(
+        &self,
+        subject: ObjectId,
+        key: String,
+        type_: Option<String>,
+        value: Option<String>,
+    ) {
+        let Some(metadata_id) = self.metadata_id else {
+            return;
+        };
+
+        self.wirehose.metadata_set_property(
+            metadata_id,
+            subject.into(),
+            key,
+            type_,
+            value,
+        );
+    }
+
+    /// Resets every stream with an explicit target to follow the default
+    /// sink/source.
+    pub fn clear_targets(&self) {
+        for node_id in self.nodes_playback.iter().chain(&self.nodes_recording) {
+            let Some(node) = self.nodes.get(node_id) else {
+                continue;
+            };
+            if node.target != Some(Target::Default) {
+                self.set_target(*node_id, Target::Default);
+            }
+        }
+    }
+
     /// Mutes the provided node.
     pub fn mute(&self, node_id: ObjectId) {
         let Some(node) = self.nodes.get(&node_id) else {
@@ -722,8 +1428,9 @@ impl<'a> View<'a> {
     }
 
     /// Changes the volume of the provided node. If max volume is provided,
-    /// won't change volume if result would be greater than max. Returns true
-    /// if volume was changed, otherwise false.
+    /// won't change volume if result would be greater than max. Volumes are
+    /// clamped to min_volume_percent, and locked nodes aren't changed at all.
+    /// Returns true if volume was changed, otherwise false.
     pub fn volume(
         &self,
         node_id: ObjectId,
@@ -733,31 +1440,276 @@ impl<'a> View<'a> {
         let Some(node) = self.nodes.get(&node_id) else {
             return false;
         };
-
-        let mut volumes = node.volumes.clone();
-        if volumes.is_empty() {
+        if node.locked {
             return false;
         }
-        match adjustment {
-            VolumeAdjustment::Relative(delta) => {
-                let avg = volumes.iter().sum::<f32>() / volumes.len() as f32;
-                volumes.fill((avg.cbrt() + delta).max(0.0).powi(3));
-            }
-            VolumeAdjustment::Absolute(volume) => {
-                volumes.fill(volume.max(0.0).powi(3));
-            }
+
+        let min = self.min_volume_percent;
+        match adjusted_volumes(&node.volumes, adjustment, max, min) {
+            Some(volumes) => self.set_volumes(node_id, volumes),
+            None => false,
         }
-        let volumes = volumes;
+    }
 
-        if let Some(max) = max {
-            if volumes
-                .iter()
-                .any(|volume| (volume.cbrt() * 100.0).round() > max)
+    /// Mutes all members of the provided node's group, or unmutes them if
+    /// they are all muted already. Returns false if the node isn't a group.
+    pub fn group_mute(&self, node_id: ObjectId) -> bool {
+        let Some(group) = self
+            .nodes
+            .get(&node_id)
+            .and_then(|node| node.group.as_ref())
+        else {
+            return false;
+        };
+
+        let mute = !group.mute;
+        for member_id in &group.members {
+            if self
+                .nodes
+                .get(member_id)
+                .is_some_and(|node| node.mute != mute)
             {
-                return false;
+                self.wirehose.node_mute(*member_id, mute);
             }
         }
 
+        true
+    }
+
+    /// Changes the volume of the provided node's group by scaling each
+    /// member's volume proportionally. Nothing is changed if any member
+    /// would exceed max or is locked. Returns true if volume was changed,
+    /// otherwise false.
+    pub fn group_volume(
+        &self,
+        node_id: ObjectId,
+        adjustment: VolumeAdjustment,
+        max: Option<f32>,
+    ) -> bool {
+        let Some(group) = self
+            .nodes
+            .get(&node_id)
+            .and_then(|node| node.group.as_ref())
+        else {
+            return false;
+        };
+        let min = self.min_volume_percent;
+        let Some(&[volume]) =
+            adjusted_volumes(&[group.volume], adjustment, max, min).as_deref()
+        else {
+            return false;
+        };
+
+        // Silent groups can't be scaled, so set every member to the volume.
+        let adjustment = if group.volume > 0.0 {
+            VolumeAdjustment::Scale(volume / group.volume)
+        } else {
+            VolumeAdjustment::Absolute(volume.cbrt())
+        };
+        let fits = group.members.iter().all(|member_id| {
+            self.nodes.get(member_id).map_or(true, |node| {
+                !node.locked
+                    && adjusted_volumes(&node.volumes, adjustment, max, min)
+                        .is_some()
+            })
+        });
+        if !fits {
+            return false;
+        }
+
+        for member_id in &group.members {
+            self.volume(*member_id, adjustment, max);
+        }
+
+        true
+    }
+
+    /// Shifts the balance of the provided node's front channels toward the
+    /// right by delta. Returns true if volume was changed, otherwise false.
+    pub fn balance(
+        &self,
+        node_id: ObjectId,
+        delta: f32,
+        linked: bool,
+        max: Option<f32>,
+    ) -> bool {
+        let Some((left, right)) = self
+            .nodes
+            .get(&node_id)
+            .and_then(|node| node.front_channels())
+        else {
+            return false;
+        };
+
+        self.volume(
+            node_id,
+            VolumeAdjustment::Balance {
+                left,
+                right,
+                delta,
+                linked,
+            },
+            max,
+        )
+    }
+
+    /// Evens out the provided node's front channels. Returns true if volume
+    /// was changed, otherwise false.
+    pub fn center_balance(
+        &self,
+        node_id: ObjectId,
+        linked: bool,
+        max: Option<f32>,
+    ) -> bool {
+        let Some((left, right)) = self
+            .nodes
+            .get(&node_id)
+            .and_then(|node| node.front_channels())
+        else {
+            return false;
+        };
+
+        self.volume(
+            node_id,
+            VolumeAdjustment::CenterBalance {
+                left,
+                right,
+                linked,
+            },
+            max,
+        )
+    }
+
+    /// Toggles mute on all of a device's active routes. Returns false if the
+    /// device has no active route.
+    pub fn device_mute(&self, device_id: ObjectId) -> bool {
+        let Some(device) = self.devices.get(&device_id) else {
+            return false;
+        };
+        if device.routes.is_empty() {
+            return false;
+        }
+
+        let mute = !device.is_muted();
+        for route in &device.routes {
+            self.wirehose.device_mute(
+                device_id,
+                route.index,
+                route.card_device,
+                mute,
+            );
+        }
+
+        true
+    }
+
+    /// Changes the volume of all of a device's active routes. Nothing is
+    /// changed if any route would exceed max. Returns true if volume was
+    /// changed, otherwise false.
+    pub fn device_volume(
+        &self,
+        device_id: ObjectId,
+        adjustment: VolumeAdjustment,
+        max: Option<f32>,
+    ) -> bool {
+        let Some(device) = self.devices.get(&device_id) else {
+            return false;
+        };
+        if device.routes.is_empty() {
+            return false;
+        }
+        let Some(volumes) = device
+            .routes
+            .iter()
+            .map(|route| {
+                adjusted_volumes(
+                    &route.volumes,
+                    adjustment,
+                    max,
+                    self.min_volume_percent,
+                )
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+
+        for (route, volumes) in device.routes.iter().zip(volumes) {
+            self.wirehose.device_volumes(
+                device_id,
+                route.index,
+                route.card_device,
+                volumes,
+            );
+        }
+
+        true
+    }
+
+    /// Toggles mute on the active route at position in [`Device::routes`].
+    /// Returns false if there is no such route.
+    pub fn route_mute(&self, device_id: ObjectId, position: usize) -> bool {
+        let Some(route) = self
+            .devices
+            .get(&device_id)
+            .and_then(|device| device.routes.get(position))
+        else {
+            return false;
+        };
+
+        self.wirehose.device_mute(
+            device_id,
+            route.index,
+            route.card_device,
+            !route.mute,
+        );
+
+        true
+    }
+
+    /// Changes the volume of the active route at position in
+    /// [`Device::routes`]. Returns true if volume was changed, otherwise
+    /// false.
+    pub fn route_volume(
+        &self,
+        device_id: ObjectId,
+        position: usize,
+        adjustment: VolumeAdjustment,
+        max: Option<f32>,
+    ) -> bool {
+        let Some(route) = self
+            .devices
+            .get(&device_id)
+            .and_then(|device| device.routes.get(position))
+        else {
+            return false;
+        };
+        let Some(volumes) = adjusted_volumes(
+            &route.volumes,
+            adjustment,
+            max,
+            self.min_volume_percent,
+        ) else {
+            return false;
+        };
+
+        self.wirehose.device_volumes(
+            device_id,
+            route.index,
+            route.card_device,
+            volumes,
+        );
+
+        true
+    }
+
+    /// Sets the volumes of the provided node's channels. Returns true if the
+    /// node exists.
+    pub fn set_volumes(&self, node_id: ObjectId, volumes: Vec<f32>) -> bool {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return false;
+        };
+
         if let Some((device_id, route_index, route_device)) = node.device_info {
             self.wirehose.device_volumes(
                 device_id,
@@ -779,22 +1731,64 @@ impl<'a> View<'a> {
             ListKind::Node(NodeKind::Output) => &self.nodes_output,
             ListKind::Node(NodeKind::Input) => &self.nodes_input,
             ListKind::Node(NodeKind::All) => &self.nodes_all,
+            ListKind::Node(NodeKind::Streams) => &self.nodes_streams,
             ListKind::Device => &self.devices_all,
         }
     }
 
-    /// Gets all the nodes without filtering.
-    pub fn full_nodes(&self, node_kind: NodeKind) -> Vec<&Node> {
-        let node_ids = self.object_ids(ListKind::Node(node_kind));
+    /// Returns the object IDs in list_kind. If search is provided, only
+    /// objects with titles containing it (ignoring case) are included.
+    pub fn filtered_ids(
+        &self,
+        list_kind: ListKind,
+        search: Option<&str>,
+    ) -> Cow<'_, [ObjectId]> {
+        let objects = self.object_ids(list_kind);
+        let Some(search) = search.filter(|search| !search.is_empty()) else {
+            return Cow::Borrowed(objects);
+        };
+        let search = search.to_lowercase();
+
+        Cow::Owned(
+            objects
+                .iter()
+                .copied()
+                .filter(|object_id| {
+                    self.title(object_id).is_some_and(|title| {
+                        title.to_lowercase().contains(&search)
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn title(&self, object_id: &ObjectId) -> Option<&str> {
+        self.nodes
+            .get(object_id)
+            .map(|node| node.title.as_str())
+            .or_else(|| {
+                self.devices
+                    .get(object_id)
+                    .map(|device| device.title.as_str())
+            })
+    }
+
+    /// Gets all the nodes matching search without viewport limits.
+    pub fn full_nodes(
+        &self,
+        node_kind: NodeKind,
+        search: Option<&str>,
+    ) -> Vec<&Node> {
+        let node_ids = self.filtered_ids(ListKind::Node(node_kind), search);
         node_ids
             .iter()
             .filter_map(|node_id| self.nodes.get(node_id))
             .collect()
     }
 
-    /// Gets all the devices without filtering.
-    pub fn full_devices(&self) -> Vec<&Device> {
-        let device_ids = self.object_ids(ListKind::Device);
+    /// Gets all the devices matching search without viewport limits.
+    pub fn full_devices(&self, search: Option<&str>) -> Vec<&Device> {
+        let device_ids = self.filtered_ids(ListKind::Device, search);
         device_ids
             .iter()
             .filter_map(|device_id| self.devices.get(device_id))
@@ -805,14 +1799,19 @@ impl<'a> View<'a> {
     pub fn next_id(
         &self,
         list_kind: ListKind,
+        search: Option<&str>,
         object_id: Option<ObjectId>,
     ) -> Option<ObjectId> {
-        let objects = self.object_ids(list_kind);
+        let objects = self.filtered_ids(list_kind, search);
         let next_index = match object_id {
-            Some(object_id) => objects
-                .iter()
-                .position(|&id| id == object_id)?
-                .saturating_add(1),
+            Some(object_id) => {
+                let index = objects.iter().position(|&id| id == object_id)?;
+                if self.wrap_navigation {
+                    (index + 1) % objects.len()
+                } else {
+                    index.saturating_add(1)
+                }
+            }
             None => 0,
         };
         objects.get(next_index).copied()
@@ -822,14 +1821,19 @@ impl<'a> View<'a> {
     pub fn previous_id(
         &self,
         list_kind: ListKind,
+        search: Option<&str>,
         object_id: Option<ObjectId>,
     ) -> Option<ObjectId> {
-        let objects = self.object_ids(list_kind);
+        let objects = self.filtered_ids(list_kind, search);
         let next_index = match object_id {
-            Some(object_id) => objects
-                .iter()
-                .position(|&id| id == object_id)?
-                .saturating_sub(1),
+            Some(object_id) => {
+                let index = objects.iter().position(|&id| id == object_id)?;
+                if self.wrap_navigation {
+                    index.checked_sub(1).unwrap_or(objects.len() - 1)
+                } else {
+                    index.saturating_sub(1)
+                }
+            }
             None => 0,
         };
         objects.get(next_index).copied()
@@ -839,32 +1843,40 @@ impl<'a> View<'a> {
     pub fn position(
         &self,
         list_kind: ListKind,
+        search: Option<&str>,
         object_id: ObjectId,
     ) -> Option<usize> {
-        self.object_ids(list_kind)
+        self.filtered_ids(list_kind, search)
             .iter()
             .position(|&id| id == object_id)
     }
 
     /// Returns length of the list_kind.
-    pub fn len(&self, list_kind: ListKind) -> usize {
-        self.object_ids(list_kind).len()
+    pub fn len(&self, list_kind: ListKind, search: Option<&str>) -> usize {
+        self.filtered_ids(list_kind, search).len()
     }
 
     /// Returns the possible targets for a node.
     pub fn node_targets(
         &self,
         node_id: ObjectId,
-    ) -> Option<(Vec<(Target, String)>, usize)> {
+    ) -> Option<(Vec<(Target, String, bool)>, usize)> {
         let node = self.nodes.get(&node_id)?;
 
+        // Nodes are always available.
+        let available = |nodes: &[(Target, String)]| {
+            nodes
+                .iter()
+                .map(|(target, title)| (*target, title.clone(), true))
+                .collect()
+        };
         // Get the target list appropriate to the node type
         let (mut targets, default) = if let Some(routes) = &node.routes {
             (routes.clone(), None)
         } else if media_class::is_sink_input(&node.media_class) {
-            (self.sinks.clone(), self.default_sink)
+            (available(&self.sinks), self.default_sink)
         } else if media_class::is_source_output(&node.media_class) {
-            (self.sources.clone(), self.default_source)
+            (available(&self.sources), self.default_source)
         } else {
             (Vec::new(), None)
         };
@@ -873,17 +1885,17 @@ impl<'a> View<'a> {
             .and_then(|default| {
                 targets
                     .iter()
-                    .find(|(target, _)| *target == default)
-                    .map(|(_, name)| format!("Default: {name}"))
+                    .find(|(target, ..)| *target == default)
+                    .map(|(_, name, _)| format!("Default: {name}"))
             })
             .unwrap_or(String::from("Default: No default"));
         // Sort targets by name
-        targets.sort_by(|(_, a), (_, b)| a.cmp(b));
+        targets.sort_by(|(_, a, _), (_, b, _)| a.cmp(b));
         // If the targets are nodes, add the default node to the top
         if media_class::is_sink_input(&node.media_class)
             || media_class::is_source_output(&node.media_class)
         {
-            targets.insert(0, (Target::Default, default_name.clone()));
+            targets.insert(0, (Target::Default, default_name.clone(), true));
         };
         let targets = targets;
 
@@ -894,7 +1906,7 @@ impl<'a> View<'a> {
             .and_then(|node_target| {
                 targets
                     .iter()
-                    .position(|&(target, _)| target == node_target)
+                    .position(|&(target, ..)| target == node_target)
             })
             .unwrap_or(0);
 
@@ -905,7 +1917,7 @@ impl<'a> View<'a> {
     pub fn device_targets(
         &self,
         device_id: ObjectId,
-    ) -> Option<(Vec<(Target, String)>, usize)> {
+    ) -> Option<(Vec<(Target, String, bool)>, usize)> {
         let device = self.devices.get(&device_id)?;
 
         let targets = device.profiles.clone();
@@ -914,10 +1926,535 @@ impl<'a> View<'a> {
             .and_then(|device_target| {
                 targets
                     .iter()
-                    .position(|&(target, _)| target == device_target)
+                    .position(|&(target, ..)| target == device_target)
             })
             .unwrap_or(0);
 
         Some((targets, selected_position))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+    use crate::wirehose::{PropertyStore, StateEvent};
+
+    /// Returns properties for a node with its serial taken from object_id and
+    /// title as both its description and node.name.
+    fn node_props(
+        object_id: ObjectId,
+        media_class: &str,
+        title: &str,
+    ) -> PropertyStore {
+        let mut props = PropertyStore::default();
+        props.set_media_class(String::from(media_class));
+        props.set_node_description(String::from(title));
+        props.set_node_name(String::from(title));
+        props.set_object_serial(u32::from(object_id) as u64);
+        props
+    }
+
+    /// Adds an unmuted node at full volume, which is enough for it to appear
+    /// in a View.
+    fn create_node(
+        state: &mut state::State,
+        object_id: ObjectId,
+        props: PropertyStore,
+    ) {
+        state.update(StateEvent::NodeProperties { object_id, props });
+        state.update(StateEvent::NodeVolumes {
+            object_id,
+            volumes: vec![1.0, 1.0],
+        });
+        state.update(StateEvent::NodeMute {
+            object_id,
+            mute: false,
+        });
+    }
+
+    /// Builds a View of state with the default names and no filters.
+    fn view_of<'a>(
+        wirehose: &'a mock::WirehoseHandle,
+        state: &state::State,
+        sort: &config::Sort,
+        hidden: &Hidden,
+    ) -> View<'a> {
+        View::from(
+            wirehose,
+            state,
+            &config::Names::default(),
+            &Vec::new(),
+            sort,
+            hidden,
+        )
+    }
+
+    fn balance(volumes: &[f32], delta: f32, linked: bool) -> Vec<f32> {
+        let adjustment = VolumeAdjustment::Balance {
+            left: 0,
+            right: 1,
+            delta,
+            linked,
+        };
+        adjusted_volumes(volumes, adjustment, None, 0.0)
+            .unwrap()
+            .iter()
+            .map(|volume| (volume.cbrt() * 100.0).round() / 100.0)
+            .collect()
+    }
+
+    #[test]
+    fn linked_balance_keeps_louder_side() {
+        let volumes = [0.5_f32.powi(3), 0.5_f32.powi(3), 0.25];
+        assert_eq!(balance(&volumes, 0.5, true), vec![0.25, 0.5, 0.63]);
+        assert_eq!(balance(&volumes, -0.5, true), vec![0.5, 0.25, 0.63]);
+        // Balance stops at one side
+        assert_eq!(balance(&volumes, 2.0, true), vec![0.0, 0.5, 0.63]);
+    }
+
+    #[test]
+    fn center_balance() {
+        let volumes = [0.25_f32.powi(3), 0.75_f32.powi(3), 0.25];
+        let center = |linked| {
+            let adjustment = VolumeAdjustment::CenterBalance {
+                left: 0,
+                right: 1,
+                linked,
+            };
+            adjusted_volumes(&volumes, adjustment, None, 0.0)
+                .unwrap()
+                .iter()
+                .map(|volume| (volume.cbrt() * 100.0).round() / 100.0)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(center(true), vec![0.75, 0.75, 0.63]);
+        assert_eq!(center(false), vec![0.5, 0.5, 0.63]);
+    }
+
+    #[test]
+    fn process_from_client() {
+        let mut state = state::State::default();
+        let client_id = ObjectId::from_raw_id(1);
+        let mut props = PropertyStore::default();
+        props.set_application_process_binary(String::from("firefox"));
+        props.set_application_process_id(1234);
+        state.update(StateEvent::ClientProperties {
+            object_id: client_id,
+            props,
+        });
+
+        let node_id = ObjectId::from_raw_id(2);
+        let mut props = node_props(node_id, "Stream/Output/Audio", "Stream");
+        props.set_client_id(client_id);
+        create_node(&mut state, node_id, props);
+        let node = &state.nodes[&node_id];
+        assert_eq!(process(&state, node).as_deref(), Some("firefox, PID 1234"));
+
+        // Nodes without a client have no process
+        let node_id = ObjectId::from_raw_id(3);
+        let props = node_props(node_id, "Stream/Output/Audio", "Stream");
+        create_node(&mut state, node_id, props);
+        assert_eq!(process(&state, &state.nodes[&node_id]), None);
+    }
+
+    #[test]
+    fn virtual_and_network_nodes() {
+        let mut state = state::State::default();
+        let wirehose = mock::WirehoseHandle::default();
+        let virtual_id = ObjectId::from_raw_id(1);
+        let network_id = ObjectId::from_raw_id(2);
+        for object_id in [virtual_id, network_id] {
+            let mut props = node_props(object_id, "Audio/Sink", "Sink");
+            if object_id == virtual_id {
+                props.set_node_virtual(true);
+            } else {
+                props.set_node_network(true);
+            }
+            create_node(&mut state, object_id, props);
+        }
+
+        let view = view_of(
+            &wirehose,
+            &state,
+            &config::Sort::default(),
+            &Hidden::default(),
+        );
+
+        let node = &view.nodes[&virtual_id];
+        assert!(node.is_virtual && !node.is_network);
+        let node = &view.nodes[&network_id];
+        assert!(node.is_network && !node.is_virtual);
+    }
+
+    #[test]
+    fn all_nodes_sorted() {
+        let mut state = state::State::default();
+        let wirehose = mock::WirehoseHandle::default();
+        let speakers_id = ObjectId::from_raw_id(1);
+        let headphones_id = ObjectId::from_raw_id(2);
+        for (object_id, title) in
+            [(speakers_id, "Speakers"), (headphones_id, "Headphones")]
+        {
+            let props = node_props(object_id, "Audio/Sink", title);
+            create_node(&mut state, object_id, props);
+        }
+        let view_for =
+            |sort| view_of(&wirehose, &state, sort, &Hidden::default());
+
+        let view = view_for(&config::Sort::default());
+        assert_eq!(view.nodes_all, vec![speakers_id, headphones_id]);
+
+        let sort = config::Sort {
+            all: config::SortMode::Name,
+            ..Default::default()
+        };
+        let view = view_for(&sort);
+        assert_eq!(view.nodes_all, vec![headphones_id, speakers_id]);
+    }
+
+    #[test]
+    fn device_targets_availability() {
+        let mut state = state::State::default();
+        let wirehose = mock::WirehoseHandle::default();
+        let device_id = ObjectId::from_raw_id(0);
+        let mut props = PropertyStore::default();
+        props.set_device_nick(String::from("Device"));
+        props.set_object_serial(0);
+        state.update(StateEvent::DeviceProperties {
+            object_id: device_id,
+            props,
+        });
+        for (index, available) in [(0, true), (1, false)] {
+            state.update(StateEvent::DeviceEnumProfile {
+                object_id: device_id,
+                index,
+                description: format!("Profile {index}"),
+                available,
+                classes: Vec::new(),
+            });
+        }
+        state.update(StateEvent::DeviceProfile {
+            object_id: device_id,
+            index: 0,
+        });
+
+        let view = view_of(
+            &wirehose,
+            &state,
+            &config::Sort::default(),
+            &Hidden::default(),
+        );
+        let (targets, selected) = view.device_targets(device_id).unwrap();
+        assert_eq!(
+            targets,
+            vec![
+                (
+                    Target::Profile(device_id, 0),
+                    String::from("Profile 0"),
+                    true
+                ),
+                (
+                    Target::Profile(device_id, 1),
+                    String::from("Profile 1 (unavailable)"),
+                    false
+                ),
+            ]
+        );
+        assert_eq!(selected, 0);
+    }
+
+    #[test]
+    fn profile_ports_summary() {
+        let classes = vec![
+            (String::from("Audio/Sink"), vec![1, 2]),
+            (String::from("Audio/Source"), vec![3]),
+            (String::from("Video/Source"), vec![4]),
+        ];
+        assert_eq!(profile_ports(&classes).as_deref(), Some("2 out, 1 in"));
+        assert_eq!(profile_ports(&classes[1..]).as_deref(), Some("1 in"));
+        assert_eq!(profile_ports(&[]), None);
+
+        let classes = vec![
+            (String::from("Audio/Duplex"), vec![1]),
+            (String::from("Audio/Sink"), vec![2]),
+        ];
+        assert_eq!(profile_ports(&classes).as_deref(), Some("2 out, 1 in"));
+    }
+
+    #[test]
+    fn hide_monitor_sources() {
+        let mut state = state::State::default();
+        let wirehose = mock::WirehoseHandle::default();
+        let classes = ["Audio/Sink", "Audio/Source", "Audio/Duplex"];
+        for (i, media_class) in classes.into_iter().enumerate() {
+            let object_id = ObjectId::from_raw_id(i as u32);
+            let props = node_props(object_id, media_class, media_class);
+            create_node(&mut state, object_id, props);
+        }
+        let source_id = ObjectId::from_raw_id(1);
+        let duplex_id = ObjectId::from_raw_id(2);
+        let view_for = |hidden| {
+            view_of(&wirehose, &state, &config::Sort::default(), &hidden)
+        };
+
+        let view = view_for(Hidden::default());
+        assert_eq!(view.sources.len(), 3);
+        assert_eq!(view.nodes_input.len(), 2);
+
+        // Duplex nodes are real sources rather than monitors.
+        let view = view_for(Hidden {
+            monitor_sources: true,
+            ..Default::default()
+        });
+        let sources: Vec<_> = view.sources.iter().map(|(t, _)| *t).collect();
+        assert_eq!(
+            sources,
+            vec![Target::Node(duplex_id), Target::Node(source_id)]
+        );
+        assert_eq!(view.nodes_input, vec![source_id, duplex_id]);
+    }
+
+    #[test]
+    fn wrap_navigation() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut view = View::new(&wirehose);
+        let ids: Vec<_> = (0..3).map(ObjectId::from_raw_id).collect();
+        view.nodes_playback = ids.clone();
+        let list_kind = ListKind::Node(NodeKind::Playback);
+
+        assert_eq!(view.next_id(list_kind, None, Some(ids[2])), None);
+        assert_eq!(
+            view.previous_id(list_kind, None, Some(ids[0])),
+            Some(ids[0])
+        );
+
+        view.wrap_navigation = true;
+        assert_eq!(view.next_id(list_kind, None, Some(ids[2])), Some(ids[0]));
+        assert_eq!(view.next_id(list_kind, None, Some(ids[0])), Some(ids[1]));
+        assert_eq!(
+            view.previous_id(list_kind, None, Some(ids[0])),
+            Some(ids[2])
+        );
+        assert_eq!(
+            view.previous_id(list_kind, None, Some(ids[2])),
+            Some(ids[1])
+        );
+    }
+
+    #[test]
+    fn streams_list() {
+        let mut state = state::State::default();
+        let wirehose = mock::WirehoseHandle::default();
+        let media_classes =
+            ["Stream/Output/Audio", "Audio/Sink", "Stream/Input/Audio"];
+        for (i, media_class) in media_classes.into_iter().enumerate() {
+            let object_id = ObjectId::from_raw_id(i as u32);
+            let props = node_props(object_id, media_class, &format!("node{i}"));
+            create_node(&mut state, object_id, props);
+        }
+        let view = view_of(
+            &wirehose,
+            &state,
+            &config::Sort::default(),
+            &Hidden::default(),
+        );
+
+        let streams: Vec<_> = [0, 2].map(ObjectId::from_raw_id).into();
+        assert_eq!(
+            view.object_ids(ListKind::Node(NodeKind::Streams)),
+            streams.as_slice()
+        );
+    }
+
+    #[test]
+    fn hide_streams() {
+        let mut state = state::State::default();
+        let wirehose = mock::WirehoseHandle::default();
+        let stream = |i| ObjectId::from_raw_id(i);
+        // A muted stream, an uncaptured one, and a captured one
+        for i in 0..3 {
+            let props =
+                node_props(stream(i), "Stream/Output/Audio", &format!("s{i}"));
+            create_node(&mut state, stream(i), props);
+        }
+        state.update(StateEvent::NodeMute {
+            object_id: stream(0),
+            mute: true,
+        });
+        for i in [0, 2] {
+            state.update(StateEvent::NodeStreamStarted {
+                object_id: stream(i),
+                rate: 48000,
+                peaks: Arc::new([0.0.into(), 0.0.into()]),
+                clips: Arc::new([]),
+            });
+        }
+        let view_for = |hidden| {
+            view_of(&wirehose, &state, &config::Sort::default(), &hidden)
+        };
+
+        let shown = view_for(Hidden::default());
+        assert_eq!(shown.nodes_playback, vec![stream(0), stream(1), stream(2)]);
+
+        let unmuted = view_for(Hidden {
+            muted_streams: true,
+            ..Default::default()
+        });
+        assert_eq!(unmuted.nodes_playback, vec![stream(1), stream(2)]);
+        assert_eq!(unmuted.nodes_streams, vec![stream(1), stream(2)]);
+        assert_eq!(unmuted.nodes_all.len(), 3);
+
+        let active = view_for(Hidden {
+            idle_streams: true,
+            ..Default::default()
+        });
+        assert_eq!(active.nodes_playback, vec![stream(0), stream(2)]);
+    }
+
+    #[test]
+    fn linked_titles_once_per_node() {
+        let mut state = state::State::default();
+        let classes = ["Stream/Output/Audio", "Audio/Sink"];
+        for (i, media_class) in classes.into_iter().enumerate() {
+            let object_id = ObjectId::from_raw_id(i as u32);
+            let props = node_props(object_id, media_class, &format!("node{i}"));
+            create_node(&mut state, object_id, props);
+        }
+        let stream_id = ObjectId::from_raw_id(0);
+        let sink_id = ObjectId::from_raw_id(1);
+        // One link for each channel
+        for link in [10, 11] {
+            state.update(StateEvent::Link {
+                object_id: ObjectId::from_raw_id(link),
+                output_id: stream_id,
+                input_id: sink_id,
+            });
+        }
+
+        let names = config::Names::default();
+        let titles = |object_ids| linked_titles(&state, &names, object_ids);
+        assert_eq!(titles(state.inputs(sink_id)), vec![String::from("node0")]);
+        assert_eq!(
+            titles(state.outputs(stream_id)),
+            vec![String::from("node1")]
+        );
+        assert!(titles(state.inputs(stream_id)).is_empty());
+    }
+
+    #[test]
+    fn unlinked_balance_moves_volume() {
+        let volumes = [0.5_f32.powi(3), 0.5_f32.powi(3)];
+        assert_eq!(balance(&volumes, 0.25, false), vec![0.25, 0.75]);
+        assert_eq!(balance(&volumes, -1.0, false), vec![1.5, 0.0]);
+    }
+
+    fn node(positions: Option<Vec<u32>>, volumes: Vec<f32>) -> Node {
+        Node {
+            object_id: ObjectId::from_raw_id(1),
+            object_serial: 1,
+            name: String::new(),
+            title: String::new(),
+            media_class: String::new(),
+            routes: None,
+            target_title: String::new(),
+            target: None,
+            volumes,
+            mute: false,
+            peaks: None,
+            clips: None,
+            peaks_dirty: Default::default(),
+            peak_hold: Default::default(),
+            peak_history: Default::default(),
+            capturing: false,
+            locked: false,
+            positions,
+            channel_names: None,
+            format: None,
+            latency_ms: None,
+            process: None,
+            role: None,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            device_info: None,
+            is_default_sink: false,
+            is_default_source: false,
+            is_virtual: false,
+            is_network: false,
+            client_id: None,
+            group: None,
+        }
+    }
+
+    #[test]
+    fn volume_floor() {
+        let percents = |adjustment| {
+            adjusted_volumes(&[1.0, 1.0], adjustment, None, 20.0)
+                .unwrap()
+                .iter()
+                .map(|volume| (volume.cbrt() * 100.0).round())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(percents(VolumeAdjustment::Absolute(0.5)), vec![50.0, 50.0]);
+        assert_eq!(percents(VolumeAdjustment::Absolute(0.1)), vec![20.0, 20.0]);
+        assert_eq!(
+            percents(VolumeAdjustment::Channel {
+                index: 1,
+                value: 0.0
+            }),
+            vec![100.0, 20.0]
+        );
+    }
+
+    #[test]
+    fn volume_rejects_nan() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut view = View::new(&wirehose);
+        let node = node(None, vec![1.0, 1.0]);
+        let node_id = node.object_id;
+        view.nodes.insert(node_id, node);
+
+        for adjustment in [
+            VolumeAdjustment::Absolute(f32::NAN),
+            VolumeAdjustment::Relative(f32::NAN),
+            VolumeAdjustment::Scale(f32::INFINITY),
+            VolumeAdjustment::Channel {
+                index: 0,
+                value: f32::NAN,
+            },
+        ] {
+            assert!(!view.volume(node_id, adjustment, None));
+        }
+        assert!(view.volume(node_id, VolumeAdjustment::Absolute(0.5), None));
+    }
+
+    #[test]
+    fn locked_node_rejects_volume() {
+        let wirehose = mock::WirehoseHandle::default();
+        let mut view = View::new(&wirehose);
+        let node = node(None, vec![1.0, 1.0]);
+        let node_id = node.object_id;
+        view.nodes.insert(node_id, node);
+
+        let adjustment = VolumeAdjustment::Absolute(0.5);
+        assert!(view.volume(node_id, adjustment, None));
+        view.set_locked(&HashSet::from([node_id]));
+        assert!(!view.volume(node_id, adjustment, None));
+        view.set_locked(&HashSet::new());
+        assert!(view.volume(node_id, adjustment, None));
+    }
+
+    #[test]
+    fn front_channels() {
+        let channels = |positions: Option<Vec<u32>>, volumes: Vec<f32>| {
+            node(positions, volumes).front_channels()
+        };
+        // FC, FL, FR, LFE
+        assert_eq!(
+            channels(Some(vec![5, 3, 4, 6]), vec![1.0; 4]),
+            Some((1, 2))
+        );
+        assert_eq!(channels(None, vec![1.0; 2]), Some((0, 1)));
+        assert_eq!(channels(Some(vec![2]), vec![1.0]), None);
+    }
+}
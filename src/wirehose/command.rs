@@ -21,6 +21,8 @@ pub enum Command {
     ),
     NodeCaptureStop(ObjectId),
     MetadataSetProperty(ObjectId, u32, String, Option<String>, Option<String>),
+    SwitchRemote(Option<String>),
+    RereadParams,
 }
 
 /// Trait for sending commands to control PipeWire. The trait exists to
@@ -66,4 +68,6 @@ pub trait CommandSender {
         type_: Option<String>,
         value: Option<String>,
     );
+    fn switch_remote(&self, remote: Option<String>);
+    fn reread_params(&self);
 }
@@ -17,6 +17,14 @@ use crate::wirehose::{
     deserialize::deserialize, ObjectId, PropertyStore, StateEvent,
 };
 
+/// The parameters monitored on each device
+pub const PARAMS: [ParamType; 4] = [
+    ParamType::EnumRoute,
+    ParamType::Route,
+    ParamType::Profile,
+    ParamType::EnumProfile,
+];
+
 pub fn monitor_device(
     registry: &Registry,
     object: &GlobalObject<&DictRef>,
@@ -34,13 +42,6 @@ pub fn monitor_device(
     let device: Device = registry.bind(object).ok()?;
     let device = Rc::new(device);
 
-    let params = [
-        ParamType::EnumRoute,
-        ParamType::Route,
-        ParamType::Profile,
-        ParamType::EnumProfile,
-    ];
-
     let listener = device
         .add_listener_local()
         .param({
@@ -82,14 +83,14 @@ pub fn monitor_device(
                 let Some(device) = device_weak.upgrade() else {
                     return;
                 };
-                for param in params.into_iter() {
+                for param in PARAMS {
                     device.enum_params(0, Some(param), 0, u32::MAX);
                 }
             }
         })
         .register();
 
-    device.subscribe_params(&params);
+    device.subscribe_params(&PARAMS);
 
     Some((device, Box::new(listener)))
 }
@@ -1,3 +1,4 @@
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use pipewire::link::LinkInfoRef;
@@ -15,14 +16,29 @@ pub enum Event {
     State(StateEvent),
     /// An error occurred during monitoring
     Error(PipewireError),
+    /// Connecting to remote after a switch failed, so wirehose is connecting
+    /// to previous again.
+    RemoteSwitchFailed {
+        remote: Option<String>,
+        previous: Option<String>,
+        error: PipewireError,
+    },
     /// The [StateEvent]s representing the PipeWire state at the time of
     /// connection have been sent. wirehose is listening for changes now.
     Ready,
+    /// The connection to PipeWire was lost. The state is no longer valid, and
+    /// wirehose will send it again, followed by [Event::Ready], once it
+    /// reconnects.
+    Disconnected,
 }
 
 #[derive(Debug)]
 /// PipeWire state change events.
 pub enum StateEvent {
+    CoreProperties {
+        props: PropertyStore,
+    },
+
     DeviceEnumRoute {
         object_id: ObjectId,
         index: i32,
@@ -93,12 +109,16 @@ pub enum StateEvent {
         object_id: ObjectId,
         rate: u32,
         peaks: Arc<[AtomicF32]>,
+        /// Latched when a channel clips, until whoever displays it clears it
+        clips: Arc<[AtomicBool]>,
     },
     NodeStreamStopped {
         object_id: ObjectId,
     },
-    NodePeaksDirty {
-        object_id: ObjectId,
+    /// Peaks of one or more capturing nodes have changed. No more of these
+    /// are sent until pending is cleared.
+    PeaksDirty {
+        pending: Arc<AtomicBool>,
     },
 
     Link {
@@ -157,6 +177,9 @@ impl StateEvent {
                 .map(|_| vec![ObjectId::from_raw_id(0)])
                 .unwrap_or_default(),
             StateEvent::MetadataProperty { .. } => vec![],
+            StateEvent::CoreProperties { .. } => {
+                vec![ObjectId::from_raw_id(0)]
+            }
 
             // For the rest only the object_id is affected.
             StateEvent::DeviceEnumRoute { object_id, .. } => {
@@ -196,9 +219,7 @@ impl StateEvent {
             StateEvent::NodeStreamStopped { object_id } => {
                 vec![*object_id]
             }
-            StateEvent::NodePeaksDirty { object_id } => {
-                vec![*object_id]
-            }
+            StateEvent::PeaksDirty { .. } => Vec::new(),
         }
     }
 }
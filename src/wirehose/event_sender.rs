@@ -1,4 +1,6 @@
 use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use pipewire::main_loop::MainLoopWeak;
 
@@ -23,6 +25,8 @@ where
 pub struct EventSender {
     handler: RefCell<Box<dyn EventHandler>>,
     main_loop_weak: MainLoopWeak,
+    /// Set while a PeaksDirty event is waiting to be handled
+    peaks_pending: Arc<AtomicBool>,
 }
 
 impl EventSender {
@@ -33,6 +37,7 @@ impl EventSender {
         Self {
             handler: RefCell::new(Box::new(handler)),
             main_loop_weak,
+            peaks_pending: Default::default(),
         }
     }
 
@@ -44,6 +49,17 @@ impl EventSender {
         }
     }
 
+    /// Reports changed peaks. Streams can process buffers much faster than
+    /// the UI renders, so this sends a single event for all streams until the
+    /// receiver clears the event's pending flag.
+    pub fn send_peaks_dirty(&self) {
+        if !self.peaks_pending.swap(true, Ordering::Relaxed) {
+            self.send(StateEvent::PeaksDirty {
+                pending: Arc::clone(&self.peaks_pending),
+            });
+        }
+    }
+
     pub fn send_ready(&self) {
         if !self.handler.borrow_mut().handle_event(Event::Ready) {
             if let Some(main_loop) = self.main_loop_weak.upgrade() {
@@ -52,6 +68,32 @@ impl EventSender {
         }
     }
 
+    pub fn send_disconnected(&self) {
+        if !self.handler.borrow_mut().handle_event(Event::Disconnected) {
+            if let Some(main_loop) = self.main_loop_weak.upgrade() {
+                main_loop.quit();
+            }
+        }
+    }
+
+    pub fn send_remote_switch_failed(
+        &self,
+        remote: Option<String>,
+        previous: Option<String>,
+        error: String,
+    ) {
+        let event = Event::RemoteSwitchFailed {
+            remote,
+            previous,
+            error,
+        };
+        if !self.handler.borrow_mut().handle_event(event) {
+            if let Some(main_loop) = self.main_loop_weak.upgrade() {
+                main_loop.quit();
+            }
+        }
+    }
+
     pub fn send_error(&self, error: String) {
         if !self.handler.borrow_mut().handle_event(Event::Error(error)) {
             if let Some(main_loop) = self.main_loop_weak.upgrade() {
@@ -3,7 +3,7 @@ use std::rc::Rc;
 use crate::wirehose::event_sender::EventSender;
 use crate::wirehose::proxy_registry::ProxyRegistry;
 use crate::wirehose::stream_registry::StreamRegistry;
-use crate::wirehose::{command::Command, stream};
+use crate::wirehose::{command::Command, device, node, stream};
 
 use pipewire::{core::CoreRc, device::Device, node::Node};
 
@@ -84,6 +84,21 @@ pub fn execute_command(
                 );
             }
         }
+        // The session handles this since it owns the connection.
+        Command::SwitchRemote(_) => {}
+        Command::RereadParams => {
+            // The listeners handle the results like any other update.
+            for proxy in proxies.nodes.values() {
+                for param in node::PARAMS {
+                    proxy.enum_params(0, Some(param), 0, u32::MAX);
+                }
+            }
+            for proxy in proxies.devices.values() {
+                for param in device::PARAMS {
+                    proxy.enum_params(0, Some(param), 0, u32::MAX);
+                }
+            }
+        }
     }
 }
 
@@ -17,6 +17,9 @@ use crate::wirehose::{
     deserialize::deserialize, ObjectId, PropertyStore, StateEvent,
 };
 
+/// The parameters monitored on each node
+pub const PARAMS: [ParamType; 2] = [ParamType::Props, ParamType::PortConfig];
+
 pub fn monitor_node(
     registry: &Registry,
     object: &GlobalObject<&DictRef>,
@@ -72,7 +75,7 @@ pub fn monitor_node(
             }
         })
         .register();
-    node.subscribe_params(&[ParamType::Props, ParamType::PortConfig]);
+    node.subscribe_params(&PARAMS);
 
     Some((node, Box::new(listener)))
 }
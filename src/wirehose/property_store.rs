@@ -161,7 +161,7 @@ macro_rules! define_properties {
                         .and_then(|entry| entry.get_value())
                 }
 
-                #[cfg(test)]
+                #[cfg(any(test, feature = "test-util"))]
                 paste::paste! {
                     pub fn [<set_ $name>](&mut self, value: $type) {
                         self.properties.insert(
@@ -212,6 +212,17 @@ impl PropertyStore {
     pub fn raw(&self, key: &str) -> Option<&str> {
         self.properties.get(key).map(|e| e.raw.as_str())
     }
+
+    /// Get the raw string values of all properties, sorted by key.
+    pub fn entries(&self) -> Vec<(&str, &str)> {
+        let mut entries: Vec<_> = self
+            .properties
+            .iter()
+            .map(|(key, entry)| (key.as_str(), entry.raw.as_str()))
+            .collect();
+        entries.sort_unstable();
+        entries
+    }
 }
 
 impl FromStr for ObjectId {
@@ -2,12 +2,14 @@
 //!
 //! [`Session::spawn()`] starts a PipeWire monitoring thread.
 
-use anyhow::Result;
-use std::cell::RefCell;
+use anyhow::{Context, Result};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::sync::{atomic::AtomicBool, Arc};
 use std::thread;
+use std::time::{Duration, Instant};
 
+use nix::errno::Errno;
 use nix::sys::eventfd::{EfdFlags, EventFd};
 use std::os::fd::AsRawFd;
 
@@ -20,9 +22,22 @@ use crate::wirehose::{
     client, command::Command, device, event_sender::EventSender, execute, link,
     metadata, node, proxy_registry::ProxyRegistry, stream::PeakProcessor,
     stream_registry::StreamRegistry, sync_registry::SyncRegistry,
-    CommandSender, EventHandler, ObjectId, StateEvent,
+    CommandSender, EventHandler, ObjectId, PropertyStore, StateEvent,
 };
 
+/// How long to wait between attempts to reconnect to PipeWire
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Why monitoring stopped.
+enum Exit {
+    /// The session is shutting down, or the event handler is gone.
+    Shutdown,
+    /// The connection to PipeWire was lost.
+    Disconnected,
+    /// A different remote was requested.
+    Switched(Option<String>),
+}
+
 /// Handle for a PipeWire monitoring thread.
 ///
 /// On cleanup, the PipeWire [`MainLoop`](`pipewire::main_loop::MainLoop`) will
@@ -68,6 +83,9 @@ impl Session {
 }
 
 /// Wrapper for handling PipeWire initialization/deinitialization.
+///
+/// If the connection to PipeWire is lost, it is re-established until the
+/// session shuts down.
 fn run<F: EventHandler>(
     remote: Option<String>,
     rx: pipewire::channel::Receiver<Command>,
@@ -83,15 +101,68 @@ fn run<F: EventHandler>(
     let main_loop = MainLoopRc::new(None)?;
     let sender = Rc::new(EventSender::new(handler, main_loop.downgrade()));
 
-    let err_sender = Rc::clone(&sender);
-    monitor_pipewire(remote, main_loop, sender, rx, shutdown_fd)
-        .unwrap_or_else(move |e| {
-            err_sender.send_error(e.to_string());
-        });
+    let mut rx = Some(rx);
+    let mut reconnecting = false;
+    let mut remote = remote;
+    // The remote to go back to if a switch fails
+    let mut previous = None;
+    loop {
+        let result = monitor_pipewire(
+            remote.clone(),
+            main_loop.clone(),
+            Rc::clone(&sender),
+            &mut rx,
+            Arc::clone(&shutdown_fd),
+        );
+        match result {
+            Ok(Exit::Shutdown) => break,
+            Ok(Exit::Disconnected) => {
+                reconnecting = true;
+                previous = None;
+            }
+            Ok(Exit::Switched(new_remote)) => {
+                reconnecting = true;
+                previous = Some(std::mem::replace(&mut remote, new_remote));
+                continue;
+            }
+            Err(e) if previous.is_some() && rx.is_some() => {
+                let previous = previous.take().flatten();
+                sender.send_remote_switch_failed(
+                    std::mem::replace(&mut remote, previous.clone()),
+                    previous,
+                    e.to_string(),
+                );
+                continue;
+            }
+            // Keep trying while the daemon restarts, but give up if the
+            // first connection fails.
+            Err(_) if reconnecting && rx.is_some() => {}
+            Err(e) => {
+                sender.send_error(e.to_string());
+                break;
+            }
+        }
+        if wait_for_shutdown(&shutdown_fd, RECONNECT_DELAY) {
+            break;
+        }
+    }
 
     Ok(())
 }
 
+/// Waits for delay. Returns true if the session was shut down in the
+/// meantime.
+fn wait_for_shutdown(shutdown_fd: &EventFd, delay: Duration) -> bool {
+    let deadline = Instant::now() + delay;
+    while Instant::now() < deadline {
+        if shutdown_fd.read().is_ok() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
+
 impl Drop for Session {
     /// Shut down the PipeWire monitoring thread.
     fn drop(&mut self) {
@@ -205,18 +276,32 @@ impl CommandSender for Session {
             object_id, subject, key, type_, value,
         ));
     }
+
+    /// Disconnect and connect to another remote. If the new remote can't be
+    /// reached, the previous one is reconnected.
+    fn switch_remote(&self, remote: Option<String>) {
+        let _ = self.tx.send(Command::SwitchRemote(remote));
+    }
+
+    /// Read the parameters of every node and device again, in case an
+    /// update was missed.
+    fn reread_params(&self) {
+        let _ = self.tx.send(Command::RereadParams);
+    }
 }
 
 /// Monitors PipeWire.
 ///
-/// Sets up core listeners and runs the PipeWire main loop.
+/// Sets up core listeners and runs the PipeWire main loop. The command
+/// receiver is taken from rx while running and put back if the connection is
+/// lost, so that it can be used again after reconnecting.
 fn monitor_pipewire(
     remote: Option<String>,
     main_loop: MainLoopRc,
     sender: Rc<EventSender>,
-    rx: pipewire::channel::Receiver<Command>,
+    rx: &mut Option<pipewire::channel::Receiver<Command>>,
     shutdown_fd: Arc<EventFd>,
-) -> Result<()> {
+) -> Result<Exit> {
     let context = pipewire::context::ContextRc::new(&main_loop, None)?;
     let props = remote.map(|remote| {
         properties! {
@@ -239,9 +324,25 @@ fn monitor_pipewire(
             });
 
     let syncs = Rc::new(RefCell::new(SyncRegistry::default()));
+    let disconnected = Rc::new(Cell::new(false));
+    // The remote to switch to, if one was requested
+    let switch_to = Rc::new(RefCell::new(None));
 
     let _core_listener = core
         .add_listener_local()
+        .info({
+            let sender_weak = Rc::downgrade(&sender);
+            move |info| {
+                let Some(sender) = sender_weak.upgrade() else {
+                    return;
+                };
+                if let Some(props) = info.props() {
+                    sender.send(StateEvent::CoreProperties {
+                        props: PropertyStore::from(props),
+                    });
+                }
+            }
+        })
         .done({
             let sender_weak = Rc::downgrade(&sender);
             let syncs_weak = Rc::downgrade(&syncs);
@@ -259,10 +360,24 @@ fn monitor_pipewire(
         })
         .error({
             let sender_weak = Rc::downgrade(&sender);
-            move |_id, _seq, _res, message| {
-                if let Some(sender) = sender_weak.upgrade() {
-                    sender.send_error(message.to_string());
+            let main_loop_weak = main_loop.downgrade();
+            let disconnected = Rc::clone(&disconnected);
+            move |id, _seq, res, message| {
+                let Some(sender) = sender_weak.upgrade() else {
+                    return;
                 };
+                // The core reports a broken pipe when the daemon goes away.
+                if id == pipewire::core::PW_ID_CORE
+                    && res == -(Errno::EPIPE as i32)
+                {
+                    disconnected.set(true);
+                    sender.send_disconnected();
+                    if let Some(main_loop) = main_loop_weak.upgrade() {
+                        main_loop.quit();
+                    }
+                    return;
+                }
+                sender.send_error(message.to_string());
             }
         })
         .register();
@@ -451,27 +566,50 @@ fn monitor_pipewire(
         .register();
 
     let proxies = Rc::clone(&proxies);
-    let _receiver = rx.attach(main_loop.loop_(), {
-        let sender_weak = Rc::downgrade(&sender);
-        let streams_weak = Rc::downgrade(&streams);
-        move |command| {
-            let Some(sender) = sender_weak.upgrade() else {
-                return;
-            };
-            let Some(streams) = streams_weak.upgrade() else {
-                return;
-            };
-            execute::execute_command(
-                &core,
-                sender,
-                &mut streams.borrow_mut(),
-                &Rc::clone(&proxies).borrow(),
-                command,
-            );
-        }
-    });
+    let receiver = rx
+        .take()
+        .context("PipeWire command receiver is missing")?
+        .attach(main_loop.loop_(), {
+            let sender_weak = Rc::downgrade(&sender);
+            let streams_weak = Rc::downgrade(&streams);
+            let main_loop_weak = main_loop.downgrade();
+            let switch_to = Rc::clone(&switch_to);
+            move |command| {
+                let Some(sender) = sender_weak.upgrade() else {
+                    return;
+                };
+                if let Command::SwitchRemote(remote) = command {
+                    *switch_to.borrow_mut() = Some(remote);
+                    sender.send_disconnected();
+                    if let Some(main_loop) = main_loop_weak.upgrade() {
+                        main_loop.quit();
+                    }
+                    return;
+                }
+                let Some(streams) = streams_weak.upgrade() else {
+                    return;
+                };
+                execute::execute_command(
+                    &core,
+                    sender,
+                    &mut streams.borrow_mut(),
+                    &Rc::clone(&proxies).borrow(),
+                    command,
+                );
+            }
+        });
 
     main_loop.run();
 
-    Ok(())
+    if let Some(remote) = switch_to.take() {
+        *rx = Some(receiver.deattach());
+        return Ok(Exit::Switched(remote));
+    }
+
+    if disconnected.get() {
+        *rx = Some(receiver.deattach());
+        return Ok(Exit::Disconnected);
+    }
+
+    Ok(Exit::Shutdown)
 }
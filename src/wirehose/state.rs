@@ -57,6 +57,7 @@ pub struct Node {
     pub volumes: Option<Vec<f32>>,
     pub mute: Option<bool>,
     pub peaks: Option<Arc<[AtomicF32]>>,
+    pub clips: Option<Arc<[AtomicBool]>>,
     pub peaks_dirty: Arc<AtomicBool>,
     pub rate: Option<u32>,
     pub positions: Option<Vec<u32>>,
@@ -89,6 +90,8 @@ pub enum CaptureEligibility {
 ///
 /// This is primarily for maintaining a representation of the PipeWire state.
 pub struct State {
+    /// Properties of the PipeWire core, such as its name and version
+    pub core: PropertyStore,
     pub clients: HashMap<ObjectId, Client>,
     pub nodes: HashMap<ObjectId, Node>,
     pub devices: HashMap<ObjectId, Device>,
@@ -106,6 +109,9 @@ impl State {
         let mut capture_eligibility = Vec::new();
 
         match event {
+            StateEvent::CoreProperties { props } => {
+                self.core = props;
+            }
             StateEvent::ClientProperties { object_id, props } => {
                 self.client_entry(object_id).props = props;
             }
@@ -208,17 +214,20 @@ impl State {
                 object_id,
                 rate,
                 peaks,
+                clips,
             } => {
                 self.node_entry(object_id).rate = Some(rate);
                 self.node_entry(object_id).peaks = Some(peaks);
+                self.node_entry(object_id).clips = Some(clips);
             }
             StateEvent::NodeStreamStopped { object_id } => {
                 // It's likely that the node doesn't exist anymore.
-                self.nodes
-                    .entry(object_id)
-                    .and_modify(|node| node.peaks = None);
+                self.nodes.entry(object_id).and_modify(|node| {
+                    node.peaks = None;
+                    node.clips = None;
+                });
             }
-            StateEvent::NodePeaksDirty { object_id: _ } => {
+            StateEvent::PeaksDirty { .. } => {
                 // This message just wakes up the App.
             }
             StateEvent::Link {
@@ -357,6 +366,17 @@ impl State {
             .collect()
     }
 
+    /// Returns true if a node is eligible for capture as things stand - always
+    /// for sources and streams, and for sinks only while they have inputs.
+    pub fn is_capture_eligible(&self, object_id: ObjectId) -> bool {
+        let Some(node) = self.nodes.get(&object_id) else {
+            return false;
+        };
+        self.on_node(node).is_some()
+            || (self.on_link(node).is_some()
+                && !self.inputs(object_id).is_empty())
+    }
+
     /// Call when a node's capture eligibility might have changed.
     fn on_node(&self, node: &Node) -> Option<CaptureEligibility> {
         if !node
@@ -51,6 +51,8 @@ where
 pub struct StreamData {
     format: AudioInfoRaw,
     peaks: Arc<[AtomicF32]>,
+    /// Set when a channel's unprocessed peak reaches full scale
+    clips: Arc<[AtomicBool]>,
     peaks_dirty: Arc<AtomicBool>,
     peak_processor: Option<Arc<dyn PeakProcessor>>,
     buffers_seen: u8,
@@ -85,6 +87,17 @@ fn find_peak(samples: &[f32]) -> f32 {
     Arch::new().dispatch(Max(samples))
 }
 
+/// Clamps a peak to [0, 1], replacing NaN and infinity with 0. Glitching
+/// devices can produce garbage samples, which would otherwise stick in the
+/// meters through the peak processor.
+fn sanitize_peak(peak: f32) -> f32 {
+    if peak.is_finite() {
+        peak.clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
 pub fn capture_node(
     core: &CoreRc,
     sender: &Rc<EventSender>,
@@ -106,6 +119,7 @@ pub fn capture_node(
     let data = StreamData {
         format: Default::default(),
         peaks: Default::default(),
+        clips: Default::default(),
         peaks_dirty,
         peak_processor,
         buffers_seen: 0,
@@ -144,6 +158,9 @@ pub fn capture_node(
                 user_data.peaks = (0..user_data.format.channels())
                     .map(|_| AtomicF32::new(0.0))
                     .collect::<Arc<[_]>>();
+                user_data.clips = (0..user_data.format.channels())
+                    .map(|_| AtomicBool::new(false))
+                    .collect::<Arc<[_]>>();
 
                 let Some(sender) = sender_weak.upgrade() else {
                     return;
@@ -152,6 +169,7 @@ pub fn capture_node(
                     object_id,
                     rate: user_data.format.rate(),
                     peaks: Arc::clone(&user_data.peaks),
+                    clips: Arc::clone(&user_data.clips),
                 });
             }
         })
@@ -206,7 +224,12 @@ pub fn capture_node(
                         n_samples = samples.len() as u32;
                     }
 
-                    let new_peak = find_peak(samples);
+                    let new_peak = sanitize_peak(find_peak(samples));
+
+                    // Latch clips before ballistics can smooth them away.
+                    if new_peak >= 1.0 {
+                        user_data.clips[c].store(true, Ordering::Relaxed);
+                    }
 
                     if let Some(peak_processor) = &user_data.peak_processor {
                         let _ = user_data.peaks[c].fetch_update(|current| {
@@ -216,21 +239,20 @@ pub fn capture_node(
                             } else {
                                 current
                             };
-                            Some(peak_processor.process_peak(
+                            Some(sanitize_peak(peak_processor.process_peak(
                                 new_peak,
                                 current,
                                 n_samples,
                                 user_data.format.rate(),
-                            ))
+                            )))
                         });
                     } else {
                         user_data.peaks[c].store(new_peak);
                     }
                 }
 
-                if !user_data.peaks_dirty.swap(true, Ordering::Relaxed) {
-                    sender.send(StateEvent::NodePeaksDirty { object_id });
-                }
+                user_data.peaks_dirty.store(true, Ordering::Relaxed);
+                sender.send_peaks_dirty();
             }
         })
         .register()
@@ -266,3 +288,17 @@ pub fn capture_node(
 
     Some((stream, listener))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_peak_is_finite() {
+        assert_eq!(sanitize_peak(f32::NAN), 0.0);
+        assert_eq!(sanitize_peak(f32::INFINITY), 0.0);
+        assert_eq!(sanitize_peak(f32::NEG_INFINITY), 0.0);
+        assert_eq!(sanitize_peak(1.5), 1.0);
+        assert_eq!(sanitize_peak(0.5), 0.5);
+    }
+}
@@ -0,0 +1,98 @@
+//! Drives a [`Mixer`] through its public API, as an embedder would, against
+//! the mock command sender.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use wiremix::config::Config;
+use wiremix::mixer::Mixer;
+use wiremix::mock::{self, MockCommand};
+use wiremix::opt::Opt;
+use wiremix::wirehose::{Event, ObjectId, PropertyStore, StateEvent};
+
+fn config() -> Config {
+    Config::try_new(None, &Opt::default()).unwrap()
+}
+
+fn send_sink(tx: &mpsc::Sender<Event>, object_id: ObjectId) {
+    let mut props = PropertyStore::default();
+    props.set_media_class(String::from("Audio/Sink"));
+    props.set_node_name(String::from("alsa_output.speakers"));
+    props.set_node_description(String::from("Speakers"));
+    props.set_object_serial(0);
+    for event in [
+        StateEvent::NodeProperties { object_id, props },
+        StateEvent::NodeVolumes {
+            object_id,
+            volumes: vec![1.0, 1.0],
+        },
+        StateEvent::NodeMute {
+            object_id,
+            mute: false,
+        },
+        StateEvent::MetadataMetadataName {
+            object_id: ObjectId::from_raw_id(1),
+            metadata_name: String::from("default"),
+        },
+    ] {
+        tx.send(Event::State(event)).unwrap();
+    }
+}
+
+#[test]
+fn commands_by_node_name() {
+    let commands = RefCell::new(VecDeque::new());
+    let wirehose = mock::WirehoseHandle::with_commands(&commands);
+    let (tx, rx) = mpsc::channel();
+    let mixer = Mixer::with_sender(wirehose, rx, config());
+
+    let object_id = ObjectId::from_raw_id(0);
+    send_sink(&tx, object_id);
+    tx.send(Event::Ready).unwrap();
+    mixer.wait_until_ready(Duration::from_secs(5)).unwrap();
+
+    let snapshot = mixer.snapshot();
+    let node = snapshot.node("Speakers").unwrap();
+    assert_eq!(node.name, "alsa_output.speakers");
+    assert_eq!(node.volumes, vec![1.0, 1.0]);
+    assert!(!node.mute);
+
+    mixer.set_volume("Speakers", 50.0).unwrap();
+    mixer.mute("alsa_output.speakers", true).unwrap();
+    // Already unmuted, so nothing is sent.
+    mixer.mute("Speakers", false).unwrap();
+    mixer.set_default("Speakers").unwrap();
+    assert!(mixer.mute("Headphones", true).is_err());
+
+    let mut commands = commands.borrow_mut();
+    assert_eq!(
+        commands.pop_front(),
+        Some(MockCommand::NodeVolumes(object_id, vec![0.125, 0.125]))
+    );
+    assert_eq!(
+        commands.pop_front(),
+        Some(MockCommand::NodeMute(object_id, true))
+    );
+    assert!(matches!(
+        commands.pop_front(),
+        Some(MockCommand::MetadataSetProperty(key, _))
+            if key == "default.configured.audio.sink"
+    ));
+    assert!(commands.is_empty());
+}
+
+#[test]
+fn error_before_ready() {
+    let wirehose = mock::WirehoseHandle::default();
+    let (tx, rx) = mpsc::channel();
+    let mixer = Mixer::with_sender(wirehose, rx, config());
+
+    tx.send(Event::Error(String::from("no socket"))).unwrap();
+    let error = mixer.wait_until_ready(Duration::from_secs(5));
+    assert_eq!(error.unwrap_err().to_string(), "no socket");
+
+    drop(tx);
+    assert!(mixer.wait_until_ready(Duration::ZERO).is_err());
+}